@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// The inclusive range a secret is drawn from and guesses must fall in.
+///
+/// Bounds are i128 so that ranges may be negative or reach the limits of i64 and i128 themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    min: i128,
+    max: i128,
+}
+
+impl Bounds {
+    /// The range the book's version of the game used.
+    pub const DEFAULT: Bounds = Bounds { min: 1, max: 100 };
+
+    /// Builds the range `min..=max`; `min` must be strictly smaller than `max` so there is something to guess.
+    pub fn new(min: i128, max: i128) -> Result<Bounds, String> {
+        if min < max {
+            Ok(Bounds { min, max })
+        } else {
+            Err(format!(
+                "the minimum ({min}) must be smaller than the maximum ({max})"
+            ))
+        }
+    }
+
+    pub fn min(&self) -> i128 {
+        self.min
+    }
+
+    pub fn max(&self) -> i128 {
+        self.max
+    }
+
+    pub fn contains(&self, value: i128) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// `max - min`, which is one less than the number of values in the range.
+    /// The count itself can be 2^128 for the full i128 range, which no integer type holds, so the width is what we keep.
+    pub fn width(&self) -> u128 {
+        self.max.abs_diff(self.min)
+    }
+}
+
+impl Default for Bounds {
+    fn default() -> Bounds {
+        Bounds::DEFAULT
+    }
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} and {}", self.min, self.max)
+    }
+}
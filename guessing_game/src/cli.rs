@@ -0,0 +1,738 @@
+// Command-line options for the guessing_game binary. std::env::args gives us the arguments as an iterator of Strings; the first one is the program name.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::bounds::Bounds;
+use crate::codes::CodeRules;
+use crate::difficulty::{Difficulty, Rules};
+use crate::floats::FloatRules;
+use crate::game::Redundancy;
+use crate::grid::GridRules;
+use crate::hints::{Feedback, Thresholds};
+use crate::locale::Language;
+use crate::protocol::Protocol;
+use crate::strategy;
+
+pub const USAGE: &str = "\
+Usage: guessing_game [COMMAND] [OPTIONS]
+
+Commands:
+  play           Play the game (the default); type :help while playing for the commands
+                 (:history, :range, :hint, :undo, :giveup, :new)
+  tui            Play full screen: the numbers still possible, your guesses so far and
+                 the attempts left, redrawn after every key
+  reverse        Think of a number between --min and --max and let the computer guess it
+  evil           Guess against a host that puts off choosing the secret and always
+                 gives the answer that leaves you the most numbers to search
+  bulls          Crack a secret code: bulls are right symbols in the right place,
+                 cows are right symbols in the wrong place
+  grid           Find a point on a grid: each guess x,y is answered with the compass direction
+                 to the secret (N, NE, E, ... or hit)
+  words          Guess a word from a list: each guess is answered earlier or later in the alphabet
+  float          Guess a real number: a guess wins when it's within --tolerance of the secret
+  stats          Show win rate, best score and an attempts histogram for each player,
+                 and for each word list
+  leaderboard    Show the best scores for each difficulty
+  check-translations
+                 List the messages each language is missing (they're shown in English)
+  serve          Host a multiplayer race over TCP
+  join <addr>    Join a multiplayer race at host:port
+  replay <file>  Play a transcript made with --record again and report any divergence
+  tournament     Play thousands of seeded games with each guessing strategy (binary, ternary,
+                 random, linear) and compare how many attempts they take
+  http           Serve games as JSON resources over HTTP (see src/http.rs)
+
+Options:
+  --difficulty <level>  easy (1-20, 8 attempts), normal (1-100, 10 attempts, the default),
+                        hard (1-1000, 10 attempts) or custom
+  --min <n>             Smallest possible secret (default 1; may be negative)
+  --max <n>             Largest possible secret (default 100)
+  --attempts <n>        Attempts allowed per round (default unlimited for custom)
+  --seed <u64>          Seed the random number generator so a session can be replayed
+  --reveal              Show the secret and the game's internal state while playing
+  --autoplay            Let binary search play one round and show each step
+  --hints <kind>        How misses are answered: direction (too small/too big, the default)
+                        or hot-cold (burning, warm or cold, and warmer or colder than the last guess)
+  --thresholds <b,w>    For hot-cold, how close counts as burning and as warm, in percent
+                        of the range (default 5,20)
+  --length <n>          Symbols in a bulls code (default 4)
+  --alphabet <symbols>  Symbols a bulls code is made of (default 0123456789)
+  --repeats             Let a bulls code use a symbol more than once
+  --grid <WxH>          The size of the grid in grid mode (default 10x10)
+  --word-list <file>    Words for the words command, one per line (default: a bundled English list)
+  --float-range <a,b>   The range of the secret in float mode (default 1,100); decimals and
+                        scientific notation like 1e-3 both work
+  --tolerance <t>       How close a float guess has to be: absolute like 0.01 (the default),
+                        or relative to the secret like 0.5%
+  --map                 In grid mode, draw the grid after each answer with the ruled-out points shaded
+  --redundant <policy>  What happens to a guess earlier answers already ruled out: warn (answer it,
+                        count it and say so, the default), free (reject it without counting it)
+                        or allow (answer it silently). Type :range while playing to see what's left
+  --undos <n>           Guesses :undo may take back per round (default 3 on easy and custom,
+                        1 on normal, 0 on hard)
+  --record <file>       Write a transcript of the session (seed, settings, input and messages)
+  --lang <code>         Language play talks in: en, fr or de (default from LC_ALL,
+                        LC_MESSAGES or LANG, else en)
+  --protocol <format>   How play talks: text (the default) or jsonl, one JSON object per line
+                        each way, for programs (the schema is described in src/protocol.rs)
+  --player <name>       Name recorded with each round (default $USER); for stats, only show this player
+  --data-file <path>    Where finished rounds are recorded
+                        (default $XDG_DATA_HOME/guessing_game/rounds.tsv)
+  --host <addr>         Address serve and http listen on (default 127.0.0.1, this machine only)
+  --port <n>            Port serve listens on (default 7878), or http (default 7879)
+  --timeout <secs>      Disconnect players idle for this long (default 300);
+                        for http, forget games untouched for this long (default 600)
+  --rounds <n>          Stop serving after this many rounds (default: never)
+  --games <n>           Games each strategy plays in a tournament (default 1000)
+  --strategies <list>   Strategies in a tournament, separated by commas (default all of them)
+  -h, --help            Print this help
+
+Giving --min, --max or --attempts makes the difficulty custom.
+Bulls, grid, words and float rounds are unlimited unless --attempts is given. With --autoplay a
+solver plays them: it cracks the code, or binary searches the word list, the float range, or both
+axes of the grid at once.
+A win scores 1000 when it takes no more guesses than binary search would need, less the more it takes beyond that.
+
+While playing, type `give up` to end the round and see the secret, `range` to see the numbers
+still possible, or `quit` (or `q`) to leave. Commands start with a colon, and any unique start of
+a name works: :history [n], :range, :hint (costs 100 points), :undo, :giveup, :new, :help, :quit.
+
+Exit status:
+  0  the session ended normally
+  2  the arguments were invalid
+  3  the player quit
+  4  input ended (end of file or Ctrl-D) in the middle of a round
+  5  replay found a divergence from the transcript
+
+Environment:
+  GUESSING_GAME_SEED   Seed to use when --seed is not given
+  GUESSING_GAME_DEBUG  Set to anything but 0 or an empty string to act like --reveal
+  LC_ALL, LC_MESSAGES, LANG
+                       The locale, like fr_FR.UTF-8, which picks the language when --lang is not given";
+
+/// Exit status when the player types `quit`.
+pub const EXIT_QUIT: i32 = 3;
+/// Exit status when stdin runs out in the middle of a round.
+pub const EXIT_END_OF_INPUT: i32 = 4;
+/// Exit status when a replayed transcript no longer matches the engine.
+pub const EXIT_DIVERGED: i32 = 5;
+
+/// The environment variable read when `--seed` is not given.
+pub const SEED_ENV: &str = "GUESSING_GAME_SEED";
+/// The environment variable that turns on `--reveal`.
+pub const DEBUG_ENV: &str = "GUESSING_GAME_DEBUG";
+
+/// What the binary should do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    Tui,
+    Reverse,
+    Evil,
+    Bulls,
+    Grid,
+    Words,
+    Float,
+    Stats,
+    Leaderboard,
+    CheckTranslations,
+    Serve,
+    Join,
+    Replay,
+    Tournament,
+    Http,
+}
+
+/// Everything the binary was asked to do.
+///
+/// Only PartialEq: the float rules hold f64s, which aren't Eq.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub command: Command,
+    pub difficulty: Difficulty,
+    pub rules: Rules,
+    /// The seed for the game's generator; `None` means pick one at random.
+    pub seed: Option<u64>,
+    /// Show the secret, attempts and remaining interval before every prompt.
+    pub reveal: bool,
+    /// Let the solver play instead of reading guesses.
+    pub autoplay: bool,
+    /// The shape of the code in `bulls`.
+    pub code: CodeRules,
+    /// The size of the grid in `grid`.
+    pub grid: GridRules,
+    /// Draw the grid after each answer in `grid`.
+    pub map: bool,
+    /// The range and tolerance in `float`.
+    pub floats: FloatRules,
+    /// The file `words` draws secrets from; `None` means the bundled list.
+    pub word_list: Option<PathBuf>,
+    /// How play answers a guess that missed.
+    pub feedback: Feedback,
+    /// Whether play talks in sentences or in JSON lines.
+    pub protocol: Protocol,
+    /// What happens to guesses that earlier answers already ruled out.
+    pub redundancy: Redundancy,
+    /// How many guesses :undo may take back each round.
+    pub undos: u32,
+    /// The language of play's sentences.
+    pub language: Language,
+    /// The name recorded with each round; `None` means use `$USER`.
+    pub player: Option<String>,
+    /// Overrides the default data file location.
+    pub data_file: Option<PathBuf>,
+    /// The address to listen on for `serve` and `http`; `None` means localhost.
+    pub host: Option<String>,
+    /// The port to listen on for `serve` and `http`; `None` means the server's `DEFAULT_PORT`.
+    pub port: Option<u16>,
+    /// How long `serve` waits on a silent client, or `http` keeps an untouched game; `None` means the server's default.
+    pub idle_timeout: Option<Duration>,
+    /// How many rounds `serve` plays before stopping.
+    pub rounds: Option<u32>,
+    /// How many games each strategy plays in `tournament`.
+    pub games: Option<u32>,
+    /// The strategies in `tournament`; empty means all of them.
+    pub strategies: Vec<String>,
+    /// The `host:port` given to `join`.
+    pub address: Option<String>,
+    /// Where `play` writes a transcript.
+    pub record: Option<PathBuf>,
+    /// The transcript given to `replay`.
+    pub transcript: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl Config {
+    /// Parses the arguments after the program name.
+    ///
+    /// `env` looks up an environment variable by name (the binary passes `std::env::var`); flags win over the environment.
+    pub fn parse<I, E>(args: I, env: E) -> Result<Config, String>
+    where
+        I: IntoIterator<Item = String>,
+        E: Fn(&str) -> Option<String>,
+    {
+        let mut config = Config::default();
+
+        if let Some(value) = env(DEBUG_ENV) {
+            config.reveal = !value.is_empty() && value != "0";
+        }
+        config.language = Language::from_env(&env);
+
+        let mut difficulty = None;
+        let mut min = None;
+        let mut max = None;
+        let mut attempts = None;
+        let mut hot_cold = false;
+        let mut thresholds = None;
+        let mut code_length = None;
+        let mut alphabet = None;
+        let mut repeats = false;
+        let mut redundancy = None;
+        let mut undos = None;
+        let mut float_range = None;
+        let mut tolerance = None;
+
+        let mut args = args.into_iter().peekable();
+
+        // The command, if any, comes before the options.
+        if let Some(first) = args.peek() {
+            let command = match first.as_str() {
+                "play" => Some(Command::Play),
+                "reverse" => Some(Command::Reverse),
+                "evil" => Some(Command::Evil),
+                "bulls" => Some(Command::Bulls),
+                "grid" => Some(Command::Grid),
+                "words" => Some(Command::Words),
+                "float" => Some(Command::Float),
+                "tui" => Some(Command::Tui),
+                "stats" => Some(Command::Stats),
+                "leaderboard" => Some(Command::Leaderboard),
+                "check-translations" => Some(Command::CheckTranslations),
+                "serve" => Some(Command::Serve),
+                "join" => Some(Command::Join),
+                "replay" => Some(Command::Replay),
+                "tournament" => Some(Command::Tournament),
+                "http" => Some(Command::Http),
+                _ => None,
+            };
+            if let Some(command) = command {
+                config.command = command;
+                args.next();
+            }
+        }
+
+        while let Some(arg) = args.next() {
+            // Both `--seed 42` and `--seed=42` are accepted.
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+
+            match flag.as_str() {
+                "--difficulty" => {
+                    difficulty = Some(value_for(&flag, inline_value, &mut args)?.parse()?)
+                }
+                "--min" => {
+                    min = Some(parse_bound(
+                        &value_for(&flag, inline_value, &mut args)?,
+                        "--min",
+                    )?)
+                }
+                "--max" => {
+                    max = Some(parse_bound(
+                        &value_for(&flag, inline_value, &mut args)?,
+                        "--max",
+                    )?)
+                }
+                "--attempts" => {
+                    attempts = Some(parse_count(
+                        &value_for(&flag, inline_value, &mut args)?,
+                        "--attempts",
+                    )?)
+                }
+                "--seed" => {
+                    let value = value_for(&flag, inline_value, &mut args)?;
+                    config.seed = Some(parse_seed(&value, "--seed")?);
+                }
+                "--reveal" => config.reveal = true,
+                "--autoplay" => config.autoplay = true,
+                "--length" => {
+                    code_length = Some(parse_count(
+                        &value_for(&flag, inline_value, &mut args)?,
+                        "--length",
+                    )?)
+                }
+                "--alphabet" => alphabet = Some(value_for(&flag, inline_value, &mut args)?),
+                "--repeats" => repeats = true,
+                "--grid" => config.grid = value_for(&flag, inline_value, &mut args)?.parse()?,
+                "--map" => config.map = true,
+                "--float-range" => {
+                    float_range = Some(parse_float_range(&value_for(
+                        &flag,
+                        inline_value,
+                        &mut args,
+                    )?)?)
+                }
+                "--tolerance" => {
+                    tolerance = Some(value_for(&flag, inline_value, &mut args)?.parse()?)
+                }
+                "--word-list" => {
+                    config.word_list =
+                        Some(PathBuf::from(value_for(&flag, inline_value, &mut args)?))
+                }
+                "--hints" => {
+                    let value = value_for(&flag, inline_value, &mut args)?;
+                    hot_cold = match value.trim() {
+                        "direction" => false,
+                        "hot-cold" => true,
+                        _ => {
+                            return Err(format!(
+                                "unknown hints '{value}' (expected direction or hot-cold)"
+                            ))
+                        }
+                    }
+                }
+                "--thresholds" => {
+                    thresholds = Some(value_for(&flag, inline_value, &mut args)?.parse()?)
+                }
+                "--record" => {
+                    config.record = Some(PathBuf::from(value_for(&flag, inline_value, &mut args)?))
+                }
+                "--redundant" => {
+                    redundancy = Some(value_for(&flag, inline_value, &mut args)?.parse()?)
+                }
+                // Unlike the other counts, 0 makes sense here: no undos at all.
+                "--undos" => {
+                    let value = value_for(&flag, inline_value, &mut args)?;
+                    undos =
+                        Some(value.trim().parse().map_err(|_| {
+                            format!("--undos must be a whole number, got '{value}'")
+                        })?)
+                }
+                "--lang" => config.language = value_for(&flag, inline_value, &mut args)?.parse()?,
+                "--protocol" => {
+                    config.protocol = value_for(&flag, inline_value, &mut args)?.parse()?
+                }
+                "--player" => config.player = Some(value_for(&flag, inline_value, &mut args)?),
+                "--data-file" => {
+                    config.data_file =
+                        Some(PathBuf::from(value_for(&flag, inline_value, &mut args)?))
+                }
+                "--host" => config.host = Some(value_for(&flag, inline_value, &mut args)?),
+                "--port" => {
+                    let value = value_for(&flag, inline_value, &mut args)?;
+                    let port = value.trim().parse().map_err(|_| {
+                        format!("--port must be a whole number between 0 and 65535, got '{value}'")
+                    })?;
+                    config.port = Some(port);
+                }
+                "--timeout" => {
+                    let seconds =
+                        parse_count(&value_for(&flag, inline_value, &mut args)?, "--timeout")?;
+                    config.idle_timeout = Some(Duration::from_secs(seconds.into()));
+                }
+                "--rounds" => {
+                    config.rounds = Some(parse_count(
+                        &value_for(&flag, inline_value, &mut args)?,
+                        "--rounds",
+                    )?)
+                }
+                "--games" => {
+                    config.games = Some(parse_count(
+                        &value_for(&flag, inline_value, &mut args)?,
+                        "--games",
+                    )?)
+                }
+                "--strategies" => {
+                    config.strategies = Vec::new();
+                    for name in value_for(&flag, inline_value, &mut args)?.split(',') {
+                        let name = name.trim();
+                        if !strategy::NAMES.contains(&name) {
+                            return Err(format!(
+                                "unknown strategy '{name}' (expected {})",
+                                strategy::NAMES.join(", ")
+                            ));
+                        }
+                        config.strategies.push(name.to_string());
+                    }
+                }
+                "-h" | "--help" => config.help = true,
+                _ if config.command == Command::Join
+                    && config.address.is_none()
+                    && !flag.starts_with('-') =>
+                {
+                    config.address = Some(flag)
+                }
+                _ if config.command == Command::Replay
+                    && config.transcript.is_none()
+                    && !flag.starts_with('-') =>
+                {
+                    config.transcript = Some(PathBuf::from(flag))
+                }
+                _ => return Err(format!("unknown argument '{flag}'")),
+            }
+        }
+
+        // Read only when --seed wasn't given, so a stale or broken value left in the environment can't get in the way of the flag.
+        if config.seed.is_none() {
+            if let Some(value) = env(SEED_ENV) {
+                config.seed = Some(parse_seed(&value, SEED_ENV)?);
+            }
+        }
+
+        let customised = min.is_some() || max.is_some() || attempts.is_some();
+        config.difficulty = match difficulty {
+            Some(preset) if preset != Difficulty::Custom && customised => {
+                return Err(format!(
+                    "--min, --max and --attempts can't be combined with --difficulty {preset}"
+                ))
+            }
+            Some(chosen) => chosen,
+            None if customised => Difficulty::Custom,
+            None => Difficulty::default(),
+        };
+
+        let preset = config.difficulty.rules();
+        config.rules = Rules {
+            bounds: Bounds::new(
+                min.unwrap_or(preset.bounds.min()),
+                max.unwrap_or(preset.bounds.max()),
+            )?,
+            max_attempts: attempts.or(preset.max_attempts),
+        };
+
+        // Hot/cold hints never say which way the secret lies, so the player can't know a guess was ruled out, and warning them would give the direction away.
+        config.redundancy = match (hot_cold, redundancy) {
+            (false, redundancy) => redundancy.unwrap_or_default(),
+            (true, None) => Redundancy::Allow,
+            (true, Some(_)) => {
+                return Err("--redundant needs Too small/Too big answers, so it can't be combined with --hints hot-cold".to_string())
+            }
+        };
+
+        config.undos = undos.unwrap_or(config.difficulty.undos());
+
+        config.feedback = match (hot_cold, thresholds) {
+            (true, thresholds) => Feedback::HotCold(thresholds.unwrap_or(Thresholds::DEFAULT)),
+            (false, None) => Feedback::Direction,
+            (false, Some(_)) => {
+                return Err("--thresholds only applies with --hints hot-cold".to_string())
+            }
+        };
+
+        if float_range.is_some() || tolerance.is_some() {
+            let classic = FloatRules::DEFAULT;
+            let (min, max) = float_range.unwrap_or((classic.min(), classic.max()));
+            config.floats = FloatRules::new(min, max, tolerance.unwrap_or_default())?;
+        }
+
+        if code_length.is_some() || alphabet.is_some() || repeats {
+            let classic = CodeRules::default();
+            let alphabet = alphabet.unwrap_or_else(|| classic.alphabet().iter().collect());
+            config.code = CodeRules::new(
+                code_length.map_or(classic.length(), |length| length as usize),
+                &alphabet,
+                repeats,
+            )?;
+        }
+
+        if config.command == Command::Tui && config.feedback != Feedback::Direction {
+            return Err(
+                "tui draws the Too small/Too big answers as an interval, so it can't be combined with --hints hot-cold"
+                    .to_string(),
+            );
+        }
+        if config.record.is_some() && config.autoplay {
+            return Err(
+                "--record records a player's input, so it can't be combined with --autoplay"
+                    .to_string(),
+            );
+        }
+        if config.command == Command::Replay && config.transcript.is_none() && !config.help {
+            return Err("replay needs a transcript, like `replay session.jsonl`".to_string());
+        }
+        if config.command == Command::Join && config.address.is_none() && !config.help {
+            return Err("join needs the server's address, like `join 127.0.0.1:7878`".to_string());
+        }
+
+        Ok(config)
+    }
+}
+
+// Takes the value of a flag either from `--flag=value` or from the next argument.
+fn value_for<I>(flag: &str, inline_value: Option<String>, args: &mut I) -> Result<String, String>
+where
+    I: Iterator<Item = String>,
+{
+    match inline_value {
+        Some(value) => Ok(value),
+        None => args.next().ok_or_else(|| format!("{flag} needs a value")),
+    }
+}
+
+fn parse_seed(value: &str, source: &str) -> Result<u64, String> {
+    value.trim().parse().map_err(|_| {
+        format!(
+            "{source} must be a whole number between 0 and {}, got '{value}'",
+            u64::MAX
+        )
+    })
+}
+
+fn parse_bound(value: &str, flag: &str) -> Result<i128, String> {
+    value.trim().parse().map_err(|_| {
+        format!(
+            "{flag} must be a whole number between {} and {}, got '{value}'",
+            i128::MIN,
+            i128::MAX
+        )
+    })
+}
+
+// Reads a float range written low,high, like 0.5,2 or -1e3,1e3.
+fn parse_float_range(value: &str) -> Result<(f64, f64), String> {
+    let error = || format!("--float-range looks like 1,100 or 0.5,2.5, got '{value}'");
+    let (min, max) = value.split_once(',').ok_or_else(error)?;
+    let min = min.trim().parse().map_err(|_| error())?;
+    let max = max.trim().parse().map_err(|_| error())?;
+    Ok((min, max))
+}
+
+fn parse_count(value: &str, flag: &str) -> Result<u32, String> {
+    match value.trim().parse() {
+        Ok(0) | Err(_) => Err(format!(
+            "{flag} must be a whole number of at least 1, got '{value}'"
+        )),
+        Ok(count) => Ok(count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_with(args: &[&str], vars: &[(&str, &str)]) -> Result<Config, String> {
+        let env = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        };
+        Config::parse(args.iter().map(|arg| arg.to_string()), env)
+    }
+
+    fn parse(args: &[&str]) -> Result<Config, String> {
+        parse_with(args, &[])
+    }
+
+    #[test]
+    fn values_can_follow_the_flag_or_an_equals_sign() {
+        assert_eq!(parse(&["--seed", "42"]), parse(&["--seed=42"]));
+        assert_eq!(parse(&["--seed=42"]).unwrap().seed, Some(42));
+        let config = parse(&["--min=-5", "--max", "-1", "--attempts=3"]).unwrap();
+        assert_eq!(config.rules.bounds, Bounds::new(-5, -1).unwrap());
+        assert_eq!(config.rules.max_attempts, Some(3));
+        // Only the first equals sign splits, so a value may hold one too.
+        let config = parse(&["--player=a=b"]).unwrap();
+        assert_eq!(config.player.as_deref(), Some("a=b"));
+        assert_eq!(
+            parse(&["bulls", "--alphabet=ab=", "--length", "2"])
+                .unwrap()
+                .code,
+            CodeRules::new(2, "ab=", false).unwrap()
+        );
+    }
+
+    #[test]
+    fn a_flag_without_its_value_is_an_error() {
+        for args in [
+            &["--seed"][..],
+            &["--min"],
+            &["--difficulty"],
+            &["play", "--max"],
+        ] {
+            let error = parse(args).unwrap_err();
+            assert!(error.ends_with("needs a value"), "{args:?}: {error}");
+        }
+        // An empty value after = is still a value, just not a valid one.
+        assert!(parse(&["--seed="])
+            .unwrap_err()
+            .contains("must be a whole number"));
+    }
+
+    #[test]
+    fn unknown_flags_and_stray_arguments_are_errors() {
+        assert_eq!(
+            parse(&["--bogus"]).unwrap_err(),
+            "unknown argument '--bogus'"
+        );
+        assert_eq!(
+            parse(&["--bogus=1"]).unwrap_err(),
+            "unknown argument '--bogus'"
+        );
+        assert_eq!(parse(&["-x"]).unwrap_err(), "unknown argument '-x'");
+        assert_eq!(
+            parse(&["play", "extra"]).unwrap_err(),
+            "unknown argument 'extra'"
+        );
+        // A command is only read first.
+        assert!(parse(&["--seed", "1", "stats"]).is_err());
+    }
+
+    #[test]
+    fn the_minimum_must_be_below_the_maximum() {
+        assert!(parse(&["--min", "10", "--max", "10"]).is_err());
+        assert!(parse(&["--min", "11", "--max", "10"]).is_err());
+        // Against the preset's other end, too: normal goes up to 100.
+        assert!(parse(&["--min", "100"]).is_err());
+        assert!(parse(&["--min", "9", "--max", "10"]).is_ok());
+        assert!(parse(&["--min", "one"]).is_err());
+    }
+
+    #[test]
+    fn counts_must_be_at_least_one() {
+        for flag in ["--attempts", "--length", "--timeout", "--rounds", "--games"] {
+            let error = parse(&[flag, "0"]).unwrap_err();
+            assert!(error.contains("at least 1"), "{flag}: {error}");
+            assert!(parse(&[flag, "-1"]).is_err(), "{flag}");
+        }
+        // No undos at all is a real choice.
+        assert_eq!(parse(&["--undos", "0"]).unwrap().undos, 0);
+    }
+
+    #[test]
+    fn the_seed_flag_wins_over_the_environment() {
+        let vars = [(SEED_ENV, "7")];
+        assert_eq!(parse_with(&[], &vars).unwrap().seed, Some(7));
+        assert_eq!(parse_with(&["--seed", "9"], &vars).unwrap().seed, Some(9));
+        // A broken value in the environment only matters when it would be used.
+        let broken = [(SEED_ENV, "seven")];
+        assert!(parse_with(&[], &broken).is_err());
+        assert_eq!(parse_with(&["--seed=9"], &broken).unwrap().seed, Some(9));
+        assert_eq!(parse(&[]).unwrap().seed, None);
+    }
+
+    #[test]
+    fn the_debug_variable_turns_on_reveal_unless_empty_or_zero() {
+        for (value, reveal) in [("1", true), ("yes", true), ("0", false), ("", false)] {
+            let config = parse_with(&[], &[(DEBUG_ENV, value)]).unwrap();
+            assert_eq!(config.reveal, reveal, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn a_plain_run_plays_normal() {
+        let config = parse(&[]).unwrap();
+        assert_eq!(config.difficulty, Difficulty::Normal);
+        assert_eq!(config.rules, Difficulty::Normal.rules());
+        assert_eq!(config.rules.max_attempts, Some(10));
+        assert_eq!(config.undos, 1);
+    }
+
+    #[test]
+    fn custom_limits_make_the_difficulty_custom_and_clash_with_presets() {
+        let config = parse(&["--min", "5"]).unwrap();
+        assert_eq!(config.difficulty, Difficulty::Custom);
+        assert_eq!(config.rules, Rules::unlimited(Bounds::new(5, 100).unwrap()));
+        assert_eq!(config.undos, Difficulty::Custom.undos());
+
+        let config = parse(&["--attempts", "4"]).unwrap();
+        assert_eq!(config.difficulty, Difficulty::Custom);
+        assert_eq!(config.rules.bounds, Bounds::new(1, 100).unwrap());
+        assert_eq!(config.rules.max_attempts, Some(4));
+
+        let config = parse(&["--difficulty", "custom", "--max", "50", "--attempts=6"]).unwrap();
+        assert_eq!(config.difficulty, Difficulty::Custom);
+        assert_eq!(config.rules.bounds, Bounds::new(1, 50).unwrap());
+        assert_eq!(config.rules.max_attempts, Some(6));
+        assert_eq!(
+            parse(&["--difficulty", "custom"]).unwrap().rules,
+            Difficulty::Custom.rules()
+        );
+
+        for preset in ["easy", "normal", "hard"] {
+            for flag in ["--min", "--max", "--attempts"] {
+                let error = parse(&["--difficulty", preset, flag, "7"]).unwrap_err();
+                assert!(
+                    error.contains("can't be combined"),
+                    "{preset} {flag}: {error}"
+                );
+                // The order they're given in doesn't matter.
+                assert!(parse(&[flag, "7", "--difficulty", preset]).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn presets_bring_their_own_rules_and_undos() {
+        for difficulty in Difficulty::ALL {
+            let config = parse(&["--difficulty", difficulty.name()]).unwrap();
+            assert_eq!(config.rules, difficulty.rules());
+            assert_eq!(config.undos, difficulty.undos());
+        }
+        assert_eq!(
+            parse(&["--difficulty=HARD"]).unwrap().difficulty,
+            Difficulty::Hard
+        );
+        assert_eq!(
+            parse(&["--difficulty", "hard", "--undos", "2"])
+                .unwrap()
+                .undos,
+            2
+        );
+        assert!(parse(&["--difficulty", "nightmare"]).is_err());
+    }
+
+    #[test]
+    fn commands_that_need_an_argument_say_so() {
+        assert!(parse(&["join"]).is_err());
+        assert!(parse(&["replay"]).is_err());
+        assert!(parse(&["join", "--help"]).is_ok());
+        let config = parse(&["join", "127.0.0.1:7878"]).unwrap();
+        assert_eq!(config.address.as_deref(), Some("127.0.0.1:7878"));
+        assert!(parse(&["join", "a:1", "b:2"]).is_err());
+    }
+}
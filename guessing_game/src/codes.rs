@@ -0,0 +1,539 @@
+// Bulls and cows, the pen-and-paper ancestor of Mastermind: the secret is a code of several symbols, and each guess is scored with bulls (right symbol, right place) and cows (right symbol, wrong place).
+
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::game::Status;
+
+/// The most symbols an alphabet may have; scoring counts symbols in a fixed-size array.
+pub const MAX_ALPHABET: usize = 64;
+/// The longest code allowed.
+pub const MAX_LENGTH: usize = 16;
+
+/// The shape of the codes in a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeRules {
+    length: usize,
+    alphabet: Vec<char>,
+    repeats: bool,
+}
+
+impl CodeRules {
+    /// Builds the rules for codes of `length` symbols drawn from `alphabet`, which may use a symbol more than once only if `repeats` is true.
+    pub fn new(length: usize, alphabet: &str, repeats: bool) -> Result<CodeRules, String> {
+        let symbols: Vec<char> = alphabet.chars().collect();
+        if !(1..=MAX_LENGTH).contains(&length) {
+            return Err(format!(
+                "the code length must be between 1 and {MAX_LENGTH}, got {length}"
+            ));
+        }
+        if !(2..=MAX_ALPHABET).contains(&symbols.len()) {
+            return Err(format!(
+                "the alphabet must have between 2 and {MAX_ALPHABET} symbols, got {}",
+                symbols.len()
+            ));
+        }
+        if let Some(symbol) = symbols
+            .iter()
+            .enumerate()
+            .find_map(|(i, symbol)| symbols[..i].contains(symbol).then_some(symbol))
+        {
+            return Err(format!("the alphabet lists '{symbol}' twice"));
+        }
+        if symbols.iter().any(|symbol| symbol.is_whitespace()) {
+            return Err("the alphabet can't contain spaces".to_string());
+        }
+        if !repeats && length > symbols.len() {
+            return Err(format!(
+                "a {length}-symbol code can't avoid repeats with only {} symbols",
+                symbols.len()
+            ));
+        }
+        Ok(CodeRules {
+            length,
+            alphabet: symbols,
+            repeats,
+        })
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    /// How many different codes there are, or `None` if the count doesn't fit a u64.
+    pub fn count(&self) -> Option<u64> {
+        let symbols = self.alphabet.len() as u64;
+        (0..self.length as u64).try_fold(1u64, |count, position| {
+            let choices = if self.repeats {
+                symbols
+            } else {
+                symbols - position
+            };
+            count.checked_mul(choices)
+        })
+    }
+
+    /// Draws a secret code.
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Code {
+        let symbols = self.alphabet.len() as u8;
+        if self.repeats {
+            Code(
+                (0..self.length)
+                    .map(|_| rng.gen_range(0..symbols))
+                    .collect(),
+            )
+        } else {
+            let mut all: Vec<u8> = (0..symbols).collect();
+            all.shuffle(rng);
+            all.truncate(self.length);
+            Code(all)
+        }
+    }
+
+    /// Turns a line of input into a code. Spaces between symbols are allowed, so `1 2 3 4` works as well as `1234`.
+    pub fn parse(&self, input: &str) -> Result<Code, InvalidCode> {
+        let symbols: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+        if symbols.is_empty() {
+            return Err(InvalidCode::Empty);
+        }
+        if symbols.len() != self.length {
+            return Err(InvalidCode::WrongLength {
+                expected: self.length,
+                got: symbols.len(),
+            });
+        }
+        let mut code = Vec::with_capacity(self.length);
+        for symbol in symbols {
+            let index = self
+                .alphabet
+                .iter()
+                .position(|&known| known == symbol)
+                .ok_or(InvalidCode::NotInAlphabet(symbol))?;
+            if !self.repeats && code.contains(&(index as u8)) {
+                return Err(InvalidCode::Repeated(symbol));
+            }
+            code.push(index as u8);
+        }
+        Ok(Code(code))
+    }
+
+    /// Writes a code with this alphabet's symbols.
+    pub fn show(&self, code: &Code) -> String {
+        code.0
+            .iter()
+            .map(|&index| self.alphabet[index as usize])
+            .collect()
+    }
+}
+
+impl Default for CodeRules {
+    /// The classic game: four different digits.
+    fn default() -> CodeRules {
+        CodeRules::new(4, "0123456789", false).expect("the classic rules are valid")
+    }
+}
+
+/// A code, stored as positions in the alphabet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code(Vec<u8>);
+
+/// Why a line of input was not accepted as a code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidCode {
+    Empty,
+    WrongLength {
+        expected: usize,
+        got: usize,
+    },
+    NotInAlphabet(char),
+    /// A symbol used twice in a game without repeats.
+    Repeated(char),
+    Finished,
+}
+
+impl fmt::Display for InvalidCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidCode::Empty => write!(f, "Please type a code."),
+            InvalidCode::WrongLength { expected, got } => {
+                write!(f, "The code has {expected} symbols, but you typed {got}.")
+            }
+            InvalidCode::NotInAlphabet(symbol) => {
+                write!(f, "'{symbol}' isn't one of the code's symbols.")
+            }
+            InvalidCode::Repeated(symbol) => {
+                write!(
+                    f,
+                    "No symbol appears twice in this code, but you used '{symbol}' more than once."
+                )
+            }
+            InvalidCode::Finished => write!(f, "This round is already over."),
+        }
+    }
+}
+
+/// The answer to a guess: bulls are symbols in the right place, cows are symbols in the code but somewhere else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub bulls: usize,
+    pub cows: usize,
+}
+
+impl Score {
+    /// Scores `guess` against `secret`; both must have the same length.
+    pub fn of(secret: &Code, guess: &Code) -> Score {
+        let mut in_secret = [0u8; MAX_ALPHABET];
+        let mut in_guess = [0u8; MAX_ALPHABET];
+        let mut bulls = 0;
+        for (&s, &g) in secret.0.iter().zip(&guess.0) {
+            if s == g {
+                bulls += 1;
+            } else {
+                in_secret[s as usize] += 1;
+                in_guess[g as usize] += 1;
+            }
+        }
+        // Among the symbols not already bulls, each one the two codes share is a cow.
+        let cows = in_secret
+            .iter()
+            .zip(&in_guess)
+            .map(|(&s, &g)| s.min(g) as usize)
+            .sum();
+        Score { bulls, cows }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} bull{}, {} cow{}",
+            self.bulls,
+            plural(self.bulls),
+            self.cows,
+            plural(self.cows)
+        )
+    }
+}
+
+/// One round of bulls and cows.
+#[derive(Debug, Clone)]
+pub struct CodeGame {
+    rules: CodeRules,
+    secret: Code,
+    max_attempts: Option<u32>,
+    guesses: Vec<(Code, Score)>,
+    status: Status,
+}
+
+impl CodeGame {
+    /// Starts a round with a known secret, for tests and solvers.
+    pub fn new(rules: CodeRules, secret: Code, max_attempts: Option<u32>) -> CodeGame {
+        assert_eq!(
+            secret.0.len(),
+            rules.length,
+            "the secret must fit the rules"
+        );
+        CodeGame {
+            rules,
+            secret,
+            max_attempts,
+            guesses: Vec::new(),
+            status: Status::Playing,
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(
+        rules: CodeRules,
+        max_attempts: Option<u32>,
+        rng: &mut R,
+    ) -> CodeGame {
+        let secret = rules.random(rng);
+        CodeGame::new(rules, secret, max_attempts)
+    }
+
+    /// Parses a line of input and scores it as a guess.
+    pub fn submit(&mut self, input: &str) -> Result<Score, InvalidCode> {
+        let code = self.rules.parse(input)?;
+        self.guess(code)
+    }
+
+    /// Scores an already parsed guess. A rejected guess doesn't count as an attempt.
+    pub fn guess(&mut self, code: Code) -> Result<Score, InvalidCode> {
+        if self.is_finished() {
+            return Err(InvalidCode::Finished);
+        }
+        let score = Score::of(&self.secret, &code);
+        self.guesses.push((code, score));
+        if score.bulls == self.rules.length {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        Ok(score)
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    pub fn rules(&self) -> &CodeRules {
+        &self.rules
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    /// Every guess so far with its score, in order.
+    pub fn guesses(&self) -> &[(Code, Score)] {
+        &self.guesses
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+/// The solver refuses rule sets with more codes than this, since it keeps every code still possible in memory.
+pub const MAX_SOLVER_CODES: u64 = 1_000_000;
+
+// Below this many candidates the solver looks for the guess whose worst answer leaves the fewest; above it, that search would take too long and it plays the first candidate instead.
+const MINIMAX_LIMIT: usize = 2_000;
+
+/// Every code the rules allow, in order.
+pub fn all_codes(rules: &CodeRules) -> Vec<Code> {
+    let mut codes = Vec::with_capacity(rules.count().unwrap_or(0).min(MAX_SOLVER_CODES) as usize);
+    let mut used = [false; MAX_ALPHABET];
+    extend(
+        rules,
+        &mut Vec::with_capacity(rules.length),
+        &mut used,
+        &mut codes,
+    );
+    codes
+}
+
+// Tries each symbol in turn at the next position of `code` and carries on from there, adding every finished code to `codes`.
+// Without repeats a symbol already in `code` is never tried, so only codes the rules allow are built: 5,040 for the classic rules, not all 10,000 four-digit numbers.
+fn extend(
+    rules: &CodeRules,
+    code: &mut Vec<u8>,
+    used: &mut [bool; MAX_ALPHABET],
+    codes: &mut Vec<Code>,
+) {
+    if code.len() == rules.length {
+        codes.push(Code(code.clone()));
+        return;
+    }
+    for symbol in 0..rules.alphabet.len() {
+        if used[symbol] && !rules.repeats {
+            continue;
+        }
+        used[symbol] = true;
+        code.push(symbol as u8);
+        extend(rules, code, used, codes);
+        code.pop();
+        used[symbol] = false;
+    }
+}
+
+/// Plays `game` to the end, always guessing a code that every score so far allows, and returns how many guesses it took.
+///
+/// Because every guess could be the secret, no code is ever guessed twice and the game is won within `count()` guesses at the very most; in practice the minimax choice keeps it to a handful (at most 8 for the classic four different digits, and 7 for Mastermind's four of six colours with repeats).
+pub fn autoplay(game: &mut CodeGame) -> Result<u32, String> {
+    let rules = game.rules().clone();
+    match rules.count() {
+        Some(count) if count <= MAX_SOLVER_CODES => {}
+        _ => {
+            return Err(format!(
+                "there are too many possible codes to search (the solver handles up to {MAX_SOLVER_CODES})"
+            ))
+        }
+    }
+    let mut candidates = all_codes(&rules);
+    let bound = candidates.len() as u32;
+
+    // The same opening every time: the first `length` symbols, which avoids wasting the first guess on repeats.
+    let symbols = rules.alphabet.len();
+    let mut guess = Code((0..rules.length).map(|i| (i % symbols) as u8).collect());
+
+    while !game.is_finished() {
+        let score = game
+            .guess(guess.clone())
+            .map_err(|reason| format!("the game rejected {}: {reason}", rules.show(&guess)))?;
+        candidates.retain(|code| Score::of(code, &guess) == score);
+
+        if game.is_finished() {
+            break;
+        }
+        if candidates.is_empty() {
+            return Err(format!(
+                "no code is consistent with the scores so far (after {} guesses)",
+                game.attempts()
+            ));
+        }
+        if game.attempts() >= bound {
+            return Err(format!(
+                "needed more than {bound} guesses, one per possible code"
+            ));
+        }
+        guess = next_guess(&candidates, rules.length);
+    }
+    Ok(game.attempts())
+}
+
+// The candidate whose worst possible score leaves the fewest candidates standing.
+fn next_guess(candidates: &[Code], length: usize) -> Code {
+    if candidates.len() > MINIMAX_LIMIT {
+        return candidates[0].clone();
+    }
+    let mut sizes = vec![0u32; (length + 1) * (length + 1)];
+    candidates
+        .iter()
+        .min_by_key(|guess| {
+            sizes.iter_mut().for_each(|size| *size = 0);
+            for code in candidates {
+                let score = Score::of(code, guess);
+                sizes[score.bulls * (length + 1) + score.cows] += 1;
+            }
+            sizes.iter().copied().max().unwrap_or(0)
+        })
+        .expect("there is at least one candidate")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(rules: &CodeRules, text: &str) -> Code {
+        rules.parse(text).unwrap()
+    }
+
+    fn score(bulls: usize, cows: usize) -> Score {
+        Score { bulls, cows }
+    }
+
+    // The worst number of guesses `autoplay` takes over every secret the rules allow.
+    fn worst_autoplay(rules: &CodeRules) -> u32 {
+        all_codes(rules)
+            .into_iter()
+            .map(|secret| {
+                let mut game = CodeGame::new(rules.clone(), secret, None);
+                let attempts = autoplay(&mut game).unwrap();
+                assert_eq!(game.status(), Status::Won);
+                attempts
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn repeated_symbols_count_as_cows_only_as_often_as_both_codes_have_them() {
+        let rules = CodeRules::new(4, "123456", true).unwrap();
+        let secret = code(&rules, "1122");
+        assert_eq!(Score::of(&secret, &code(&rules, "1122")), score(4, 0));
+        assert_eq!(Score::of(&secret, &code(&rules, "2211")), score(0, 4));
+        assert_eq!(Score::of(&secret, &code(&rules, "1111")), score(2, 0));
+        assert_eq!(Score::of(&secret, &code(&rules, "2333")), score(0, 1));
+        assert_eq!(Score::of(&secret, &code(&rules, "1213")), score(1, 2));
+        assert_eq!(Score::of(&secret, &code(&rules, "3456")), score(0, 0));
+    }
+
+    #[test]
+    fn rules_are_checked_when_built() {
+        assert!(CodeRules::new(0, "0123456789", false).is_err());
+        assert!(CodeRules::new(MAX_LENGTH + 1, "01", true).is_err());
+        assert!(CodeRules::new(4, "0", true).is_err());
+        let too_many: String = ('!'..).take(MAX_ALPHABET + 1).collect();
+        assert!(CodeRules::new(4, &too_many, true).is_err());
+        assert!(CodeRules::new(4, "01230", true).is_err());
+        assert!(CodeRules::new(2, "a b", true).is_err());
+        assert!(CodeRules::new(4, "012", false).is_err());
+        assert!(CodeRules::new(4, "012", true).is_ok());
+        assert!(CodeRules::new(MAX_LENGTH, &too_many[..MAX_ALPHABET], false).is_ok());
+    }
+
+    #[test]
+    fn parsing_rejects_codes_that_break_the_rules() {
+        let rules = CodeRules::default();
+        assert_eq!(rules.parse("1 2 3 4"), rules.parse("1234"));
+        assert_eq!(rules.show(&code(&rules, " 9 0 1 2 ")), "9012");
+        assert_eq!(rules.parse("  "), Err(InvalidCode::Empty));
+        assert_eq!(
+            rules.parse("123"),
+            Err(InvalidCode::WrongLength {
+                expected: 4,
+                got: 3
+            })
+        );
+        assert_eq!(rules.parse("12a4"), Err(InvalidCode::NotInAlphabet('a')));
+        assert_eq!(rules.parse("1231"), Err(InvalidCode::Repeated('1')));
+
+        let repeats = CodeRules::new(4, "0123456789", true).unwrap();
+        assert!(repeats.parse("1231").is_ok());
+    }
+
+    #[test]
+    fn all_codes_lists_each_allowed_code_once_in_order() {
+        let classic = CodeRules::default();
+        let codes = all_codes(&classic);
+        assert_eq!(codes.len() as u64, classic.count().unwrap());
+        assert_eq!(codes.len(), 5040);
+        assert_eq!(classic.show(&codes[0]), "0123");
+        assert_eq!(classic.show(&codes[1]), "0124");
+        assert_eq!(classic.show(&codes[5039]), "9876");
+        assert!(codes.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        let repeats = CodeRules::new(2, "abc", true).unwrap();
+        let shown: Vec<String> = all_codes(&repeats)
+            .iter()
+            .map(|code| repeats.show(code))
+            .collect();
+        assert_eq!(
+            shown,
+            ["aa", "ab", "ac", "ba", "bb", "bc", "ca", "cb", "cc"]
+        );
+    }
+
+    #[test]
+    fn autoplay_wins_every_game_of_small_rules() {
+        let rules = CodeRules::new(3, "01234", false).unwrap();
+        assert!(worst_autoplay(&rules) <= 6);
+    }
+
+    // The promise in autoplay's documentation. It plays over 6,000 games and takes minutes even with optimisations, so run it with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn autoplay_keeps_its_promise_for_every_classic_and_mastermind_secret() {
+        assert!(worst_autoplay(&CodeRules::default()) <= 8);
+        assert!(worst_autoplay(&CodeRules::new(4, "123456", true).unwrap()) <= 7);
+    }
+}
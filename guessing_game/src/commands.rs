@@ -0,0 +1,268 @@
+// The commands a player can type instead of a guess. A command starts with a colon, so it can never be mistaken for a number:
+//
+//   :history [n]   the guesses so far this round, or only the last n
+//   :range         the numbers still possible
+//   :hint          halve the numbers still possible, at a cost to the round's score
+//   :undo          take back the last guess, as many times as the difficulty allows
+//   :giveup        end the round and see the secret
+//   :new           give up the round and start the next one
+//   :help          list the commands
+//   :quit          leave the game
+//
+// Any start of a name that only one command has works too (:his, :r, :u), which is also what a shell's tab completion would offer; `complete` lists the candidates.
+
+use std::fmt;
+
+/// A command typed during a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Show the guesses so far, or only the last this many.
+    History(Option<u32>),
+    Range,
+    Hint,
+    Undo,
+    GiveUp,
+    New,
+    Help,
+    Quit,
+}
+
+/// Every command's name, in the order `:help` lists them.
+pub const NAMES: [&str; 8] = [
+    "history", "range", "hint", "undo", "giveup", "new", "help", "quit",
+];
+
+/// Why a line starting with a colon isn't a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// No command starts with this.
+    Unknown(String),
+    /// More than one command starts with this, so it's not clear which was meant.
+    Ambiguous {
+        prefix: String,
+        matches: Vec<&'static str>,
+    },
+    /// The command takes no argument, but got one.
+    UnexpectedArgument {
+        command: &'static str,
+        argument: String,
+    },
+    /// The argument isn't a count of at least 1.
+    BadCount {
+        command: &'static str,
+        argument: String,
+    },
+}
+
+impl CommandError {
+    /// How the JSON lines protocol names the error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CommandError::Unknown(_) => "unknown_command",
+            CommandError::Ambiguous { .. } => "ambiguous_command",
+            CommandError::UnexpectedArgument { .. } | CommandError::BadCount { .. } => {
+                "bad_argument"
+            }
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Unknown(name) => {
+                write!(f, "There's no command :{name}. Type :help for the list.")
+            }
+            CommandError::Ambiguous { prefix, matches } => write!(
+                f,
+                ":{prefix} could be :{}; type more of the name.",
+                matches.join(", :")
+            ),
+            CommandError::UnexpectedArgument { command, argument } => {
+                write!(
+                    f,
+                    ":{command} doesn't take an argument, but got '{argument}'."
+                )
+            }
+            CommandError::BadCount { command, argument } => write!(
+                f,
+                ":{command} takes a number of guesses, like :{command} 3, but got '{argument}'."
+            ),
+        }
+    }
+}
+
+/// The command names starting with `prefix`, for completing a partly typed name.
+pub fn complete(prefix: &str) -> Vec<&'static str> {
+    let prefix = prefix.to_lowercase();
+    NAMES
+        .into_iter()
+        .filter(|name| name.starts_with(&prefix))
+        .collect()
+}
+
+/// Reads a line as a command, or returns `None` if it isn't one (it doesn't start with a colon).
+pub fn parse(line: &str) -> Option<Result<Command, CommandError>> {
+    let rest = line.trim().strip_prefix(':')?;
+    let mut words = rest.split_whitespace();
+    let typed = words.next().unwrap_or("");
+    let argument: Vec<&str> = words.collect();
+    let argument = (!argument.is_empty()).then(|| argument.join(" "));
+    Some(command(typed, argument))
+}
+
+fn command(typed: &str, argument: Option<String>) -> Result<Command, CommandError> {
+    let typed = typed.to_lowercase();
+    // An exact name always wins, even where it's also the start of a longer one.
+    let name = match NAMES.into_iter().find(|name| *name == typed) {
+        Some(name) => name,
+        None => match complete(&typed).as_slice() {
+            [name] => name,
+            [] => return Err(CommandError::Unknown(typed)),
+            matches => {
+                return Err(CommandError::Ambiguous {
+                    prefix: typed,
+                    matches: matches.to_vec(),
+                })
+            }
+        },
+    };
+
+    if name == "history" {
+        return match argument {
+            None => Ok(Command::History(None)),
+            Some(argument) => match argument.parse() {
+                Ok(count) if count > 0 => Ok(Command::History(Some(count))),
+                _ => Err(CommandError::BadCount {
+                    command: name,
+                    argument,
+                }),
+            },
+        };
+    }
+    if let Some(argument) = argument {
+        return Err(CommandError::UnexpectedArgument {
+            command: name,
+            argument,
+        });
+    }
+    Ok(match name {
+        "range" => Command::Range,
+        "hint" => Command::Hint,
+        "undo" => Command::Undo,
+        "giveup" => Command::GiveUp,
+        "new" => Command::New,
+        "help" => Command::Help,
+        _ => Command::Quit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ambiguous(prefix: &str, matches: &[&'static str]) -> Result<Command, CommandError> {
+        Err(CommandError::Ambiguous {
+            prefix: prefix.to_string(),
+            matches: matches.to_vec(),
+        })
+    }
+
+    #[test]
+    fn whole_names_and_unique_prefixes_are_commands() {
+        let cases = [
+            (":history", Command::History(None)),
+            (":range", Command::Range),
+            (":hint", Command::Hint),
+            (":undo", Command::Undo),
+            (":giveup", Command::GiveUp),
+            (":new", Command::New),
+            (":help", Command::Help),
+            (":quit", Command::Quit),
+            (":u", Command::Undo),
+            (":his", Command::History(None)),
+            (":r", Command::Range),
+            (":g", Command::GiveUp),
+            (":n", Command::New),
+            (":q", Command::Quit),
+            (":hin", Command::Hint),
+            (":HeLp", Command::Help),
+            ("  :undo  ", Command::Undo),
+            (":history 3", Command::History(Some(3))),
+            (":his 3", Command::History(Some(3))),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(parse(line), Some(Ok(expected)), "{line:?}");
+        }
+    }
+
+    #[test]
+    fn prefixes_more_than_one_command_shares_are_ambiguous() {
+        let cases = [
+            (":h", ambiguous("h", &["history", "hint", "help"])),
+            (":hi", ambiguous("hi", &["history", "hint"])),
+            (":h 3", ambiguous("h", &["history", "hint", "help"])),
+            (":he", Ok(Command::Help)),
+            (":", ambiguous("", &NAMES)),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(parse(line), Some(expected), "{line:?}");
+        }
+        assert_eq!(
+            parse(":h").unwrap().unwrap_err().code(),
+            "ambiguous_command"
+        );
+    }
+
+    #[test]
+    fn unknown_commands_and_bad_arguments_are_errors() {
+        let unknown = |name: &str| Err(CommandError::Unknown(name.to_string()));
+        let unexpected = |command, argument: &str| {
+            Err(CommandError::UnexpectedArgument {
+                command,
+                argument: argument.to_string(),
+            })
+        };
+        let bad_count = |argument: &str| {
+            Err(CommandError::BadCount {
+                command: "history",
+                argument: argument.to_string(),
+            })
+        };
+        let cases = [
+            (":x", unknown("x")),
+            (":undoo", unknown("undoo")),
+            (":QUITE", unknown("quite")),
+            (":undo 2", unexpected("undo", "2")),
+            (":quit now please", unexpected("quit", "now please")),
+            (":history 0", bad_count("0")),
+            (":history -1", bad_count("-1")),
+            (":history three", bad_count("three")),
+            (":history 1 2", bad_count("1 2")),
+        ];
+        for (line, expected) in cases {
+            let parsed = parse(line);
+            assert_eq!(parsed, Some(expected), "{line:?}");
+        }
+        assert_eq!(parse(":x").unwrap().unwrap_err().code(), "unknown_command");
+        assert_eq!(
+            parse(":undo 2").unwrap().unwrap_err().code(),
+            "bad_argument"
+        );
+    }
+
+    #[test]
+    fn lines_without_a_colon_are_not_commands() {
+        for line in ["", "  ", "42", "undo", "quit", "4:2", "-1"] {
+            assert_eq!(parse(line), None, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn completion_lists_every_name_with_the_prefix() {
+        assert_eq!(complete("h"), ["history", "hint", "help"]);
+        assert_eq!(complete("G"), ["giveup"]);
+        assert!(complete("z").is_empty());
+        assert_eq!(complete(""), NAMES);
+    }
+}
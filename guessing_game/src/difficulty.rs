@@ -0,0 +1,123 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bounds::Bounds;
+
+/// The named presets offered on the command line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// Any range and budget the player chose themselves.
+    Custom,
+}
+
+impl Difficulty {
+    /// Every difficulty, in increasing order of challenge (custom last).
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Custom,
+    ];
+
+    /// The range and attempt budget of a preset. `Custom` starts from the classic 1 to 100 with no limit.
+    pub fn rules(self) -> Rules {
+        // Each preset leaves a few more attempts than binary search needs, except hard, which allows exactly the optimum.
+        let (min, max, max_attempts) = match self {
+            Difficulty::Easy => (1, 20, Some(8)),
+            Difficulty::Normal => (1, 100, Some(10)),
+            Difficulty::Hard => (1, 1000, Some(10)),
+            Difficulty::Custom => (1, 100, None),
+        };
+        Rules {
+            bounds: Bounds::new(min, max).expect("preset ranges are valid"),
+            max_attempts,
+        }
+    }
+
+    /// How many guesses a round lets the player take back with :undo; hard, which already allows only the optimum, allows none.
+    pub fn undos(self) -> u32 {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Normal => 1,
+            Difficulty::Hard => 0,
+            Difficulty::Custom => 3,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// Implementing FromStr is what lets us call "hard".parse::<Difficulty>(), just like parsing a number.
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!("unknown difficulty '{s}' (expected easy, normal, hard or custom)")
+            })
+    }
+}
+
+/// The constraints one round is played under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rules {
+    pub bounds: Bounds,
+    /// How many guesses the player gets; `None` means they can guess forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Rules {
+    pub fn unlimited(bounds: Bounds) -> Rules {
+        Rules {
+            bounds,
+            max_attempts: None,
+        }
+    }
+
+    /// The fewest guesses that always suffice, floor(log2(number of values)) + 1, which is what binary search needs in the worst case (the last guess being the one that wins).
+    pub fn optimal_attempts(&self) -> u32 {
+        // floor(log2(n)) + 1 is the number of bits needed to write n down. A range of width w holds w + 1 values, which for the full i128 range is 2^128, one more than a u128 holds.
+        match self.bounds.width().checked_add(1) {
+            Some(values) => u128::BITS - values.leading_zeros(),
+            None => u128::BITS + 1,
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Difficulty::default().rules()
+    }
+}
+
+/// The points each :hint takes off a round's score.
+pub const HINT_COST: u32 = 100;
+
+/// The score of a round: 1000 for a win within the optimal number of attempts, scaled down the more attempts it took beyond that, and 0 for anything but a win.
+pub fn score(rules: &Rules, won: bool, attempts: u32) -> u32 {
+    if !won {
+        return 0;
+    }
+    let optimal = rules.optimal_attempts();
+    1000 * optimal / attempts.max(optimal)
+}
@@ -0,0 +1,288 @@
+// A host that cheats as much as it can without ever lying: it doesn't pick a secret until it has to, and answers every guess so as to keep as many numbers possible as it can.
+// Against it, every guesser faces its worst case, which is why binary search's floor(log2(n)) + 1 guesses is the best any strategy can promise.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::bounds::Bounds;
+use crate::difficulty::{self, Rules};
+use crate::game::{self, InvalidGuess, Outcome, Status};
+use crate::solver::Host;
+
+/// One round against the evil host.
+#[derive(Debug, Clone)]
+pub struct EvilHost {
+    rules: Rules,
+    // Every number in low..=high is consistent with all the answers given so far; any of them could still be "the" secret.
+    low: i128,
+    high: i128,
+    attempts: u32,
+    status: Status,
+    answers: Vec<(i128, Outcome)>,
+    // Chosen only when the round is over.
+    secret: Option<i128>,
+}
+
+impl EvilHost {
+    pub fn new(rules: Rules) -> EvilHost {
+        EvilHost {
+            rules,
+            low: rules.bounds.min(),
+            high: rules.bounds.max(),
+            attempts: 0,
+            status: Status::Playing,
+            answers: Vec::new(),
+            secret: None,
+        }
+    }
+
+    /// Parses a line of player input and submits it as a guess, exactly as `Game::submit` does.
+    pub fn submit(&mut self, input: &str) -> Outcome {
+        match game::parse_guess(self.rules.bounds, input) {
+            Ok(guess) => self.guess(guess),
+            Err(reason) => Outcome::Invalid(reason),
+        }
+    }
+
+    /// Answers a guess with whichever of Too small and Too big leaves more numbers possible, and admits a win only when the guess is the last number left.
+    pub fn guess(&mut self, guess: i128) -> Outcome {
+        if self.is_finished() {
+            return Outcome::Invalid(InvalidGuess::Finished);
+        }
+        if !self.rules.bounds.contains(guess) {
+            return Outcome::Invalid(InvalidGuess::OutOfRange(self.rules.bounds));
+        }
+        self.attempts += 1;
+
+        let outcome = if guess < self.low {
+            // Already ruled out, so the answer is forced and changes nothing.
+            Outcome::TooSmall
+        } else if guess > self.high {
+            Outcome::TooBig
+        } else {
+            // How many numbers each answer would leave; abs_diff can't overflow even across the whole i128 range.
+            let above = self.high.abs_diff(guess);
+            let below = guess.abs_diff(self.low);
+            if above == 0 && below == 0 {
+                self.secret = Some(guess);
+                self.status = Status::Won;
+                Outcome::Win
+            } else if above >= below {
+                // guess < high here, so guess + 1 can't overflow; likewise guess - 1 below.
+                self.low = guess + 1;
+                Outcome::TooSmall
+            } else {
+                self.high = guess - 1;
+                Outcome::TooBig
+            }
+        };
+        self.answers.push((guess, outcome));
+
+        if !self.is_finished() && self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        outcome
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    /// Once the round is over, commits to a secret from the numbers still possible and returns it. Before then there is no secret to reveal.
+    pub fn settle<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<i128> {
+        if !self.is_finished() {
+            return None;
+        }
+        let (low, high) = (self.low, self.high);
+        Some(*self.secret.get_or_insert_with(|| rng.gen_range(low..=high)))
+    }
+
+    /// Whether `secret` would have produced every answer given, which is how the host's honesty can be checked after the fact.
+    pub fn consistent_with(&self, secret: i128) -> bool {
+        self.rules.bounds.contains(secret)
+            && self
+                .answers
+                .iter()
+                .all(|&(guess, outcome)| Outcome::from_ordering(guess.cmp(&secret)) == outcome)
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Every guess and the answer it got, in order.
+    pub fn answers(&self) -> &[(i128, Outcome)] {
+        &self.answers
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.rules
+            .max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn score(&self) -> u32 {
+        difficulty::score(&self.rules, self.status == Status::Won, self.attempts)
+    }
+
+    /// The numbers the host could still choose.
+    pub fn remaining(&self) -> RangeInclusive<i128> {
+        self.low..=self.high
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+impl Host for EvilHost {
+    fn bounds(&self) -> Bounds {
+        self.rules.bounds
+    }
+
+    fn guess(&mut self, guess: i128) -> Outcome {
+        EvilHost::guess(self, guess)
+    }
+
+    fn is_finished(&self) -> bool {
+        EvilHost::is_finished(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use crate::solver;
+
+    fn evil(min: i128, max: i128) -> EvilHost {
+        EvilHost::new(Rules::unlimited(Bounds::new(min, max).unwrap()))
+    }
+
+    // floor(log2(n)) + 1 for the n numbers from min to max.
+    fn forced(min: i128, max: i128) -> u32 {
+        let n = (max - min + 1) as u32;
+        n.ilog2() + 1
+    }
+
+    #[test]
+    fn every_answer_stays_consistent_with_a_secret() {
+        let mut rng = rng::seeded(16);
+        for _ in 0..200 {
+            let mut host = evil(1, 50);
+            // Guesses at random, wasted ones included, until the host has to give in.
+            while !host.is_finished() {
+                host.guess(rng.gen_range(1..=50));
+                let remaining = host.remaining();
+                assert!(!remaining.is_empty(), "{:?}", host.answers());
+                assert!(
+                    remaining.clone().all(|secret| host.consistent_with(secret)),
+                    "{:?}",
+                    host.answers()
+                );
+            }
+            let secret = host.settle(&mut rng).unwrap();
+            assert!(host.consistent_with(secret));
+            assert_eq!(
+                host.settle(&mut rng),
+                Some(secret),
+                "the secret stays settled"
+            );
+        }
+    }
+
+    #[test]
+    fn there_is_no_secret_to_reveal_before_the_round_is_over() {
+        let mut host = evil(1, 100);
+        host.guess(50);
+        assert_eq!(host.settle(&mut rng::seeded(1)), None);
+        host.give_up();
+        let secret = host.settle(&mut rng::seeded(1)).unwrap();
+        assert!(host.consistent_with(secret));
+    }
+
+    #[test]
+    fn binary_search_needs_every_guess_it_can_promise() {
+        let ranges = [
+            (1, 2),
+            (1, 10),
+            (1, 100),
+            (1, 1000),
+            (-500, 523),
+            (0, 1 << 20),
+        ];
+        for (min, max) in ranges {
+            let mut host = evil(min, max);
+            let steps = solver::autoplay(&mut host).unwrap();
+            assert_eq!(steps.len() as u32, forced(min, max), "{min} to {max}");
+            assert_eq!(host.status(), Status::Won);
+        }
+        for max in 2..=130 {
+            let mut host = evil(1, max);
+            let steps = solver::autoplay(&mut host).unwrap();
+            assert_eq!(steps.len() as u32, forced(1, max), "1 to {max}");
+        }
+    }
+
+    #[test]
+    fn no_guesser_gets_away_with_fewer() {
+        let mut rng = rng::seeded(3);
+        for max in [2, 7, 64, 100] {
+            for _ in 0..50 {
+                let mut host = evil(1, max);
+                // Any guess still possible, picked at random: it sometimes guesses well, but the host never lets that pay off.
+                while !host.is_finished() {
+                    let remaining = host.remaining();
+                    host.guess(rng.gen_range(remaining));
+                }
+                assert!(host.attempts() >= forced(1, max), "1 to {max}");
+            }
+        }
+    }
+
+    #[test]
+    fn it_concedes_only_when_one_number_is_left() {
+        let mut host = evil(1, 4);
+        // With two numbers left, neither is admitted.
+        assert_eq!(host.guess(2), Outcome::TooSmall);
+        assert_eq!(host.remaining(), 3..=4);
+        assert_eq!(host.guess(4), Outcome::TooBig);
+        assert_eq!(host.remaining(), 3..=3);
+        assert_eq!(host.guess(3), Outcome::Win);
+
+        let mut host = evil(1, 50);
+        let mut rng = rng::seeded(9);
+        while !host.is_finished() {
+            let before = host.remaining();
+            if host.guess(rng.gen_range(1..=50)) == Outcome::Win {
+                assert_eq!(before.start(), before.end());
+            }
+        }
+    }
+
+    #[test]
+    fn the_attempt_limit_still_applies() {
+        let mut host = EvilHost::new(Rules {
+            bounds: Bounds::new(1, 100).unwrap(),
+            max_attempts: Some(3),
+        });
+        for guess in [50, 25, 12] {
+            host.guess(guess);
+        }
+        assert_eq!(host.status(), Status::Lost);
+        assert_eq!(host.guess(1), Outcome::Invalid(InvalidGuess::Finished));
+        let secret = host.settle(&mut rng::seeded(1)).unwrap();
+        assert!(host.consistent_with(secret));
+    }
+}
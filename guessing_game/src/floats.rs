@@ -0,0 +1,503 @@
+// The guessing game with real numbers: the secret is an f64 somewhere in a range, and a guess wins when it is close enough to it.
+//
+// f64 is Rust's default floating-point type: an IEEE-754 double, with about 15 to 17 significant decimal digits. A secret drawn from a range almost never
+// has a short decimal form, so nobody could type it exactly; instead a guess wins when it is within a tolerance of the secret, either absolute
+// (within 0.01) or relative (within 0.1% of the secret). Any other guess is Too small or Too big, decided by comparing the two numbers themselves, which is exact
+// for floats: only the distance used for the tolerance is rounded, so a guess is never called Too small when it's really bigger than the secret.
+//
+// NaN and the infinities are valid f64 values, and `"nan".parse::<f64>()` even succeeds, but neither can be the secret, so guesses like that are refused.
+// NaN also breaks `Ord`: it isn't equal to itself, which is why f64 only implements `PartialOrd` and this file compares with `total_cmp`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::game::Status;
+
+/// How close a guess has to be to the secret to win.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// Within this distance of the secret.
+    Absolute(f64),
+    /// Within this fraction of the secret's size: 0.001 is 0.1%.
+    Relative(f64),
+}
+
+impl Tolerance {
+    /// The classic tolerance: two decimal places.
+    pub const DEFAULT: Tolerance = Tolerance::Absolute(0.01);
+
+    /// How far from `secret` a winning guess may be.
+    pub fn allowance(self, secret: f64) -> f64 {
+        match self {
+            Tolerance::Absolute(epsilon) => epsilon,
+            Tolerance::Relative(fraction) => fraction * secret.abs(),
+        }
+    }
+
+    /// Whether `guess` is close enough to `secret` to win.
+    pub fn accepts(self, guess: f64, secret: f64) -> bool {
+        // The subtraction rounds, so a guess right at the edge may land either side of it; but both numbers are finite and in the range, so it can't overflow.
+        (guess - secret).abs() <= self.allowance(secret)
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Tolerance {
+        Tolerance::DEFAULT
+    }
+}
+
+impl fmt::Display for Tolerance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tolerance::Absolute(epsilon) => write!(f, "{epsilon}"),
+            Tolerance::Relative(fraction) => write!(f, "{}%", fraction * 100.0),
+        }
+    }
+}
+
+/// Reads a tolerance: a plain number like `0.01` or `1e-3` is absolute, and one ending in `%` like `0.5%` is relative.
+impl FromStr for Tolerance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Tolerance, String> {
+        let error = || format!("a tolerance looks like 0.01 or 0.5%, got '{s}'");
+        let s = s.trim();
+        let (number, relative) = match s.strip_suffix('%') {
+            Some(percent) => (percent.trim(), true),
+            None => (s, false),
+        };
+        let value: f64 = number.parse().map_err(|_| error())?;
+        if !value.is_finite() || value <= 0.0 {
+            return Err(format!(
+                "the tolerance must be a positive, finite number, got '{s}'"
+            ));
+        }
+        Ok(if relative {
+            Tolerance::Relative(value / 100.0)
+        } else {
+            Tolerance::Absolute(value)
+        })
+    }
+}
+
+/// The range the secret is drawn from and how close a guess has to be.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatRules {
+    min: f64,
+    max: f64,
+    tolerance: Tolerance,
+}
+
+impl FloatRules {
+    /// The classic range, 1 to 100, to two decimal places.
+    pub const DEFAULT: FloatRules = FloatRules {
+        min: 1.0,
+        max: 100.0,
+        tolerance: Tolerance::DEFAULT,
+    };
+
+    /// Builds the rules for secrets in `min..=max`. Both ends must be finite with `min < max`, and the range's width must be finite too, so that halving it always works.
+    ///
+    /// A relative tolerance can't be met near 0, where the allowance shrinks to nothing, so with one the range may not include 0;
+    /// and no tolerance may be finer than the gap between neighbouring f64 values in the range.
+    pub fn new(min: f64, max: f64, tolerance: Tolerance) -> Result<FloatRules, String> {
+        if !min.is_finite() || !max.is_finite() {
+            return Err(format!(
+                "the range must be finite numbers, got {min} to {max}"
+            ));
+        }
+        if min >= max {
+            return Err(format!(
+                "the minimum ({min}) must be smaller than the maximum ({max})"
+            ));
+        }
+        if !(max - min).is_finite() {
+            return Err(format!(
+                "the range from {min:e} to {max:e} is too wide for an f64 to measure"
+            ));
+        }
+        let (Tolerance::Absolute(value) | Tolerance::Relative(value)) = tolerance;
+        if !value.is_finite() || value <= 0.0 {
+            return Err(format!(
+                "the tolerance must be a positive, finite number, got {value}"
+            ));
+        }
+        if let Tolerance::Relative(_) = tolerance {
+            if min <= 0.0 && 0.0 <= max {
+                return Err(format!(
+                    "a relative tolerance can't be met near 0, so the range can't include it (got {min} to {max})"
+                ));
+            }
+        }
+        // Near x, consecutive f64 values are about f64::EPSILON * |x| apart, and that gap grows with x.
+        // A tolerance finer than the gap could only be met by typing the secret exactly, digit for digit. An absolute tolerance has to clear the
+        // widest gap in the range, the one just below its far end; a relative one grows with x just like the gap does, so it only has to clear EPSILON.
+        match tolerance {
+            Tolerance::Absolute(epsilon) => {
+                let largest = min.abs().max(max.abs());
+                // The gap below rather than above: above f64::MAX there is only infinity, which would make the gap infinite too.
+                let spacing = largest - largest.next_down();
+                if epsilon < spacing {
+                    return Err(format!(
+                        "a tolerance of {tolerance} is finer than an f64 can tell apart near {largest:e}, where numbers are {spacing:e} apart"
+                    ));
+                }
+            }
+            Tolerance::Relative(fraction) => {
+                if fraction < f64::EPSILON {
+                    return Err(format!(
+                        "a tolerance of {tolerance} is finer than an f64 can tell apart; relative tolerances start at {}%",
+                        f64::EPSILON * 100.0
+                    ));
+                }
+            }
+        }
+        Ok(FloatRules {
+            min,
+            max,
+            tolerance,
+        })
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn tolerance(&self) -> Tolerance {
+        self.tolerance
+    }
+
+    /// The fewest guesses that always suffice: bisection halves what's left with every guess, and its middle wins once half of what's left is within the smallest allowance in the range.
+    pub fn optimal_attempts(&self) -> u32 {
+        // The smallest allowance is at the end nearest 0; for an absolute tolerance every secret gets the same one.
+        let smallest = self.tolerance.allowance(self.min.abs().min(self.max.abs()));
+        let mut half_width = (self.max - self.min) / 2.0;
+        let mut attempts = 1;
+        // Halving by 2 is exact for floats, so this counts the same halvings the solver does.
+        while half_width > smallest {
+            half_width /= 2.0;
+            attempts += 1;
+        }
+        attempts
+    }
+
+    /// Picks the secret, anywhere in the range.
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        rng.gen_range(self.min..=self.max)
+    }
+
+    /// Turns a line of input into a guess. Decimals (`3.14`, `.5`, `-2`) and scientific notation (`6.02e23`, `1E-3`) are both accepted.
+    pub fn parse(&self, input: &str) -> Result<f64, InvalidFloat> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(InvalidFloat::Empty);
+        }
+        let value: f64 = match input.parse() {
+            Ok(value) => value,
+            // A decimal comma is the commonest slip; say so rather than just "not a number".
+            Err(_) if input.contains(',') => return Err(InvalidFloat::DecimalComma),
+            Err(_) => return Err(InvalidFloat::NotANumber),
+        };
+        if value.is_nan() {
+            return Err(InvalidFloat::NotFinite);
+        }
+        if value.is_infinite() {
+            // "inf" was meant to be infinite; "1e999" is just a number too big for an f64, which parse rounds to infinity.
+            let spelled_out = input.chars().any(|c| c.eq_ignore_ascii_case(&'i'));
+            return Err(if spelled_out {
+                InvalidFloat::NotFinite
+            } else {
+                InvalidFloat::OutOfRange(*self)
+            });
+        }
+        if value < self.min || value > self.max {
+            return Err(InvalidFloat::OutOfRange(*self));
+        }
+        Ok(value)
+    }
+}
+
+impl Default for FloatRules {
+    fn default() -> FloatRules {
+        FloatRules::DEFAULT
+    }
+}
+
+/// Why a line of input was not accepted as a guess.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InvalidFloat {
+    Empty,
+    NotANumber,
+    /// Something like `3,5`, which Rust (like most programming languages) doesn't read as a number.
+    DecimalComma,
+    /// NaN or infinity.
+    NotFinite,
+    OutOfRange(FloatRules),
+    Finished,
+}
+
+impl fmt::Display for InvalidFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidFloat::Empty => write!(f, "Please type a number."),
+            InvalidFloat::NotANumber => write!(f, "That is not a number."),
+            InvalidFloat::DecimalComma => {
+                write!(f, "Use a point for decimals: 3.5, not 3,5.")
+            }
+            InvalidFloat::NotFinite => {
+                write!(
+                    f,
+                    "NaN and infinity are never the secret; guess a real number."
+                )
+            }
+            InvalidFloat::OutOfRange(rules) => write!(
+                f,
+                "Your guess must be between {} and {}.",
+                rules.min, rules.max
+            ),
+            InvalidFloat::Finished => write!(f, "This round is already over."),
+        }
+    }
+}
+
+/// One round of the float game.
+#[derive(Debug, Clone)]
+pub struct FloatGame {
+    rules: FloatRules,
+    secret: f64,
+    max_attempts: Option<u32>,
+    /// Each guess with how it compares with the secret; Equal means it was close enough to win.
+    guesses: Vec<(f64, Ordering)>,
+    // The numbers still consistent with every answer. A guess that was too small is itself ruled out, but a float range is kept closed at both ends for simplicity.
+    low: f64,
+    high: f64,
+    status: Status,
+}
+
+impl FloatGame {
+    /// Starts a round with a known secret, for tests and solvers.
+    pub fn new(rules: FloatRules, secret: f64, max_attempts: Option<u32>) -> FloatGame {
+        assert!(
+            rules.min <= secret && secret <= rules.max,
+            "the secret must be in the range"
+        );
+        FloatGame {
+            rules,
+            secret,
+            max_attempts,
+            guesses: Vec::new(),
+            low: rules.min,
+            high: rules.max,
+            status: Status::Playing,
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(
+        rules: FloatRules,
+        max_attempts: Option<u32>,
+        rng: &mut R,
+    ) -> FloatGame {
+        let secret = rules.random(rng);
+        FloatGame::new(rules, secret, max_attempts)
+    }
+
+    /// Parses a line of input and answers it as a guess.
+    pub fn submit(&mut self, input: &str) -> Result<Ordering, InvalidFloat> {
+        let guess = self.rules.parse(input)?;
+        self.guess(guess)
+    }
+
+    /// Answers an already parsed guess: `Equal` if it is within the tolerance, otherwise how it compares with the secret. A rejected guess doesn't count as an attempt.
+    pub fn guess(&mut self, guess: f64) -> Result<Ordering, InvalidFloat> {
+        if self.is_finished() {
+            return Err(InvalidFloat::Finished);
+        }
+        if !guess.is_finite() {
+            return Err(InvalidFloat::NotFinite);
+        }
+        if guess < self.rules.min || guess > self.rules.max {
+            return Err(InvalidFloat::OutOfRange(self.rules));
+        }
+        // The tolerance is checked first, so a guess close enough wins even if it is a little off; only a guess outside it is compared,
+        // and that comparison is exact, so it can't come out Equal.
+        let ordering = if self.rules.tolerance.accepts(guess, self.secret) {
+            Ordering::Equal
+        } else {
+            guess.total_cmp(&self.secret)
+        };
+        match ordering {
+            Ordering::Less => self.low = self.low.max(guess),
+            Ordering::Greater => self.high = self.high.min(guess),
+            Ordering::Equal => {}
+        }
+        self.guesses.push((guess, ordering));
+        if ordering == Ordering::Equal {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        Ok(ordering)
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    pub fn rules(&self) -> &FloatRules {
+        &self.rules
+    }
+
+    pub fn secret(&self) -> f64 {
+        self.secret
+    }
+
+    /// Every guess so far with its answer, in order.
+    pub fn guesses(&self) -> &[(f64, Ordering)] {
+        &self.guesses
+    }
+
+    /// The numbers still consistent with every answer, as `(low, high)`.
+    pub fn remaining(&self) -> (f64, f64) {
+        (self.low, self.high)
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+/// Plays `game` to the end by bisection, always guessing the middle of what's left, and returns how many guesses it took.
+///
+/// Needing more than `optimal_attempts` guesses means the game's answers were inconsistent, and is returned as an error.
+pub fn autoplay(game: &mut FloatGame) -> Result<u32, String> {
+    let bound = game.rules().optimal_attempts();
+    while !game.is_finished() {
+        let (low, high) = game.remaining();
+        // low + (high - low) / 2 rather than (low + high) / 2, which could overflow to infinity for a range near the limits of f64.
+        let guess = low + (high - low) / 2.0;
+        game.guess(guess)
+            .map_err(|reason| format!("the game rejected {guess}: {reason}"))?;
+        if game.attempts() > bound {
+            return Err(format!(
+                "bisection needed more than {bound} guesses, the most this range and tolerance should take"
+            ));
+        }
+    }
+    Ok(game.attempts())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerances_are_read_as_absolute_or_relative() {
+        assert_eq!("0.01".parse(), Ok(Tolerance::Absolute(0.01)));
+        assert_eq!(" 1e-3 ".parse(), Ok(Tolerance::Absolute(0.001)));
+        assert_eq!("0.5 %".parse(), Ok(Tolerance::Relative(0.005)));
+        for bad in ["", "%", "0", "-1", "0%", "nan", "inf", "1e999", "a%"] {
+            assert!(bad.parse::<Tolerance>().is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn a_guess_right_at_the_edge_of_the_tolerance_wins() {
+        let absolute = Tolerance::Absolute(0.5);
+        assert!(absolute.accepts(1.5, 1.0));
+        assert!(absolute.accepts(0.5, 1.0));
+        assert!(!absolute.accepts(1.5f64.next_up(), 1.0));
+
+        let relative = Tolerance::Relative(0.5);
+        assert!(relative.accepts(-3.0, -2.0));
+        assert!(!relative.accepts(-3.5, -2.0));
+    }
+
+    #[test]
+    fn a_relative_tolerance_cannot_span_zero() {
+        let relative = Tolerance::Relative(0.01);
+        assert!(FloatRules::new(-1.0, 1.0, relative).is_err());
+        assert!(FloatRules::new(0.0, 1.0, relative).is_err());
+        assert!(FloatRules::new(-2.0, -1.0, relative).is_ok());
+        assert!(FloatRules::new(-1.0, 1.0, Tolerance::Absolute(0.01)).is_ok());
+    }
+
+    #[test]
+    fn a_tolerance_finer_than_an_f64_is_refused() {
+        assert!(FloatRules::new(1.0, 2.0, Tolerance::Absolute(f64::EPSILON)).is_ok());
+        assert!(FloatRules::new(1.0, 2.0, Tolerance::Absolute(f64::EPSILON / 4.0)).is_err());
+        assert!(FloatRules::new(1e6, 2e6, Tolerance::Relative(f64::EPSILON)).is_ok());
+        assert!(FloatRules::new(1e6, 2e6, Tolerance::Relative(f64::EPSILON / 2.0)).is_err());
+    }
+
+    #[test]
+    fn the_largest_f64_has_a_finite_spacing() {
+        let error = FloatRules::new(0.0, f64::MAX, Tolerance::Absolute(1.0)).unwrap_err();
+        assert!(!error.contains("inf"), "{error}");
+        let spacing = f64::MAX - f64::MAX.next_down();
+        let rules = FloatRules::new(0.0, f64::MAX, Tolerance::Absolute(spacing)).unwrap();
+        for secret in [0.0, 1.0, f64::MAX.next_down(), f64::MAX] {
+            let mut game = FloatGame::new(rules, secret, None);
+            assert!(autoplay(&mut game).unwrap() <= rules.optimal_attempts());
+        }
+    }
+
+    #[test]
+    fn ranges_must_be_finite_and_in_order() {
+        let tolerance = Tolerance::DEFAULT;
+        assert!(FloatRules::new(f64::NAN, 1.0, tolerance).is_err());
+        assert!(FloatRules::new(0.0, f64::INFINITY, tolerance).is_err());
+        assert!(FloatRules::new(2.0, 1.0, tolerance).is_err());
+        assert!(FloatRules::new(-f64::MAX, f64::MAX, Tolerance::Absolute(f64::MAX)).is_err());
+    }
+
+    #[test]
+    fn guesses_that_are_not_real_numbers_are_explained() {
+        let rules = FloatRules::DEFAULT;
+        assert_eq!(rules.parse(" 2.5 "), Ok(2.5));
+        assert_eq!(rules.parse("3,5"), Err(InvalidFloat::DecimalComma));
+        assert_eq!(rules.parse("NaN"), Err(InvalidFloat::NotFinite));
+        assert_eq!(rules.parse("-inf"), Err(InvalidFloat::NotFinite));
+        assert_eq!(rules.parse("1e999"), Err(InvalidFloat::OutOfRange(rules)));
+        assert_eq!(rules.parse("0.5"), Err(InvalidFloat::OutOfRange(rules)));
+    }
+
+    #[test]
+    fn autoplay_wins_within_the_bound() {
+        let rules = FloatRules::DEFAULT;
+        for secret in [1.0, 1.005, 37.25, 99.999, 100.0] {
+            let mut game = FloatGame::new(rules, secret, None);
+            assert!(autoplay(&mut game).unwrap() <= rules.optimal_attempts());
+            assert_eq!(game.status(), Status::Won);
+        }
+    }
+
+    #[test]
+    fn autoplay_stops_when_the_attempts_run_out() {
+        let mut game = FloatGame::new(FloatRules::DEFAULT, 40.0, Some(3));
+        assert_eq!(autoplay(&mut game), Ok(3));
+        assert_eq!(game.status(), Status::Lost);
+    }
+}
@@ -0,0 +1,722 @@
+// The Rng trait defines methods that random number generators implement, and this trait must be in scope for us to use those methods.
+use rand::Rng;
+// The Ordering type is another enum and has the variants Less, Greater, and Equal. These are the three outcomes that are possible when you compare two values.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::bounds::Bounds;
+use crate::difficulty::{self, Rules};
+use crate::solver;
+
+/// What the engine answers to a single submitted guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win,
+    /// The input was not a usable guess; it does not count as an attempt.
+    Invalid(InvalidGuess),
+}
+
+/// Why a line of input was not accepted as a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidGuess {
+    /// The line was blank.
+    Empty,
+    NotANumber,
+    /// A negative number in a game whose range has none.
+    Negative(Bounds),
+    /// The number parsed but lies outside the game's range, so comparing it would tell the player nothing.
+    OutOfRange(Bounds),
+    /// The digits describe a number too large (or too small) for even an i128 to hold.
+    Overflow(Bounds),
+    /// The round is already over.
+    Finished,
+    /// Earlier answers already ruled the number out, and the round's `Redundancy` is `Free`.
+    Redundant(Redundant),
+}
+
+/// A guess that couldn't have been the secret, given the answers before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Redundant {
+    pub guess: i128,
+    /// The numbers still possible when the guess was made.
+    pub low: i128,
+    pub high: i128,
+    /// The same number was guessed earlier in the round.
+    pub repeated: bool,
+}
+
+impl fmt::Display for Redundant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Redundant {
+            guess, low, high, ..
+        } = self;
+        if self.repeated {
+            write!(
+                f,
+                "You already guessed {guess}; the secret is between {low} and {high}."
+            )
+        } else {
+            write!(
+                f,
+                "Earlier answers already ruled out {guess}; the secret is between {low} and {high}."
+            )
+        }
+    }
+}
+
+/// What a round does with a redundant guess.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Redundancy {
+    /// Answer it like any other guess, without a word.
+    Allow,
+    /// Answer it and count it, but point out that it was wasted.
+    #[default]
+    Warn,
+    /// Reject it without counting it as an attempt.
+    Free,
+}
+
+impl Redundancy {
+    pub fn name(self) -> &'static str {
+        match self {
+            Redundancy::Allow => "allow",
+            Redundancy::Warn => "warn",
+            Redundancy::Free => "free",
+        }
+    }
+}
+
+impl FromStr for Redundancy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Redundancy, String> {
+        [Redundancy::Allow, Redundancy::Warn, Redundancy::Free]
+            .into_iter()
+            .find(|redundancy| redundancy.name() == s.trim())
+            .ok_or_else(|| format!("unknown redundancy '{s}' (expected allow, warn or free)"))
+    }
+}
+
+impl fmt::Display for InvalidGuess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidGuess::Empty => write!(f, "Please type a number."),
+            InvalidGuess::NotANumber => write!(f, "That is not a number."),
+            InvalidGuess::Negative(bounds) => {
+                write!(
+                    f,
+                    "Negative numbers are never the secret; guess between {bounds}."
+                )
+            }
+            InvalidGuess::OutOfRange(bounds) => {
+                write!(f, "Your guess must be between {bounds}.")
+            }
+            InvalidGuess::Overflow(bounds) => {
+                write!(f, "That number is far too large; guess between {bounds}.")
+            }
+            InvalidGuess::Finished => write!(f, "This round is already over."),
+            InvalidGuess::Redundant(redundant) => {
+                write!(f, "{redundant} That guess doesn't count.")
+            }
+        }
+    }
+}
+
+impl Outcome {
+    /// Maps the result of `guess.cmp(&secret)` onto the answer the player sees.
+    pub fn from_ordering(ordering: Ordering) -> Outcome {
+        match ordering {
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+            Ordering::Equal => Outcome::Win,
+        }
+    }
+
+    /// The `guess.cmp(&secret)` an answer came from, or `None` for a rejected guess.
+    pub fn ordering(self) -> Option<Ordering> {
+        match self {
+            Outcome::TooSmall => Some(Ordering::Less),
+            Outcome::TooBig => Some(Ordering::Greater),
+            Outcome::Win => Some(Ordering::Equal),
+            Outcome::Invalid(_) => None,
+        }
+    }
+}
+
+/// Where a round stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Playing,
+    Won,
+    GaveUp,
+    /// Every allowed attempt was used without finding the secret.
+    Lost,
+}
+
+/// What `Catalog::number` puts between groups of digits in each language, and the spaces people type for the French one.
+const GROUP_SEPARATORS: [char; 5] = [',', '.', '\u{202f}', '\u{a0}', ' '];
+
+/// Turns a line of input into a guess for a game played within `bounds`, explaining why when it can't be one.
+///
+/// Digits may be grouped in threes the way the prompts show them, like 1,000 or 1.000, so a number can be typed back as it was read.
+pub fn parse_guess(bounds: Bounds, input: &str) -> Result<i128, InvalidGuess> {
+    let input = input.trim();
+    let input = ungroup(input).unwrap_or_else(|| input.to_string());
+    // We need to tell Rust the exact number type we want by using let guess: i128; the comparison with the secret means both sides are i128.
+    let guess: Result<i128, _> = input.parse();
+    match guess {
+        // parse produced the num value and placed it inside Ok
+        Ok(num) if num < 0 && bounds.min() >= 0 => Err(InvalidGuess::Negative(bounds)),
+        Ok(num) => Ok(num),
+        // ParseIntError::kind tells us why parsing failed, which lets us give a more helpful message than a catchall `Err(_)` would.
+        Err(error) => Err(match error.kind() {
+            IntErrorKind::Empty => InvalidGuess::Empty,
+            IntErrorKind::PosOverflow => InvalidGuess::Overflow(bounds),
+            IntErrorKind::NegOverflow if bounds.min() >= 0 => InvalidGuess::Negative(bounds),
+            IntErrorKind::NegOverflow => InvalidGuess::Overflow(bounds),
+            _ => InvalidGuess::NotANumber,
+        }),
+    }
+}
+
+// The digits of a number grouped in threes with one of GROUP_SEPARATORS, with the separators taken out; `None` if it isn't written that way.
+// Every group after the first must have exactly three digits, so 1,5 or 1.25 are still not numbers.
+fn ungroup(input: &str) -> Option<String> {
+    let (sign, digits) = match input.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", input),
+    };
+    let separator = digits.chars().find(|c| !c.is_ascii_digit())?;
+    if !GROUP_SEPARATORS.contains(&separator) {
+        return None;
+    }
+    let all_digits = |group: &str| group.bytes().all(|byte| byte.is_ascii_digit());
+    let mut groups = digits.split(separator);
+    let first = groups.next()?;
+    let grouped = (1..=3).contains(&first.len())
+        && all_digits(first)
+        && groups.all(|group| group.len() == 3 && all_digits(group));
+    grouped.then(|| format!("{sign}{}", digits.replace(separator, "")))
+}
+
+/// A single round against one secret number.
+///
+/// `Game` is a pure state machine: it does no I/O, so a driver feeds it guesses and decides how to present each `Outcome`.
+/// The secret is chosen once and stays fixed until the round is won, lost or given up.
+#[derive(Debug, Clone)]
+pub struct Game {
+    rules: Rules,
+    bounds: Bounds,
+    secret: i128,
+    attempts: u32,
+    status: Status,
+    // The smallest and largest numbers still consistent with every answer given so far.
+    low: i128,
+    high: i128,
+    redundancy: Redundancy,
+    // Every guess answered this round, in order.
+    guesses: Vec<i128>,
+    // How many times each number in `guesses` appears, so spotting a repeat doesn't mean scanning them all.
+    guessed: HashMap<i128, u32>,
+    redundant_guesses: u32,
+    // Set by each guess: whether it was redundant.
+    last_redundant: Option<Redundant>,
+    // low, high and hints as they were before each guess in `guesses`, so :undo can put them back.
+    before: Vec<(i128, i128, u32)>,
+    undos_left: u32,
+    hints: u32,
+}
+
+/// Why a guess couldn't be taken back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoError {
+    Finished,
+    NothingToUndo,
+    /// The round's undos are used up.
+    NoneLeft,
+}
+
+impl fmt::Display for UndoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UndoError::Finished => write!(f, "This round is already over."),
+            UndoError::NothingToUndo => write!(f, "There's no guess to take back yet."),
+            UndoError::NoneLeft => write!(f, "You have no undos left this round."),
+        }
+    }
+}
+
+impl Game {
+    /// Starts a game against a known secret, which must lie within the rules' bounds.
+    pub fn new(rules: Rules, secret: i128) -> Game {
+        let bounds = rules.bounds;
+        assert!(
+            bounds.contains(secret),
+            "secret {secret} is not between {bounds}"
+        );
+        Game {
+            rules,
+            bounds,
+            secret,
+            attempts: 0,
+            status: Status::Playing,
+            low: bounds.min(),
+            high: bounds.max(),
+            redundancy: Redundancy::default(),
+            guesses: Vec::new(),
+            guessed: HashMap::new(),
+            redundant_guesses: 0,
+            last_redundant: None,
+            before: Vec::new(),
+            undos_left: 0,
+            hints: 0,
+        }
+    }
+
+    /// Sets how many guesses the player may take back this round.
+    pub fn set_undos(&mut self, undos: u32) {
+        self.undos_left = undos;
+    }
+
+    /// Sets what happens to guesses earlier answers have already ruled out.
+    pub fn set_redundancy(&mut self, redundancy: Redundancy) {
+        self.redundancy = redundancy;
+    }
+
+    /// Starts a game against a secret within the rules' bounds drawn from `rng`.
+    pub fn random<R: Rng + ?Sized>(rules: Rules, rng: &mut R) -> Game {
+        let bounds = rules.bounds;
+        // The gen_range method is defined by the Rng trait. The kind of range expression we’re using here takes the form start..=end and is inclusive on the lower and upper bounds, so 1..=100 requests a number between 1 and 100.
+        Game::new(rules, rng.gen_range(bounds.min()..=bounds.max()))
+    }
+
+    /// Parses a line of player input and submits it as a guess.
+    pub fn submit(&mut self, input: &str) -> Outcome {
+        match self.parse(input) {
+            Ok(guess) => self.guess(guess),
+            Err(reason) => {
+                self.last_redundant = None;
+                Outcome::Invalid(reason)
+            }
+        }
+    }
+
+    /// Turns a line of input into a guess, explaining why when it can't be one.
+    pub fn parse(&self, input: &str) -> Result<i128, InvalidGuess> {
+        parse_guess(self.bounds, input)
+    }
+
+    /// Submits an already parsed guess. Guesses outside the range, or made after the round is over, are rejected without counting as an attempt.
+    pub fn guess(&mut self, guess: i128) -> Outcome {
+        self.last_redundant = None;
+        if self.is_finished() {
+            return Outcome::Invalid(InvalidGuess::Finished);
+        }
+        if !self.bounds.contains(guess) {
+            return Outcome::Invalid(InvalidGuess::OutOfRange(self.bounds));
+        }
+
+        self.last_redundant = self.redundant(guess);
+        if let Some(redundant) = self.last_redundant {
+            self.redundant_guesses += 1;
+            if self.redundancy == Redundancy::Free {
+                return Outcome::Invalid(InvalidGuess::Redundant(redundant));
+            }
+        }
+        self.guesses.push(guess);
+        *self.guessed.entry(guess).or_insert(0) += 1;
+        self.before.push((self.low, self.high, self.hints));
+        self.attempts += 1;
+
+        //  The cmp method compares two values and can be called on anything that can be compared. It takes a reference to whatever you want to compare with
+        // Then it returns a variant of the Ordering enum; from_ordering decides which answer each variant becomes.
+        let outcome = Outcome::from_ordering(guess.cmp(&self.secret));
+        match outcome {
+            // guess < secret, so guess + 1 can't overflow; likewise for guess - 1 below.
+            Outcome::TooSmall => self.low = self.low.max(guess + 1),
+            Outcome::TooBig => self.high = self.high.min(guess - 1),
+            Outcome::Win => {
+                self.low = guess;
+                self.high = guess;
+                self.status = Status::Won;
+            }
+            Outcome::Invalid(_) => {}
+        }
+        // The last allowed guess still gets its Too small/Too big answer; the round just ends with it.
+        if !self.is_finished() && self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        outcome
+    }
+
+    /// Takes back the latest guess: it stops counting as an attempt, and the numbers it ruled out are possible again. Returns the guess.
+    ///
+    /// The player still heard the answer, which is why undos are rationed. Hints taken since the guess are taken back with it, cost and all. A redundant guess taken back still counts in `redundant_guesses`.
+    pub fn undo(&mut self) -> Result<i128, UndoError> {
+        if self.is_finished() {
+            return Err(UndoError::Finished);
+        }
+        if self.guesses.is_empty() {
+            return Err(UndoError::NothingToUndo);
+        }
+        if self.undos_left == 0 {
+            return Err(UndoError::NoneLeft);
+        }
+        self.undos_left -= 1;
+        self.attempts -= 1;
+        let (low, high, hints) = self.before.pop().expect("one entry per guess");
+        self.low = low;
+        self.high = high;
+        self.hints = hints;
+        let guess = self.guesses.pop().expect("checked above");
+        match self.guessed.get_mut(&guess) {
+            Some(count) if *count > 1 => *count -= 1,
+            _ => {
+                self.guessed.remove(&guess);
+            }
+        }
+        Ok(guess)
+    }
+
+    /// Halves the numbers still possible, keeping the half with the secret in it, and returns what's left; each hint costs `HINT_COST` points off the round's score.
+    ///
+    /// Returns `None`, and costs nothing, when the round is over or only one number is left.
+    pub fn hint(&mut self) -> Option<RangeInclusive<i128>> {
+        if self.is_finished() || self.low == self.high {
+            return None;
+        }
+        let middle = solver::midpoint(self.low, self.high);
+        if self.secret <= middle {
+            self.high = middle;
+        } else {
+            // middle < secret <= high, so middle + 1 can't overflow.
+            self.low = middle + 1;
+        }
+        self.hints += 1;
+        Some(self.remaining())
+    }
+
+    pub fn hints(&self) -> u32 {
+        self.hints
+    }
+
+    pub fn undos_left(&self) -> u32 {
+        self.undos_left
+    }
+
+    /// Every guess answered this round, with its answer.
+    pub fn history(&self) -> Vec<(i128, Outcome)> {
+        self.guesses
+            .iter()
+            .map(|&guess| (guess, Outcome::from_ordering(guess.cmp(&self.secret))))
+            .collect()
+    }
+
+    /// Ends the round without a win. Does nothing if the round is already over.
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    pub fn secret(&self) -> i128 {
+        self.secret
+    }
+
+    /// The number of valid guesses submitted so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// How many guesses are left, or `None` when there is no limit.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.rules
+            .max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// The round's score so far; see `difficulty::score`. Each hint taken costs `HINT_COST` of it.
+    pub fn score(&self) -> u32 {
+        difficulty::score(&self.rules, self.status == Status::Won, self.attempts)
+            .saturating_sub(self.hints * difficulty::HINT_COST)
+    }
+
+    /// Whether `guess` would be wasted: outside the numbers still possible, which every repeated miss is.
+    pub fn redundant(&self, guess: i128) -> Option<Redundant> {
+        if self.remaining().contains(&guess) {
+            return None;
+        }
+        Some(Redundant {
+            guess,
+            low: self.low,
+            high: self.high,
+            repeated: self.guessed.contains_key(&guess),
+        })
+    }
+
+    pub fn redundancy(&self) -> Redundancy {
+        self.redundancy
+    }
+
+    /// Why the latest guess was wasted, or `None` if it wasn't (or was rejected for another reason).
+    pub fn last_redundant(&self) -> Option<Redundant> {
+        self.last_redundant
+    }
+
+    /// How many guesses this round were redundant, counted or not.
+    pub fn redundant_guesses(&self) -> u32 {
+        self.redundant_guesses
+    }
+
+    /// The guesses answered so far, in order.
+    pub fn guesses(&self) -> &[i128] {
+        &self.guesses
+    }
+
+    /// The numbers that are still possible given every Too small/Too big answer so far.
+    pub fn remaining(&self) -> RangeInclusive<i128> {
+        self.low..=self.high
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(max_attempts: Option<u32>) -> Rules {
+        Rules {
+            bounds: Bounds::DEFAULT,
+            max_attempts,
+        }
+    }
+
+    #[test]
+    fn answers_guesses_against_a_fixed_secret() {
+        let mut game = Game::new(rules(None), 42);
+        assert_eq!(game.submit("10"), Outcome::TooSmall);
+        assert_eq!(game.submit("90"), Outcome::TooBig);
+        assert_eq!(game.secret(), 42);
+        assert_eq!(game.remaining(), 11..=89);
+        assert_eq!(game.submit(" 42\n"), Outcome::Win);
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(game.attempts(), 3);
+        assert_eq!(game.guesses(), [10, 90, 42]);
+    }
+
+    #[test]
+    fn rejected_input_does_not_count_as_an_attempt() {
+        let mut game = Game::new(rules(None), 42);
+        let bounds = Bounds::DEFAULT;
+        assert_eq!(game.submit(""), Outcome::Invalid(InvalidGuess::Empty));
+        assert_eq!(
+            game.submit("forty"),
+            Outcome::Invalid(InvalidGuess::NotANumber)
+        );
+        assert_eq!(
+            game.submit("-5"),
+            Outcome::Invalid(InvalidGuess::Negative(bounds))
+        );
+        assert_eq!(
+            game.submit("101"),
+            Outcome::Invalid(InvalidGuess::OutOfRange(bounds))
+        );
+        assert_eq!(
+            game.submit("1000000000000000000000000000000000000000"),
+            Outcome::Invalid(InvalidGuess::Overflow(bounds))
+        );
+        assert_eq!(game.attempts(), 0);
+        assert_eq!(game.status(), Status::Playing);
+    }
+
+    #[test]
+    fn negative_numbers_are_fine_when_the_range_has_them() {
+        let bounds = Bounds::new(-10, 10).unwrap();
+        assert_eq!(parse_guess(bounds, "-3"), Ok(-3));
+        assert_eq!(
+            parse_guess(bounds, &format!("{}0", i128::MIN)),
+            Err(InvalidGuess::Overflow(bounds))
+        );
+    }
+
+    #[test]
+    fn digits_may_be_grouped_as_the_prompts_show_them() {
+        let bounds = Bounds::new(-10_000_000, 10_000_000).unwrap();
+        assert_eq!(parse_guess(bounds, "1,000"), Ok(1000));
+        assert_eq!(parse_guess(bounds, "-1.234.567"), Ok(-1_234_567));
+        assert_eq!(parse_guess(bounds, "12\u{202f}345"), Ok(12_345));
+        assert_eq!(parse_guess(bounds, "12 345"), Ok(12_345));
+        for odd in [
+            "1,5",
+            "1.25",
+            "1,000.000",
+            "1000,000",
+            ",100",
+            "1,,000",
+            "1,000,",
+        ] {
+            assert_eq!(
+                parse_guess(bounds, odd),
+                Err(InvalidGuess::NotANumber),
+                "{odd}"
+            );
+        }
+    }
+
+    #[test]
+    fn the_last_allowed_miss_loses_the_round() {
+        let mut game = Game::new(rules(Some(2)), 42);
+        assert_eq!(game.submit("50"), Outcome::TooBig);
+        assert_eq!(game.attempts_left(), Some(1));
+        assert_eq!(game.submit("30"), Outcome::TooSmall);
+        assert_eq!(game.status(), Status::Lost);
+        assert_eq!(game.attempts_left(), Some(0));
+        assert_eq!(game.submit("42"), Outcome::Invalid(InvalidGuess::Finished));
+        assert_eq!(game.score(), 0);
+    }
+
+    #[test]
+    fn giving_up_ends_only_a_round_in_play() {
+        let mut game = Game::new(rules(None), 42);
+        game.give_up();
+        assert_eq!(game.status(), Status::GaveUp);
+
+        let mut game = Game::new(rules(None), 42);
+        game.guess(42);
+        game.give_up();
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn the_secret_stays_the_same_for_the_whole_round() {
+        let mut rng = crate::rng::seeded(1);
+        let mut game = Game::random(rules(None), &mut rng);
+        let secret = game.secret();
+        assert!(Bounds::DEFAULT.contains(secret));
+        for guess in 1..=100 {
+            if game.guess(guess) == Outcome::Win {
+                assert_eq!(guess, secret);
+                break;
+            }
+            assert_eq!(game.secret(), secret);
+        }
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn wins_within_the_optimal_attempts_score_full_marks() {
+        let mut game = Game::new(rules(None), 50);
+        game.guess(50);
+        assert_eq!(game.score(), 1000);
+
+        let mut game = Game::new(rules(None), 1);
+        for guess in (1..=14).rev() {
+            game.guess(guess);
+        }
+        // 7 attempts are optimal for 100 numbers, and this took 14.
+        assert_eq!(game.score(), 500);
+    }
+
+    #[test]
+    fn undo_takes_back_the_guess_and_the_hints_after_it() {
+        let mut game = Game::new(rules(None), 42);
+        game.set_undos(1);
+        assert_eq!(game.undo(), Err(UndoError::NothingToUndo));
+        game.guess(50);
+        assert_eq!(game.hint(), Some(26..=49));
+        assert_eq!(game.hints(), 1);
+
+        assert_eq!(game.undo(), Ok(50));
+        assert_eq!(game.attempts(), 0);
+        assert_eq!(game.remaining(), 1..=100);
+        assert_eq!(game.hints(), 0);
+        assert_eq!(game.undos_left(), 0);
+        game.guess(60);
+        assert_eq!(game.undo(), Err(UndoError::NoneLeft));
+
+        game.guess(42);
+        assert_eq!(game.undo(), Err(UndoError::Finished));
+        assert_eq!(game.score(), 1000);
+    }
+
+    #[test]
+    fn each_hint_halves_what_is_left_and_costs_points() {
+        let mut game = Game::new(rules(None), 42);
+        assert_eq!(game.hint(), Some(1..=50));
+        assert_eq!(game.hint(), Some(26..=50));
+        game.guess(42);
+        assert_eq!(game.score(), 1000 - 2 * difficulty::HINT_COST);
+        // Nothing is left to narrow once the round is over.
+        assert_eq!(game.hint(), None);
+        assert_eq!(game.hints(), 2);
+    }
+
+    #[test]
+    fn redundant_guesses_are_warned_about_or_refused() {
+        let mut game = Game::new(rules(None), 42);
+        game.guess(50);
+        assert_eq!(game.guess(60), Outcome::TooBig);
+        assert_eq!(
+            game.last_redundant(),
+            Some(Redundant {
+                guess: 60,
+                low: 1,
+                high: 49,
+                repeated: false
+            })
+        );
+        assert_eq!(game.attempts(), 2);
+
+        let mut game = Game::new(rules(None), 42);
+        game.set_redundancy(Redundancy::Free);
+        game.guess(50);
+        assert!(matches!(
+            game.guess(50),
+            Outcome::Invalid(InvalidGuess::Redundant(Redundant { repeated: true, .. }))
+        ));
+        assert_eq!(game.attempts(), 1);
+        assert_eq!(game.redundant_guesses(), 1);
+    }
+
+    #[test]
+    fn a_guess_taken_back_is_no_longer_a_repeat() {
+        let mut game = Game::new(rules(None), 42);
+        game.set_undos(2);
+        game.guess(50);
+        game.guess(50);
+        assert_eq!(
+            game.last_redundant().map(|redundant| redundant.repeated),
+            Some(true)
+        );
+
+        game.undo().unwrap();
+        game.undo().unwrap();
+        game.guess(49);
+        game.guess(50);
+        assert_eq!(
+            game.last_redundant().map(|redundant| redundant.repeated),
+            Some(false)
+        );
+    }
+}
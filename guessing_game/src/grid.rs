@@ -0,0 +1,606 @@
+// The guessing game in two dimensions: the secret is a point on a grid, and each guess `x,y` is answered with the compass direction from the guess to the secret.
+//
+// Each axis is compared on its own with `Ordering`, exactly as the one-number game compares a guess: x grows to the east and y to the north, so a secret
+// that is further east and further south than the guess is answered SE, one in the same column but further north is answered N, and the guess itself is a hit.
+// Since every answer is two Too small/Too big answers at once, binary search works on both axes at the same time; `autoplay` shows it.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::bounds::Bounds;
+use crate::difficulty::Rules;
+use crate::game::Status;
+
+/// The longest side a grid may have.
+pub const MAX_SIDE: u32 = 1_000_000;
+/// The longest side `map` will draw; a bigger map wouldn't fit on a terminal anyway.
+pub const MAX_MAP_SIDE: u32 = 60;
+
+/// The size of the grid: columns 1 to `width` from west to east, and rows 1 to `height` from south to north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridRules {
+    width: u32,
+    height: u32,
+}
+
+impl GridRules {
+    /// The classic grid: 10 by 10.
+    pub const DEFAULT: GridRules = GridRules {
+        width: 10,
+        height: 10,
+    };
+
+    /// Builds a `width` by `height` grid; both sides must be between 1 and `MAX_SIDE`, and the grid must hold more than one point so there is something to guess.
+    pub fn new(width: u32, height: u32) -> Result<GridRules, String> {
+        for (side, value) in [("width", width), ("height", height)] {
+            if !(1..=MAX_SIDE).contains(&value) {
+                return Err(format!(
+                    "the grid's {side} must be between 1 and {MAX_SIDE}, got {value}"
+                ));
+            }
+        }
+        if width == 1 && height == 1 {
+            return Err("a 1x1 grid has nothing to guess".to_string());
+        }
+        Ok(GridRules { width, height })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (1..=self.width).contains(&point.x) && (1..=self.height).contains(&point.y)
+    }
+
+    /// The fewest guesses that always suffice: binary search halves both axes with every guess, so it needs as many as the longer side needs on its own.
+    pub fn optimal_attempts(&self) -> u32 {
+        let side = |length: u32| {
+            // A side of length 1 needs no searching; Bounds needs min < max, so it's counted by hand.
+            Bounds::new(1, length.into())
+                .map_or(1, |bounds| Rules::unlimited(bounds).optimal_attempts())
+        };
+        side(self.width).max(side(self.height))
+    }
+
+    /// Picks the secret point.
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Point {
+        Point {
+            x: rng.gen_range(1..=self.width),
+            y: rng.gen_range(1..=self.height),
+        }
+    }
+
+    /// Turns a line of input into a point. The coordinates may be separated by a comma, spaces or both, and wrapped in parentheses: `3,7`, `3 7` and `(3, 7)` are all the same point.
+    pub fn parse(&self, input: &str) -> Result<Point, InvalidPoint> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(InvalidPoint::Empty);
+        }
+        let inner = input
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap_or(input);
+        let parts: Vec<&str> = inner
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .collect();
+        let [x, y] = parts.as_slice() else {
+            return Err(InvalidPoint::Malformed);
+        };
+        // A negative or huge coordinate is just as far off the grid as 0 is, so anything that's a whole number gets the same answer.
+        let coordinate = |part: &str| match part.parse::<i128>() {
+            Ok(value) => Ok(u32::try_from(value).unwrap_or(0)),
+            Err(_) => Err(InvalidPoint::Malformed),
+        };
+        let point = Point {
+            x: coordinate(x)?,
+            y: coordinate(y)?,
+        };
+        if !self.contains(point) {
+            return Err(InvalidPoint::OffGrid(*self));
+        }
+        Ok(point)
+    }
+}
+
+impl Default for GridRules {
+    fn default() -> GridRules {
+        GridRules::DEFAULT
+    }
+}
+
+impl fmt::Display for GridRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Reads a size written `WIDTHxHEIGHT`, like `10x10`.
+impl FromStr for GridRules {
+    type Err = String;
+
+    fn from_str(size: &str) -> Result<GridRules, String> {
+        let bad = || format!("a grid size looks like 10x10, got '{size}'");
+        let (width, height) = size.trim().split_once(['x', 'X']).ok_or_else(bad)?;
+        let width = width.trim().parse().map_err(|_| bad())?;
+        let height = height.trim().parse().map_err(|_| bad())?;
+        GridRules::new(width, height)
+    }
+}
+
+/// A point on the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Why a line of input was not accepted as a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPoint {
+    Empty,
+    /// Not two whole numbers.
+    Malformed,
+    OffGrid(GridRules),
+    Finished,
+}
+
+impl fmt::Display for InvalidPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidPoint::Empty => write!(f, "Please type a point, like 3,7."),
+            InvalidPoint::Malformed => {
+                write!(f, "A point is two whole numbers, x and y, like 3,7.")
+            }
+            InvalidPoint::OffGrid(rules) => write!(
+                f,
+                "The point must be on the grid: x between 1 and {}, y between 1 and {}.",
+                rules.width, rules.height
+            ),
+            InvalidPoint::Finished => write!(f, "This round is already over."),
+        }
+    }
+}
+
+/// Where the secret lies, seen from a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    Hit,
+}
+
+impl Direction {
+    /// The direction from `guess` to `secret`, from how each of the secret's coordinates compares with the guess's.
+    pub fn between(guess: Point, secret: Point) -> Direction {
+        match (secret.x.cmp(&guess.x), secret.y.cmp(&guess.y)) {
+            (Ordering::Equal, Ordering::Greater) => Direction::North,
+            (Ordering::Greater, Ordering::Greater) => Direction::NorthEast,
+            (Ordering::Greater, Ordering::Equal) => Direction::East,
+            (Ordering::Greater, Ordering::Less) => Direction::SouthEast,
+            (Ordering::Equal, Ordering::Less) => Direction::South,
+            (Ordering::Less, Ordering::Less) => Direction::SouthWest,
+            (Ordering::Less, Ordering::Equal) => Direction::West,
+            (Ordering::Less, Ordering::Greater) => Direction::NorthWest,
+            (Ordering::Equal, Ordering::Equal) => Direction::Hit,
+        }
+    }
+
+    /// How the secret's x and y compare with the guess's: the inverse of `between`.
+    pub fn orderings(self) -> (Ordering, Ordering) {
+        match self {
+            Direction::North => (Ordering::Equal, Ordering::Greater),
+            Direction::NorthEast => (Ordering::Greater, Ordering::Greater),
+            Direction::East => (Ordering::Greater, Ordering::Equal),
+            Direction::SouthEast => (Ordering::Greater, Ordering::Less),
+            Direction::South => (Ordering::Equal, Ordering::Less),
+            Direction::SouthWest => (Ordering::Less, Ordering::Less),
+            Direction::West => (Ordering::Less, Ordering::Equal),
+            Direction::NorthWest => (Ordering::Less, Ordering::Greater),
+            Direction::Hit => (Ordering::Equal, Ordering::Equal),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::North => "N",
+            Direction::NorthEast => "NE",
+            Direction::East => "E",
+            Direction::SouthEast => "SE",
+            Direction::South => "S",
+            Direction::SouthWest => "SW",
+            Direction::West => "W",
+            Direction::NorthWest => "NW",
+            Direction::Hit => "hit",
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The rectangle of points still consistent with every answer so far, corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub west: u32,
+    pub east: u32,
+    pub south: u32,
+    pub north: u32,
+}
+
+impl Region {
+    /// The whole grid.
+    pub fn all(rules: &GridRules) -> Region {
+        Region {
+            west: 1,
+            east: rules.width,
+            south: 1,
+            north: rules.height,
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.west..=self.east).contains(&point.x) && (self.south..=self.north).contains(&point.y)
+    }
+
+    /// How many points are left.
+    pub fn size(&self) -> u64 {
+        let side = |low: u32, high: u32| u64::from(high.saturating_sub(low)) + 1;
+        if self.west > self.east || self.south > self.north {
+            return 0;
+        }
+        side(self.west, self.east) * side(self.south, self.north)
+    }
+
+    /// Keeps only the points that would have given `direction` as the answer to `guess`. Each axis narrows just like an interval of numbers does.
+    pub fn narrow(&mut self, guess: Point, direction: Direction) {
+        let (x, y) = direction.orderings();
+        narrow_axis(&mut self.west, &mut self.east, guess.x, x);
+        narrow_axis(&mut self.south, &mut self.north, guess.y, y);
+    }
+}
+
+// low and high never pass 0 or u32::MAX here: a guess is on the grid, so there's always room for the +1 or -1 a consistent answer needs.
+fn narrow_axis(low: &mut u32, high: &mut u32, guess: u32, ordering: Ordering) {
+    match ordering {
+        Ordering::Greater => *low = (*low).max(guess.saturating_add(1)),
+        Ordering::Less => *high = (*high).min(guess.saturating_sub(1)),
+        Ordering::Equal => {
+            *low = guess;
+            *high = guess;
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "x {} to {}, y {} to {}",
+            self.west, self.east, self.south, self.north
+        )
+    }
+}
+
+/// One round of the grid game.
+#[derive(Debug, Clone)]
+pub struct GridGame {
+    rules: GridRules,
+    secret: Point,
+    max_attempts: Option<u32>,
+    guesses: Vec<(Point, Direction)>,
+    region: Region,
+    status: Status,
+}
+
+impl GridGame {
+    /// Starts a round with a known secret, for tests and solvers.
+    pub fn new(rules: GridRules, secret: Point, max_attempts: Option<u32>) -> GridGame {
+        assert!(rules.contains(secret), "the secret must be on the grid");
+        GridGame {
+            rules,
+            secret,
+            max_attempts,
+            guesses: Vec::new(),
+            region: Region::all(&rules),
+            status: Status::Playing,
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(
+        rules: GridRules,
+        max_attempts: Option<u32>,
+        rng: &mut R,
+    ) -> GridGame {
+        let secret = rules.random(rng);
+        GridGame::new(rules, secret, max_attempts)
+    }
+
+    /// Parses a line of input and answers it as a guess.
+    pub fn submit(&mut self, input: &str) -> Result<Direction, InvalidPoint> {
+        let point = self.rules.parse(input)?;
+        self.guess(point)
+    }
+
+    /// Answers an already parsed guess. A rejected guess doesn't count as an attempt.
+    pub fn guess(&mut self, point: Point) -> Result<Direction, InvalidPoint> {
+        if self.is_finished() {
+            return Err(InvalidPoint::Finished);
+        }
+        if !self.rules.contains(point) {
+            return Err(InvalidPoint::OffGrid(self.rules));
+        }
+        let direction = Direction::between(point, self.secret);
+        self.guesses.push((point, direction));
+        self.region.narrow(point, direction);
+        if direction == Direction::Hit {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        Ok(direction)
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    pub fn rules(&self) -> &GridRules {
+        &self.rules
+    }
+
+    pub fn secret(&self) -> Point {
+        self.secret
+    }
+
+    /// Every guess so far with its answer, in order.
+    pub fn guesses(&self) -> &[(Point, Direction)] {
+        &self.guesses
+    }
+
+    /// The points still consistent with every answer.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    /// Draws the grid with north at the top: `.` for points still possible, `#` for points the answers ruled out, and `o` for each guess.
+    /// Once the round is over the secret is shown as `*`. Returns `None` for grids with a side longer than `MAX_MAP_SIDE`.
+    pub fn map(&self) -> Option<String> {
+        if self.rules.width > MAX_MAP_SIDE || self.rules.height > MAX_MAP_SIDE {
+            return None;
+        }
+        let label_width = self.rules.height.to_string().len();
+        let mut map = String::new();
+        for y in (1..=self.rules.height).rev() {
+            map.push_str(&format!("{y:>label_width$} "));
+            for x in 1..=self.rules.width {
+                let point = Point { x, y };
+                let cell = if self.is_finished() && point == self.secret {
+                    '*'
+                } else if self.guesses.iter().any(|&(guess, _)| guess == point) {
+                    'o'
+                } else if self.region.contains(point) {
+                    '.'
+                } else {
+                    '#'
+                };
+                map.push(cell);
+            }
+            map.push('\n');
+        }
+        // The last digit of each column's x, which is enough to count along the bottom.
+        map.push_str(&" ".repeat(label_width + 1));
+        for x in 1..=self.rules.width {
+            map.push(char::from_digit(x % 10, 10).expect("a single digit"));
+        }
+        Some(map)
+    }
+}
+
+/// One guess made by `autoplay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// The points the solver knew the secret was among before guessing.
+    pub region: Region,
+    pub guess: Point,
+    pub direction: Direction,
+}
+
+/// Plays `game` to the end with binary search on both axes at once, always guessing the middle of what's left, and returns every step taken.
+///
+/// An answer that leaves no point possible, or needing more guesses than `optimal_attempts`, means the game's answers were inconsistent, and is returned as an error.
+pub fn autoplay(game: &mut GridGame) -> Result<Vec<Step>, String> {
+    let bound = game.rules().optimal_attempts();
+    let mut region = Region::all(game.rules());
+    let mut steps = Vec::new();
+
+    while !game.is_finished() {
+        if region.size() == 0 {
+            return Err(format!(
+                "the answers so far leave no possible point (after {} guesses)",
+                steps.len()
+            ));
+        }
+        // Rounding down, like the one-number solver; the sides are far from u32::MAX, so the sum can't overflow.
+        let guess = Point {
+            x: (region.west + region.east) / 2,
+            y: (region.south + region.north) / 2,
+        };
+        let direction = game
+            .guess(guess)
+            .map_err(|reason| format!("the game rejected {guess}: {reason}"))?;
+        steps.push(Step {
+            region,
+            guess,
+            direction,
+        });
+        region.narrow(guess, direction);
+
+        if steps.len() as u32 > bound {
+            return Err(format!(
+                "binary search needed more than {bound} guesses, the most a {} grid should take",
+                game.rules()
+            ));
+        }
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: u32, y: u32) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn directions_compare_each_axis_on_its_own() {
+        let guess = point(5, 5);
+        let cases = [
+            (point(5, 8), Direction::North),
+            (point(9, 9), Direction::NorthEast),
+            (point(6, 5), Direction::East),
+            (point(7, 1), Direction::SouthEast),
+            (point(5, 4), Direction::South),
+            (point(1, 1), Direction::SouthWest),
+            (point(2, 5), Direction::West),
+            (point(4, 6), Direction::NorthWest),
+            (point(5, 5), Direction::Hit),
+        ];
+        for (secret, expected) in cases {
+            let direction = Direction::between(guess, secret);
+            assert_eq!(direction, expected, "secret {secret}");
+            // orderings gives back how each coordinate of the secret compares with the guess's.
+            assert_eq!(
+                direction.orderings(),
+                (secret.x.cmp(&guess.x), secret.y.cmp(&guess.y)),
+                "secret {secret}"
+            );
+        }
+    }
+
+    #[test]
+    fn points_parse_in_several_spellings() {
+        let rules = GridRules::DEFAULT;
+        for input in ["3,7", "3 7", " 3 , 7 ", "(3, 7)", "(3 7)"] {
+            assert_eq!(rules.parse(input), Ok(point(3, 7)), "{input:?}");
+        }
+        assert_eq!(rules.parse("10,10"), Ok(point(10, 10)));
+        assert_eq!(rules.parse(""), Err(InvalidPoint::Empty));
+        for input in ["3", "3,7,1", "a,b", "3.5,7", "(3,7", "3;7"] {
+            assert_eq!(
+                rules.parse(input),
+                Err(InvalidPoint::Malformed),
+                "{input:?}"
+            );
+        }
+        for input in ["0,5", "5,0", "11,5", "5,11", "-1,5", "99999999999999,5"] {
+            assert_eq!(
+                rules.parse(input),
+                Err(InvalidPoint::OffGrid(rules)),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn sizes_are_checked() {
+        assert_eq!("7x3".parse(), Ok(GridRules::new(7, 3).unwrap()));
+        assert_eq!(" 7 X 3 ".parse(), Ok(GridRules::new(7, 3).unwrap()));
+        for size in ["7", "7x", "x3", "7x3x1", "0x5", "1x1"] {
+            assert!(size.parse::<GridRules>().is_err(), "{size:?}");
+        }
+        assert!(GridRules::new(MAX_SIDE + 1, 2).is_err());
+        assert!(GridRules::new(1, 2).is_ok());
+    }
+
+    #[test]
+    fn narrowing_can_shrink_the_region_to_one_cell() {
+        let rules = GridRules::DEFAULT;
+        let secret = point(8, 3);
+        let mut region = Region::all(&rules);
+        assert_eq!(region.size(), 100);
+
+        for guess in [point(5, 5), point(8, 2), point(7, 3)] {
+            region.narrow(guess, Direction::between(guess, secret));
+            assert!(region.contains(secret), "after {guess}");
+        }
+        assert_eq!(
+            region,
+            Region {
+                west: 8,
+                east: 8,
+                south: 3,
+                north: 3
+            }
+        );
+        assert_eq!(region.size(), 1);
+
+        region.narrow(secret, Direction::Hit);
+        assert_eq!(region.size(), 1);
+        // An answer no point allows leaves nothing.
+        region.narrow(secret, Direction::East);
+        assert_eq!(region.size(), 0);
+    }
+
+    #[test]
+    fn autoplay_wins_within_its_bound_from_every_cell() {
+        for rules in [GridRules::new(7, 5).unwrap(), GridRules::new(1, 9).unwrap()] {
+            let bound = rules.optimal_attempts();
+            for x in 1..=rules.width() {
+                for y in 1..=rules.height() {
+                    let mut game = GridGame::new(rules, point(x, y), None);
+                    let steps = autoplay(&mut game).unwrap();
+                    assert_eq!(game.status(), Status::Won, "{x},{y} on {rules}");
+                    assert!(steps.len() as u32 <= bound, "{x},{y} on {rules}");
+                }
+            }
+        }
+        assert_eq!(GridRules::new(7, 5).unwrap().optimal_attempts(), 3);
+        assert_eq!(GridRules::new(1, 9).unwrap().optimal_attempts(), 4);
+    }
+}
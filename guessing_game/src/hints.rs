@@ -0,0 +1,241 @@
+// A second way to answer a guess: instead of saying which way the secret lies, say how close the guess came, and whether it came closer than the one before.
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::game::Game;
+
+/// How close a guess came, measured against the size of the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Proximity {
+    Burning,
+    Warm,
+    Cold,
+}
+
+impl Proximity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Proximity::Burning => "burning",
+            Proximity::Warm => "warm",
+            Proximity::Cold => "cold",
+        }
+    }
+}
+
+/// How a guess compares with the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Warmer,
+    Colder,
+    /// Exactly as far from the secret, which happens when the two guesses sit either side of it.
+    Same,
+}
+
+impl Trend {
+    pub fn name(self) -> &'static str {
+        match self {
+            Trend::Warmer => "warmer",
+            Trend::Colder => "colder",
+            Trend::Same => "same",
+        }
+    }
+}
+
+/// The answer to a missed guess in hot/cold mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub proximity: Proximity,
+    /// `None` for the first guess of a round, which has nothing to be compared with.
+    pub trend: Option<Trend>,
+}
+
+/// Where burning ends and cold begins, as percentages of the range's width.
+///
+/// A guess within `burning`% of the width from the secret is burning, within `warm`% is warm, and anything further is cold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    pub burning: u32,
+    pub warm: u32,
+}
+
+impl Thresholds {
+    pub const DEFAULT: Thresholds = Thresholds {
+        burning: 5,
+        warm: 20,
+    };
+
+    /// Checks that 0 < burning < warm <= 100.
+    pub fn new(burning: u32, warm: u32) -> Result<Thresholds, String> {
+        if 0 < burning && burning < warm && warm <= 100 {
+            Ok(Thresholds { burning, warm })
+        } else {
+            Err(format!(
+                "the thresholds must satisfy 0 < burning < warm <= 100, got {burning} and {warm}"
+            ))
+        }
+    }
+
+    /// Classifies a guess `distance` away from the secret in a range `width` wide.
+    pub fn proximity(&self, distance: u128, width: u128) -> Proximity {
+        // Comparing in f64 keeps the arithmetic from overflowing when the range spans all of i128; the rounding only matters at the exact edge of a threshold.
+        let percent = distance as f64 * 100.0 / width as f64;
+        if percent <= self.burning as f64 {
+            Proximity::Burning
+        } else if percent <= self.warm as f64 {
+            Proximity::Warm
+        } else {
+            Proximity::Cold
+        }
+    }
+}
+
+impl Default for Thresholds {
+    fn default() -> Thresholds {
+        Thresholds::DEFAULT
+    }
+}
+
+// Parses `burning,warm`, for example `5,20`.
+impl FromStr for Thresholds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Thresholds, String> {
+        let error = || {
+            format!(
+                "thresholds look like 5,20 (burning and warm, in percent of the range), got '{s}'"
+            )
+        };
+        let (burning, warm) = s.split_once(',').ok_or_else(error)?;
+        let burning = burning.trim().parse().map_err(|_| error())?;
+        let warm = warm.trim().parse().map_err(|_| error())?;
+        Thresholds::new(burning, warm)
+    }
+}
+
+/// How missed guesses are answered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Feedback {
+    /// Too small or Too big, the answer the book's game gives.
+    #[default]
+    Direction,
+    /// Burning, warm or cold, and warmer or colder than the last guess.
+    HotCold(Thresholds),
+}
+
+/// Gives hot/cold hints for one round, remembering how far off the previous guess was.
+#[derive(Debug, Clone)]
+pub struct HotCold {
+    thresholds: Thresholds,
+    previous_distance: Option<u128>,
+}
+
+impl HotCold {
+    pub fn new(thresholds: Thresholds) -> HotCold {
+        HotCold {
+            thresholds,
+            previous_distance: None,
+        }
+    }
+
+    /// The hint for `guess`, which has just been played in `game`.
+    pub fn hint(&mut self, game: &Game, guess: i128) -> Hint {
+        let distance = guess.abs_diff(game.secret());
+        let proximity = self.thresholds.proximity(distance, game.bounds().width());
+        // A smaller distance than last time is warmer, so compare the previous distance with this one.
+        let trend = self
+            .previous_distance
+            .map(|previous| match previous.cmp(&distance) {
+                Ordering::Greater => Trend::Warmer,
+                Ordering::Less => Trend::Colder,
+                Ordering::Equal => Trend::Same,
+            });
+        self.previous_distance = Some(distance);
+        Hint { proximity, trend }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::Bounds;
+    use crate::difficulty::Rules;
+
+    #[test]
+    fn thresholds_must_rise_from_burning_to_warm_within_100() {
+        assert_eq!(
+            Thresholds::new(5, 20),
+            Ok(Thresholds {
+                burning: 5,
+                warm: 20
+            })
+        );
+        assert!(Thresholds::new(1, 100).is_ok());
+        for (burning, warm) in [(0, 20), (20, 20), (30, 20), (5, 101), (100, 100)] {
+            assert!(Thresholds::new(burning, warm).is_err(), "{burning},{warm}");
+        }
+    }
+
+    #[test]
+    fn thresholds_parse_from_burning_comma_warm() {
+        assert_eq!("5,20".parse(), Ok(Thresholds::DEFAULT));
+        assert_eq!(" 10 , 40 ".parse(), Thresholds::new(10, 40));
+        for text in ["", "5", "5;20", "5,20,30", "a,20", "-5,20", "20,5", "5,120"] {
+            assert!(text.parse::<Thresholds>().is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn a_guess_right_on_a_threshold_gets_the_warmer_answer() {
+        let thresholds = Thresholds::DEFAULT;
+        let cases = [
+            (0, Proximity::Burning),
+            (5, Proximity::Burning),
+            (6, Proximity::Warm),
+            (20, Proximity::Warm),
+            (21, Proximity::Cold),
+            (100, Proximity::Cold),
+        ];
+        for (distance, expected) in cases {
+            assert_eq!(thresholds.proximity(distance, 100), expected, "{distance}");
+        }
+        // 5% of 60 is 3 and 20% is 12, exactly.
+        assert_eq!(thresholds.proximity(3, 60), Proximity::Burning);
+        assert_eq!(thresholds.proximity(4, 60), Proximity::Warm);
+        assert_eq!(thresholds.proximity(12, 60), Proximity::Warm);
+        assert_eq!(thresholds.proximity(13, 60), Proximity::Cold);
+        // The widest range there is still gets an answer.
+        assert_eq!(thresholds.proximity(u128::MAX, u128::MAX), Proximity::Cold);
+        assert_eq!(thresholds.proximity(1, u128::MAX), Proximity::Burning);
+    }
+
+    #[test]
+    fn each_hint_says_whether_the_guess_came_closer() {
+        let rules = Rules::unlimited(Bounds::new(0, 100).unwrap());
+        let game = Game::new(rules, 50);
+        let mut hot_cold = HotCold::new(Thresholds::DEFAULT);
+        let trends: Vec<(Proximity, Option<Trend>)> = [10, 30, 80, 20, 47, 53, 60]
+            .into_iter()
+            .map(|guess| {
+                let hint = hot_cold.hint(&game, guess);
+                (hint.proximity, hint.trend)
+            })
+            .collect();
+        assert_eq!(
+            trends,
+            [
+                (Proximity::Cold, None),
+                (Proximity::Warm, Some(Trend::Warmer)),
+                (Proximity::Cold, Some(Trend::Colder)),
+                (Proximity::Cold, Some(Trend::Same)),
+                (Proximity::Burning, Some(Trend::Warmer)),
+                (Proximity::Burning, Some(Trend::Same)),
+                (Proximity::Warm, Some(Trend::Colder)),
+            ]
+        );
+
+        // A new round starts over with nothing to compare against.
+        let mut hot_cold = HotCold::new(Thresholds::DEFAULT);
+        assert_eq!(hot_cold.hint(&game, 60).trend, None);
+    }
+}
@@ -0,0 +1,716 @@
+// A small HTTP/1.1 server that exposes games as JSON resources, for web pages and integration tests. Only the standard library is used: one request per connection, `Connection: close`, and just enough of the protocol for curl and fetch.
+//
+//   POST /games                 start a game; the optional body may give {"min":1,"max":100,"max_attempts":10,"seed":42}
+//                               (max_attempts null means unlimited); answers 201 with the game and a Location header
+//   POST /games/{id}/guesses    body {"value":50}; answers with the outcome:
+//                               {"guess":50,"ordering":"less","result":"too_small","attempts":1,"attempts_left":9,"status":"playing"}
+//                               where ordering is guess.cmp(&secret): less, greater or equal
+//   GET  /games/{id}            the game's state and every guess so far; the secret and seed appear once the game is over:
+//                               {"id":1,"min":1,"max":100,"max_attempts":10,"status":"won","attempts":7,"attempts_left":3,
+//                                "score":1000,"secret":70,"seed":42,"history":[{"guess":50,"ordering":"less","result":"too_small"}]}
+//
+// Errors are {"error":"<message>","code":"<code>"} with a matching status: 400 for a malformed request or a rejected guess (the code is the same as the JSON lines protocol's), 404 for an unknown or expired game, 405 for the wrong method, 409 for a guess at a finished game,
+// 413 for a body over MAX_BODY, 431 for a header line over MAX_LINE or more than MAX_HEADER_LINES of them,
+// and 503 when the server already holds max_games games or max_connections connections.
+// A game nobody has touched for the expiry time is forgotten; a background thread sweeps them out, so idle games don't pile up between requests.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::bounds::Bounds;
+use crate::difficulty::Rules;
+use crate::game::{Game, InvalidGuess, Outcome};
+use crate::json::{self, Value};
+use crate::protocol;
+use crate::rng::{self, GameRng};
+
+/// One past the TCP server's port, so both can run at once.
+pub const DEFAULT_PORT: u16 = 7879;
+
+// Requests are tiny; anything bigger is a mistake or an attack.
+const MAX_BODY: usize = 16 * 1024;
+const MAX_LINE: usize = 8 * 1024;
+const MAX_HEADER_LINES: usize = 100;
+
+/// How the HTTP server runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiOptions {
+    /// A game nobody has asked about for this long is removed.
+    pub expiry: Duration,
+    /// How long to wait for a client to finish sending its request.
+    pub read_timeout: Duration,
+    /// How many games may be live at once; creating one more is refused until some expire.
+    pub max_games: usize,
+    /// How many connections are served at once, each on its own thread; any more are turned away.
+    pub max_connections: usize,
+}
+
+impl Default for ApiOptions {
+    fn default() -> ApiOptions {
+        ApiOptions {
+            expiry: Duration::from_secs(600),
+            read_timeout: Duration::from_secs(10),
+            max_games: 10_000,
+            max_connections: 256,
+        }
+    }
+}
+
+struct Entry {
+    game: Game,
+    seed: u64,
+    touched: Instant,
+}
+
+struct State {
+    rules: Rules,
+    rng: GameRng,
+    options: ApiOptions,
+    games: BTreeMap<u64, Entry>,
+    next_id: u64,
+}
+
+impl State {
+    fn expire(&mut self) {
+        let expiry = self.options.expiry;
+        self.games
+            .retain(|_, entry| entry.touched.elapsed() < expiry);
+    }
+}
+
+/// A parsed request: the method, the path split into segments, and the body.
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: Vec<String>,
+    body: String,
+}
+
+/// A response waiting to be written.
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Value,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Response {
+        Response {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    fn error(status: u16, code: &str, message: &str) -> Response {
+        Response::json(
+            status,
+            Value::object().with("error", message).with("code", code),
+        )
+    }
+
+    fn write_to(&self, stream: &mut TcpStream) -> io::Result<()> {
+        let body = format!("{}\n", self.body);
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            reason(self.status),
+            body.len()
+        );
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(body.as_bytes())
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Serves the API on `listener` until the process is killed. Games use `rules` unless the request asks for another range.
+///
+/// Like the TCP server, every connection gets its own thread and the games sit behind one Mutex.
+/// One more thread wakes up now and then to forget expired games.
+pub fn serve(
+    listener: TcpListener,
+    rules: Rules,
+    rng: GameRng,
+    options: ApiOptions,
+) -> io::Result<()> {
+    let state = Arc::new(Mutex::new(State {
+        rules,
+        rng,
+        options,
+        games: BTreeMap::new(),
+        next_id: 1,
+    }));
+
+    // Checking a few times per expiry period keeps a game from outliving it by much; the bounds keep a tiny or huge expiry from making it spin or sleep forever.
+    let interval = (options.expiry / 2).clamp(Duration::from_millis(50), Duration::from_secs(60));
+    let sweeper = Arc::downgrade(&state);
+    thread::spawn(move || sweep(sweeper, interval));
+
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            // A client that gave up before we accepted it is no reason to stop serving everyone else.
+            Err(error) if error.kind() == ErrorKind::ConnectionAborted => continue,
+            Err(error) => return Err(error),
+        };
+        if connections.fetch_add(1, AtomicOrdering::SeqCst) >= options.max_connections {
+            connections.fetch_sub(1, AtomicOrdering::SeqCst);
+            let _ = turn_away(stream);
+            continue;
+        }
+        let slot = Slot(Arc::clone(&connections));
+        let state = Arc::clone(&state);
+        thread::spawn(move || {
+            let _slot = slot;
+            let _ = handle_connection(&state, stream);
+        });
+    }
+    Ok(())
+}
+
+// Holds one of the max_connections places for as long as a connection's thread runs, giving it back however the thread ends.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, AtomicOrdering::SeqCst);
+    }
+}
+
+// Answers a connection over the limit without waiting for its request. The answer is small enough to fit in the socket's buffer, and the timeout keeps a client that never reads from holding up the accept loop anyway.
+fn turn_away(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+    Response::error(
+        503,
+        "busy",
+        "the server is handling too many connections; try again shortly",
+    )
+    .write_to(&mut stream)?;
+    stream.shutdown(Shutdown::Write)?;
+    // Closing a socket with unread data sends a reset, which can make the client lose the answer; so whatever of the request has already arrived is read and dropped, without waiting for more.
+    stream.set_nonblocking(true)?;
+    let mut discard = [0; 4096];
+    while let Ok(1..) = stream.read(&mut discard) {}
+    Ok(())
+}
+
+// Forgets expired games every `interval`, until the server's state is gone.
+fn sweep(state: Weak<Mutex<State>>, interval: Duration) {
+    loop {
+        thread::sleep(interval);
+        match state.upgrade() {
+            Some(state) => state.lock().unwrap().expire(),
+            None => return,
+        }
+    }
+}
+
+fn handle_connection(state: &Mutex<State>, mut stream: TcpStream) -> io::Result<()> {
+    let read_timeout = state.lock().unwrap().options.read_timeout;
+    stream.set_read_timeout(Some(read_timeout))?;
+
+    let response = match read_request(&mut BufReader::new(stream.try_clone()?)) {
+        Ok(request) => {
+            let mut state = state.lock().unwrap();
+            state.expire();
+            route(&mut state, &request)
+        }
+        Err(response) => response,
+    };
+    response.write_to(&mut stream)
+}
+
+// Reads one line of the request head into `line`, returning false if it runs past MAX_LINE bytes.
+// read_line on its own would keep buffering for as long as a client kept sending without a newline.
+fn read_head_line<R: BufRead>(reader: &mut R, line: &mut String) -> io::Result<bool> {
+    line.clear();
+    reader.take(MAX_LINE as u64 + 1).read_line(line)?;
+    Ok(line.len() <= MAX_LINE || line.ends_with('\n'))
+}
+
+// Reads the request line, the headers and a body of Content-Length bytes. A request we can't read is answered with the error response.
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, Response> {
+    let bad = |message: &str| Response::error(400, "bad_request", message);
+    let too_large = || {
+        Response::error(
+            431,
+            "headers_too_large",
+            &format!("headers are limited to {MAX_HEADER_LINES} lines of {MAX_LINE} bytes"),
+        )
+    };
+
+    let mut line = String::new();
+    match read_head_line(reader, &mut line) {
+        Ok(true) => {}
+        Ok(false) => return Err(bad(&format!("the request line is over {MAX_LINE} bytes"))),
+        Err(_) => return Err(bad("couldn't read the request")),
+    }
+    let mut parts = line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => {
+            (method.to_string(), target.to_string())
+        }
+        _ => return Err(bad("expected a request line like `GET /games/1 HTTP/1.1`")),
+    };
+
+    let mut content_length = 0;
+    let mut lines = 0;
+    loop {
+        match read_head_line(reader, &mut line) {
+            Ok(true) => {}
+            Ok(false) => return Err(too_large()),
+            Err(_) => return Err(bad("couldn't read the headers")),
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        lines += 1;
+        if lines > MAX_HEADER_LINES {
+            return Err(too_large());
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| bad("Content-Length must be a number"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(Response::error(
+            413,
+            "too_large",
+            &format!("request bodies are limited to {MAX_BODY} bytes"),
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad("the body was shorter than its Content-Length"))?;
+    let body = String::from_utf8(body).map_err(|_| bad("the body must be UTF-8"))?;
+
+    // The query string, if any, is ignored.
+    let path = target.split('?').next().unwrap_or_default();
+    Ok(Request {
+        method,
+        path: path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect(),
+        body,
+    })
+}
+
+fn route(state: &mut State, request: &Request) -> Response {
+    let path: Vec<&str> = request.path.iter().map(String::as_str).collect();
+    let method = request.method.as_str();
+    match path.as_slice() {
+        ["games"] if method == "POST" => create_game(state, &request.body),
+        ["games"] => not_allowed("POST"),
+        ["games", id] => match (method, find(state, id)) {
+            ("GET", Ok(id)) => Response::json(200, describe(id, &state.games[&id])),
+            ("GET", Err(response)) => response,
+            _ => not_allowed("GET"),
+        },
+        ["games", id, "guesses"] => match (method, find(state, id)) {
+            ("POST", Ok(id)) => guess(state, id, &request.body),
+            ("POST", Err(response)) => response,
+            _ => not_allowed("POST"),
+        },
+        _ => Response::error(404, "not_found", "no such resource; try POST /games"),
+    }
+}
+
+fn not_allowed(allow: &str) -> Response {
+    let mut response = Response::error(
+        405,
+        "method_not_allowed",
+        &format!("this resource only accepts {allow}"),
+    );
+    response.headers.push(("Allow", allow.to_string()));
+    response
+}
+
+// Looks up a game by the id in the path, marking it as still in use.
+fn find(state: &mut State, id: &str) -> Result<u64, Response> {
+    let missing = || {
+        Response::error(
+            404,
+            "no_such_game",
+            &format!("there is no game {id}; it may have expired"),
+        )
+    };
+    let id: u64 = id.parse().map_err(|_| missing())?;
+    let entry = state.games.get_mut(&id).ok_or_else(missing)?;
+    entry.touched = Instant::now();
+    Ok(id)
+}
+
+// Reads an optional member holding a whole number of type T.
+fn member<T: std::str::FromStr>(body: &Value, key: &str) -> Result<Option<T>, Response> {
+    match body.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_number()
+            .and_then(|digits| digits.parse().ok())
+            .map(Some)
+            .ok_or_else(|| {
+                Response::error(
+                    400,
+                    "bad_value",
+                    &format!("\"{key}\" must be a whole number in range"),
+                )
+            }),
+    }
+}
+
+fn parse_body(body: &str) -> Result<Value, Response> {
+    if body.trim().is_empty() {
+        return Ok(Value::object());
+    }
+    match json::parse(body) {
+        Ok(value @ Value::Object(_)) => Ok(value),
+        Ok(_) => Err(Response::error(
+            400,
+            "bad_json",
+            "the body must be a JSON object",
+        )),
+        Err(error) => Err(Response::error(
+            400,
+            "bad_json",
+            &format!("not valid JSON: {error}"),
+        )),
+    }
+}
+
+fn create_game(state: &mut State, body: &str) -> Response {
+    let (rules, seed) = match game_options(state, body) {
+        Ok(options) => options,
+        Err(response) => return response,
+    };
+    if state.games.len() >= state.options.max_games {
+        return Response::error(
+            503,
+            "too_many_games",
+            "the server is holding as many games as it can; try again once some have expired",
+        );
+    }
+
+    // Every game gets its own seed, drawn from the server's generator when the client didn't choose one, so any game can be replayed.
+    let seed = seed.unwrap_or_else(|| state.rng.gen());
+    let game = Game::random(rules, &mut rng::seeded(seed));
+
+    let id = state.next_id;
+    state.next_id += 1;
+    let entry = Entry {
+        game,
+        seed,
+        touched: Instant::now(),
+    };
+    let mut response = Response::json(201, describe(id, &entry));
+    response.headers.push(("Location", format!("/games/{id}")));
+    state.games.insert(id, entry);
+    response
+}
+
+// The rules and seed a new game asks for; a range that isn't given comes from the server's rules.
+fn game_options(state: &State, body: &str) -> Result<(Rules, Option<u64>), Response> {
+    let body = parse_body(body)?;
+    let min = member(&body, "min")?.unwrap_or(state.rules.bounds.min());
+    let max = member(&body, "max")?.unwrap_or(state.rules.bounds.max());
+    let bounds =
+        Bounds::new(min, max).map_err(|error| Response::error(400, "bad_value", &error))?;
+    let max_attempts = match body.get("max_attempts") {
+        None => state.rules.max_attempts,
+        Some(_) => match member(&body, "max_attempts")? {
+            Some(0) => {
+                return Err(Response::error(
+                    400,
+                    "bad_value",
+                    "\"max_attempts\" must be at least 1, or null for unlimited",
+                ))
+            }
+            attempts => attempts,
+        },
+    };
+    let seed = member(&body, "seed")?;
+    Ok((
+        Rules {
+            bounds,
+            max_attempts,
+        },
+        seed,
+    ))
+}
+
+fn guess(state: &mut State, id: u64, body: &str) -> Response {
+    let body = match parse_body(body) {
+        Ok(body) => body,
+        Err(response) => return response,
+    };
+    // Like the JSON lines protocol, a string value is parsed the way typed input would be.
+    let input = match body.get("value") {
+        Some(Value::Number(digits)) => digits.clone(),
+        Some(Value::String(text)) => text.clone(),
+        _ => return Response::error(400, "bad_value", "a guess needs a number \"value\""),
+    };
+
+    let entry = state
+        .games
+        .get_mut(&id)
+        .expect("find checked the game exists");
+    let outcome = entry.game.submit(&input);
+    let guess = match (outcome, entry.game.parse(&input)) {
+        (Outcome::Invalid(InvalidGuess::Finished), _) => {
+            return Response::error(409, "finished", &InvalidGuess::Finished.to_string())
+        }
+        (Outcome::Invalid(reason), _) | (_, Err(reason)) => {
+            return Response::error(400, protocol::invalid_code(&reason), &reason.to_string())
+        }
+        (_, Ok(guess)) => guess,
+    };
+
+    let game = &entry.game;
+    let mut answer = answer_json(guess, outcome)
+        .with("attempts", game.attempts())
+        .with("attempts_left", game.attempts_left())
+        .with("status", protocol::status_name(game.status()));
+    if game.is_finished() {
+        answer = answer
+            .with("secret", game.secret())
+            .with("score", game.score());
+    }
+    Response::json(200, answer)
+}
+
+fn ordering_name(outcome: Outcome) -> Option<&'static str> {
+    outcome.ordering().map(|ordering| match ordering {
+        Ordering::Less => "less",
+        Ordering::Greater => "greater",
+        Ordering::Equal => "equal",
+    })
+}
+
+fn answer_json(guess: i128, outcome: Outcome) -> Value {
+    Value::object()
+        .with("guess", guess)
+        .with("ordering", ordering_name(outcome))
+        .with("result", protocol::outcome_name(outcome))
+}
+
+fn describe(id: u64, entry: &Entry) -> Value {
+    let game = &entry.game;
+    let mut state = Value::object()
+        .with("id", id)
+        .with("min", game.bounds().min())
+        .with("max", game.bounds().max())
+        .with("max_attempts", game.rules().max_attempts)
+        .with("status", protocol::status_name(game.status()))
+        .with("attempts", game.attempts())
+        .with("attempts_left", game.attempts_left())
+        .with("score", game.score());
+    // Revealing the secret early would spoil the game for whoever is playing it, and the seed gives the secret away too.
+    if game.is_finished() {
+        state = state.with("secret", game.secret()).with("seed", entry.seed);
+    }
+    let history: Vec<Value> = game
+        .history()
+        .into_iter()
+        .map(|(guess, outcome)| answer_json(guess, outcome))
+        .collect();
+    state.with("history", history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(expiry: Duration) -> State {
+        State {
+            rules: Rules::unlimited(Bounds::new(1, 100).unwrap()),
+            rng: rng::seeded(1),
+            options: ApiOptions {
+                expiry,
+                ..ApiOptions::default()
+            },
+            games: BTreeMap::new(),
+            next_id: 1,
+        }
+    }
+
+    fn request(method: &str, path: &[&str], body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.iter().map(|segment| segment.to_string()).collect(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_a_request_with_a_body() {
+        let text =
+            "POST /games/1/guesses HTTP/1.1\r\nHost: x\r\nContent-Length: 12\r\n\r\n{\"value\":50}";
+        let request = read_request(&mut text.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, ["games", "1", "guesses"]);
+        assert_eq!(request.body, "{\"value\":50}");
+    }
+
+    #[test]
+    fn refuses_an_endless_request_line() {
+        let text = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE));
+        let response = read_request(&mut text.as_bytes()).unwrap_err();
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn refuses_endless_headers() {
+        let long = format!("GET /games HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_LINE));
+        let response = read_request(&mut long.as_bytes()).unwrap_err();
+        assert_eq!(response.status, 431);
+
+        let many = format!(
+            "GET /games HTTP/1.1\r\n{}\r\n",
+            "X: y\r\n".repeat(MAX_HEADER_LINES + 1)
+        );
+        let response = read_request(&mut many.as_bytes()).unwrap_err();
+        assert_eq!(response.status, 431);
+
+        let enough = format!(
+            "GET /games HTTP/1.1\r\n{}\r\n",
+            "X: y\r\n".repeat(MAX_HEADER_LINES)
+        );
+        assert!(read_request(&mut enough.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn history_comes_from_the_game() {
+        let mut state = state(Duration::from_secs(60));
+        let created = route(&mut state, &request("POST", &["games"], "{\"seed\":7}"));
+        assert_eq!(created.status, 201);
+        let secret = state.games[&1].game.secret();
+        let guess = if secret == 1 { 2 } else { 1 };
+        let body = format!("{{\"value\":{guess}}}");
+        route(
+            &mut state,
+            &request("POST", &["games", "1", "guesses"], &body),
+        );
+
+        let described = route(&mut state, &request("GET", &["games", "1"], ""));
+        let history = described.body.get("history").unwrap().to_string();
+        assert!(history.contains(&format!("\"guess\":{guess}")), "{history}");
+    }
+
+    #[test]
+    fn the_sweeper_forgets_idle_games() {
+        let state = Arc::new(Mutex::new(state(Duration::from_millis(10))));
+        route(&mut state.lock().unwrap(), &request("POST", &["games"], ""));
+        assert_eq!(state.lock().unwrap().games.len(), 1);
+
+        let sweeper = Arc::downgrade(&state);
+        thread::spawn(move || sweep(sweeper, Duration::from_millis(20)));
+        thread::sleep(Duration::from_millis(200));
+        assert!(state.lock().unwrap().games.is_empty());
+    }
+
+    // Serves on a free localhost port from a background thread, returning the address to connect to.
+    fn start(options: ApiOptions) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let rules = Rules::unlimited(Bounds::new(1, 100).unwrap());
+        thread::spawn(move || serve(listener, rules, rng::seeded(1), options));
+        address
+    }
+
+    // Sends `text` as a whole request and returns the status line of the answer.
+    fn send(address: std::net::SocketAddr, text: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(text.as_bytes()).unwrap();
+        let mut answer = String::new();
+        stream.read_to_string(&mut answer).unwrap();
+        answer.lines().next().unwrap_or_default().to_string()
+    }
+
+    fn post_games(body: &str) -> String {
+        format!(
+            "POST /games HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn a_deeply_nested_body_is_a_bad_request() {
+        let address = start(ApiOptions::default());
+        let status = send(address, &post_games(&"[".repeat(16000)));
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+        // The server is still there for everyone else.
+        assert_eq!(send(address, &post_games("")), "HTTP/1.1 201 Created");
+    }
+
+    #[test]
+    fn games_are_refused_past_the_limit() {
+        let mut state = state(Duration::from_secs(60));
+        state.options.max_games = 2;
+        for _ in 0..2 {
+            assert_eq!(
+                route(&mut state, &request("POST", &["games"], "")).status,
+                201
+            );
+        }
+        let refused = route(&mut state, &request("POST", &["games"], ""));
+        assert_eq!(refused.status, 503);
+        assert_eq!(state.games.len(), 2);
+    }
+
+    #[test]
+    fn connections_are_turned_away_past_the_limit() {
+        let address = start(ApiOptions {
+            max_connections: 1,
+            ..ApiOptions::default()
+        });
+        // This one takes the only place and keeps it while it sends nothing.
+        let idle = TcpStream::connect(address).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(
+            send(address, &post_games("")),
+            "HTTP/1.1 503 Service Unavailable"
+        );
+
+        // Closing it gives the place back.
+        drop(idle);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(send(address, &post_games("")), "HTTP/1.1 201 Created");
+    }
+}
@@ -0,0 +1,472 @@
+// Just enough JSON for the line protocol: a value type, a writer and a parser. Numbers keep their original digits so an i128 guess survives the round trip, which an f64 couldn't promise.
+
+use std::fmt::{self, Write};
+
+/// A JSON value. Object members keep the order they were written in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// The number's text exactly as it appears in the JSON.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Starts an object; add members with `with`.
+    pub fn object() -> Value {
+        Value::Object(Vec::new())
+    }
+
+    /// Adds a member to an object, so messages can be built up in one expression.
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Value {
+        if let Value::Object(members) = &mut self {
+            members.push((key.to_string(), value.into()));
+        }
+        self
+    }
+
+    /// Looks up a member of an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The number's digits, for the caller to parse into whatever type it needs.
+    pub fn as_number(&self) -> Option<&str> {
+        match self {
+            Value::Number(digits) => Some(digits),
+            _ => None,
+        }
+    }
+}
+
+// From conversions let `with` take plain Rust values.
+impl From<&str> for Value {
+    fn from(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Value {
+        Value::String(text)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        Value::Bool(value)
+    }
+}
+
+macro_rules! number_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Value {
+                Value::Number(n.to_string())
+            }
+        })*
+    };
+}
+
+number_from!(u32, u64, i128, usize);
+
+impl From<f64> for Value {
+    // JSON has no NaN or infinity, so those become null.
+    fn from(n: f64) -> Value {
+        if n.is_finite() {
+            Value::Number(n.to_string())
+        } else {
+            Value::Null
+        }
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Value {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Value {
+        Value::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Writes the value as compact JSON on one line.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Number(digits) => f.write_str(digits),
+            Value::String(text) => write_string(f, text),
+            Value::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Value::Object(members) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Parses a complete JSON document, which may be surrounded by whitespace.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: text.char_indices().peekable(),
+        text,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        None => Ok(value),
+        Some((at, _)) => Err(format!("unexpected text after the value at byte {at}")),
+    }
+}
+
+/// How deeply arrays and objects may nest. The parser recurses once per level, so without a limit a line of a few thousand `[` would overflow the stack, which aborts the process rather than panicking.
+pub const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+    // How many arrays and objects enclose the value being read.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while let Some((_, ' ' | '\t' | '\n' | '\r')) = self.chars.peek() {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((at, c)) => Err(format!(
+                "expected '{expected}' but found '{c}' at byte {at}"
+            )),
+            None => Err(format!("expected '{expected}' but the text ended")),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, String> {
+        for expected in word.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Err("expected a value but the text ended".to_string()),
+            Some((_, 'n')) => self.literal("null", Value::Null),
+            Some((_, 't')) => self.literal("true", Value::Bool(true)),
+            Some((_, 'f')) => self.literal("false", Value::Bool(false)),
+            Some((_, '"')) => self.string().map(Value::String),
+            Some((_, '[' | '{')) => self.nested(),
+            Some((_, '-' | '0'..='9')) => self.number(),
+            Some((at, c)) => Err(format!("unexpected '{c}' at byte {at}")),
+        }
+    }
+
+    // Reads an array or object one level deeper, refusing to go past MAX_DEPTH.
+    fn nested(&mut self) -> Result<Value, String> {
+        if self.depth == MAX_DEPTH {
+            return Err("nested too deeply".to_string());
+        }
+        self.depth += 1;
+        let value = match self.chars.peek() {
+            Some((_, '[')) => self.array(),
+            _ => self.object(),
+        };
+        self.depth -= 1;
+        value
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.chars.peek().map_or(self.text.len(), |&(at, _)| at);
+        let mut end = start;
+        while let Some(&(at, c)) = self.chars.peek() {
+            if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                end = at + c.len_utf8();
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        let digits = &self.text[start..end];
+        // Rust's float parser judges most of the shape; JSON is stricter about a leading +, a bare . and leading zeros.
+        let unsigned = digits.strip_prefix('-').unwrap_or(digits);
+        let leading_zero = unsigned.len() > 1
+            && unsigned.starts_with('0')
+            && unsigned.as_bytes()[1].is_ascii_digit();
+        let valid = digits.parse::<f64>().is_ok()
+            && unsigned.starts_with(|c: char| c.is_ascii_digit())
+            && !digits.contains(".e")
+            && !digits.contains(".E")
+            && !digits.ends_with('.')
+            && !leading_zero;
+        if valid {
+            Ok(Value::Number(digits.to_string()))
+        } else {
+            Err(format!("'{digits}' is not a valid number"))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("a string was not closed".to_string()),
+                Some((_, '"')) => return Ok(text),
+                Some((at, '\\')) => match self.chars.next() {
+                    Some((_, '"')) => text.push('"'),
+                    Some((_, '\\')) => text.push('\\'),
+                    Some((_, '/')) => text.push('/'),
+                    Some((_, 'b')) => text.push('\u{8}'),
+                    Some((_, 'f')) => text.push('\u{c}'),
+                    Some((_, 'n')) => text.push('\n'),
+                    Some((_, 'r')) => text.push('\r'),
+                    Some((_, 't')) => text.push('\t'),
+                    Some((_, 'u')) => text.push(self.unicode_escape(at)?),
+                    _ => return Err(format!("bad escape sequence at byte {at}")),
+                },
+                Some((_, c)) => text.push(c),
+            }
+        }
+    }
+
+    // Reads the four hex digits after \u, combining a UTF-16 surrogate pair into one char when needed.
+    fn unicode_escape(&mut self, at: usize) -> Result<char, String> {
+        let first = self.hex4(at)?;
+        let code = if (0xD800..0xDC00).contains(&first) {
+            self.expect('\\')?;
+            self.expect('u')?;
+            let second = self.hex4(at)?;
+            if !(0xDC00..0xE000).contains(&second) {
+                return Err(format!("unpaired surrogate at byte {at}"));
+            }
+            0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00)
+        } else {
+            first
+        };
+        char::from_u32(code).ok_or_else(|| format!("invalid character escape at byte {at}"))
+    }
+
+    fn hex4(&mut self, at: usize) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .chars
+                .next()
+                .and_then(|(_, c)| c.to_digit(16))
+                .ok_or_else(|| format!("bad \\u escape at byte {at}"))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if let Some((_, ']')) = self.chars.peek() {
+            self.chars.next();
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => return Ok(Value::Array(values)),
+                Some((at, c)) => {
+                    return Err(format!("expected ',' or ']' but found '{c}' at byte {at}"))
+                }
+                None => return Err("an array was not closed".to_string()),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if let Some((_, '}')) = self.chars.peek() {
+            self.chars.next();
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value()?;
+            members.push((key, value));
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(Value::Object(members)),
+                Some((at, c)) => {
+                    return Err(format!("expected ',' or '}}' but found '{c}' at byte {at}"))
+                }
+                None => return Err("an object was not closed".to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_kind_of_value() {
+        let value =
+            parse(r#" {"a": [1, -2.5e3, true, false, null], "b": "x\ty", "c": {}} "#).unwrap();
+        assert_eq!(
+            value,
+            Value::object()
+                .with(
+                    "a",
+                    Value::Array(vec![
+                        Value::Number("1".to_string()),
+                        Value::Number("-2.5e3".to_string()),
+                        Value::Bool(true),
+                        Value::Bool(false),
+                        Value::Null,
+                    ])
+                )
+                .with("b", "x\ty")
+                .with("c", Value::object())
+        );
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error_not_a_crash() {
+        assert_eq!(
+            parse(&"[".repeat(16000)),
+            Err("nested too deeply".to_string())
+        );
+        assert_eq!(
+            parse(&"{\"a\":".repeat(16000)),
+            Err("nested too deeply".to_string())
+        );
+        let mixed = "[{\"a\":".repeat(MAX_DEPTH);
+        assert_eq!(parse(&mixed), Err("nested too deeply".to_string()));
+
+        let deepest = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(parse(&deepest).is_ok());
+        let too_deep = format!("[{deepest}]");
+        assert_eq!(parse(&too_deep), Err("nested too deeply".to_string()));
+    }
+
+    #[test]
+    fn numbers_keep_every_digit() {
+        let digits = i128::MIN.to_string();
+        let value = parse(&digits).unwrap();
+        assert_eq!(value.as_number(), Some(digits.as_str()));
+        assert_eq!(value.to_string(), digits);
+    }
+
+    #[test]
+    fn strings_round_trip_through_escapes() {
+        let text = "quote \" backslash \\ newline \n bell \u{7} snowman \u{2603}";
+        let written = Value::from(text).to_string();
+        assert_eq!(parse(&written).unwrap().as_str(), Some(text));
+        assert_eq!(
+            parse(r#""\u00e9\ud83d\ude00""#).unwrap().as_str(),
+            Some("é😀")
+        );
+    }
+
+    #[test]
+    fn rejects_what_json_does_not_allow() {
+        for text in [
+            "",
+            "01",
+            "+1",
+            "1.",
+            ".5",
+            "1.e5",
+            "nul",
+            "[1,]",
+            "[1 2]",
+            "{\"a\" 1}",
+            "{a:1}",
+            "\"open",
+            "\"\\x\"",
+            "\"\\ud800\"",
+            "1 2",
+        ] {
+            assert!(parse(text).is_err(), "{text:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn non_finite_floats_are_written_as_null() {
+        assert_eq!(Value::from(f64::NAN), Value::Null);
+        assert_eq!(Value::from(1.5).to_string(), "1.5");
+        assert_eq!(Value::from(None::<u32>).to_string(), "null");
+    }
+}
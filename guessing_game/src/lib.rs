@@ -0,0 +1,34 @@
+// The library half of the crate holds the rules of the game so that the binary, bots and tests can all drive the same engine without a terminal.
+// A package can contain both a src/main.rs and a src/lib.rs; both have the package name, and main.rs uses the library like any other crate.
+pub mod bounds;
+pub mod cli;
+pub mod codes;
+pub mod commands;
+pub mod difficulty;
+pub mod evil;
+pub mod floats;
+pub mod game;
+pub mod grid;
+pub mod hints;
+pub mod http;
+pub mod json;
+pub mod locale;
+pub mod protocol;
+pub mod records;
+pub mod reverse;
+pub mod rng;
+pub mod server;
+pub mod session;
+pub mod solver;
+pub mod strategy;
+pub mod tournament;
+pub mod transcript;
+pub mod tui;
+pub mod words;
+
+pub use bounds::Bounds;
+pub use cli::Config;
+pub use difficulty::{Difficulty, Rules};
+pub use game::{Game, InvalidGuess, Outcome, Status};
+pub use rng::GameRng;
+pub use session::{Session, Tally};
@@ -0,0 +1,905 @@
+// The game's messages in more than one language.
+//
+// Each language is a table from message ids to templates. A template names its values in braces, like {secret}, and the caller supplies them already formatted.
+// A message whose wording depends on a count has one id per plural category, like win.one and win.other; `Catalog::plural` picks the category the language's rules call for.
+// A message a language doesn't have is shown in English instead, and `guessing_game check-translations` lists every such gap.
+
+use std::str::FromStr;
+
+/// The languages the game can speak.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::French, Language::German];
+
+    /// The ISO 639-1 code, which is also what `--lang` takes.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+            Language::German => "de",
+        }
+    }
+
+    /// The language's name for itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// Reads a POSIX locale such as `fr_FR.UTF-8` or `de`, or `None` if it isn't one of ours.
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        // The language is whatever comes before the territory, codeset and modifier: fr_FR.UTF-8@euro is French.
+        let code = locale.split(['_', '.', '@']).next()?;
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Picks the language the environment asks for, looking at LC_ALL, then LC_MESSAGES, then LANG, as the C library does.
+    pub fn from_env<E: Fn(&str) -> Option<String>>(env: E) -> Language {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(env)
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Language::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// The plural category of `count`. All three languages have just two, but French counts zero as singular where English and German don't.
+    pub fn plural(self, count: u64) -> &'static str {
+        let one = match self {
+            Language::English | Language::German => count == 1,
+            Language::French => count <= 1,
+        };
+        if one {
+            "one"
+        } else {
+            "other"
+        }
+    }
+
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::French => FRENCH,
+            Language::German => GERMAN,
+        }
+    }
+
+    // What goes between groups of three digits, and before the fraction.
+    fn separators(self) -> (&'static str, char) {
+        match self {
+            Language::English => (",", '.'),
+            // French groups with a narrow no-break space, so a number never wraps across two lines.
+            Language::French => ("\u{202f}", ','),
+            Language::German => (".", ','),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Language, String> {
+        Language::from_locale(s.trim())
+            .ok_or_else(|| format!("unknown language '{s}' (expected en, fr or de)"))
+    }
+}
+
+/// Looks up and fills in messages in one language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Catalog {
+    language: Language,
+}
+
+impl Catalog {
+    pub fn new(language: Language) -> Catalog {
+        Catalog { language }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The message `id` with each `{name}` replaced by its value in `args`.
+    pub fn text(&self, id: &str, args: &[(&str, String)]) -> String {
+        let mut text = self.template(id).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), value);
+        }
+        text
+    }
+
+    /// Like `text`, for a message whose wording depends on `count`.
+    pub fn plural(&self, id: &str, count: u64, args: &[(&str, String)]) -> String {
+        self.text(&format!("{id}.{}", self.language.plural(count)), args)
+    }
+
+    /// A whole number with its digits grouped in threes, like 1,000,000 in English or 1.000.000 in German.
+    pub fn number<N: Into<i128>>(&self, number: N) -> String {
+        let number = number.into();
+        let (group, _) = self.language.separators();
+        let digits = number.unsigned_abs().to_string();
+        let mut text = String::new();
+        if number < 0 {
+            text.push('-');
+        }
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                text.push_str(group);
+            }
+            text.push(digit);
+        }
+        text
+    }
+
+    /// A number with `places` digits after the decimal separator, which is a comma in French and German.
+    pub fn decimal(&self, number: f64, places: usize) -> String {
+        let (_, point) = self.language.separators();
+        let text = format!("{number:.places$}");
+        match text.split_once('.') {
+            Some((whole, fraction)) => match whole.parse::<i128>() {
+                Ok(whole) => format!("{}{point}{fraction}", self.number(whole)),
+                Err(_) => text,
+            },
+            None => text,
+        }
+    }
+
+    fn template(&self, id: &str) -> &'static str {
+        translate(self.language.messages(), id)
+    }
+}
+
+// The template for `id` in `messages`, or in English when `messages` lacks it.
+fn translate(messages: &'static [(&'static str, &'static str)], id: &str) -> &'static str {
+    find(messages, id)
+        .or_else(|| find(ENGLISH, id))
+        // Every id the game uses is in the English table, so this only shows up if one is misspelled.
+        .unwrap_or("{missing message}")
+}
+
+fn lookup(language: Language, id: &str) -> Option<&'static str> {
+    find(language.messages(), id)
+}
+
+fn find(messages: &'static [(&'static str, &'static str)], id: &str) -> Option<&'static str> {
+    messages
+        .iter()
+        .find(|(key, _)| *key == id)
+        .map(|(_, template)| *template)
+}
+
+/// A way a language's table falls short of the English one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gap {
+    /// The message isn't translated, so it's shown in English.
+    Missing(&'static str),
+    /// The translation leaves out a value the English message shows, or names one that doesn't exist.
+    Placeholders(&'static str),
+}
+
+/// Everything `language` is missing compared with English, in the order of the English table.
+pub fn gaps(language: Language) -> Vec<Gap> {
+    let mut gaps = Vec::new();
+    for &(id, english) in ENGLISH {
+        match lookup(language, id) {
+            None => gaps.push(Gap::Missing(id)),
+            Some(translated) if placeholders(translated) != placeholders(english) => {
+                gaps.push(Gap::Placeholders(id))
+            }
+            Some(_) => {}
+        }
+    }
+    gaps
+}
+
+// The distinct {names} in a template, sorted.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names: Vec<&str> = template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+// English is the reference: every id the game uses is here, and the other tables are checked against it.
+#[rustfmt::skip]
+const ENGLISH: &[(&str, &str)] = &[
+    ("seed", "Seed: {seed} (replay this session with --seed {seed})"),
+    ("title", "Guess the number!"),
+    ("difficulty.easy", "easy"),
+    ("difficulty.normal", "normal"),
+    ("difficulty.hard", "hard"),
+    ("difficulty.custom", "custom"),
+    ("difficulty.limited.one", "Difficulty: {difficulty} ({max} attempt per round)"),
+    ("difficulty.limited.other", "Difficulty: {difficulty} ({max} attempts per round)"),
+    ("difficulty.unlimited", "Difficulty: {difficulty} (unlimited attempts)"),
+    ("debug", "[debug] secret: {secret}, attempts: {attempts}, remaining: {low}..={high}"),
+    ("ask_guess.limited.one", "Please input your guess (between {min} and {max}, {left} attempt left)."),
+    ("ask_guess.limited.other", "Please input your guess (between {min} and {max}, {left} attempts left)."),
+    ("ask_guess.unlimited", "Please input your guess (between {min} and {max})."),
+    ("play_again", "Play again? (y/n)"),
+    ("yes", "y"),
+    ("too_small", "Too small!"),
+    ("too_big", "Too big!"),
+    ("win.one", "You win! It took you {attempts} attempt; score: {score}"),
+    ("win.other", "You win! It took you {attempts} attempts; score: {score}"),
+    ("invalid.empty", "Please type a number."),
+    ("invalid.not_a_number", "That is not a number."),
+    ("invalid.negative", "Negative numbers are never the secret; guess between {min} and {max}."),
+    ("invalid.out_of_range", "Your guess must be between {min} and {max}."),
+    ("invalid.overflow", "That number is far too large; guess between {min} and {max}."),
+    ("invalid.finished", "This round is already over."),
+    ("invalid.repeated", "You already guessed {guess}; the secret is between {low} and {high}. That guess doesn't count."),
+    ("invalid.ruled_out", "Earlier answers already ruled out {guess}; the secret is between {low} and {high}. That guess doesn't count."),
+    ("warning.repeated", "You already guessed {guess}; the secret is between {low} and {high}."),
+    ("warning.ruled_out", "Earlier answers already ruled out {guess}; the secret is between {low} and {high}."),
+    ("interval", "The secret is between {low} and {high}."),
+    ("history.empty", "You haven't guessed yet this round."),
+    ("history.too_small", "{n}. {guess}: too small"),
+    ("history.too_big", "{n}. {guess}: too big"),
+    ("history.win", "{n}. {guess}: right"),
+    ("hinted", "Hint: the secret is between {low} and {high}. That cost {cost} points."),
+    ("undone.one", "Took back {guess}; {left} undo left this round."),
+    ("undone.other", "Took back {guess}; {left} undos left this round."),
+    ("help", "Commands (the start of a name is enough, like :his):\n  :history [n]  your guesses this round, or the last n\n  :range        the numbers still possible\n  :hint         halve the numbers still possible, for {cost} points of your score\n  :undo         take back your last guess (undos left this round: {left})\n  :giveup       end the round and see the secret\n  :new          start a new round\n  :help         this list\n  :quit         leave the game"),
+    ("command.unknown", "There's no command :{name}. Type :help for the list."),
+    ("command.ambiguous", ":{prefix} could be {matches}; type more of the name."),
+    ("command.unexpected_argument", ":{command} doesn't take an argument, but got '{argument}'."),
+    ("command.bad_count", ":{command} takes a number of guesses, like :{command} 3, but got '{argument}'."),
+    ("unavailable.hot_cold", "Hot/cold hints don't narrow down a range, so :range, :history, :hint and :undo don't work with them."),
+    ("unavailable.nothing_to_hint", "Only one number is left; there's nothing to narrow down."),
+    ("unavailable.nothing_to_undo", "There's no guess to take back yet."),
+    ("unavailable.no_undos_left", "You have no undos left this round."),
+    ("unavailable.not_in_round", "That command only works during a round."),
+    ("hint.burning", "Burning!"),
+    ("hint.warm", "Warm."),
+    ("hint.cold", "Cold."),
+    ("trend.warmer", "Warmer than your last guess."),
+    ("trend.colder", "Colder than your last guess."),
+    ("trend.same", "Just as far off as your last guess."),
+    ("round_over.input_ended", "No more input; the secret number was {secret}."),
+    ("round_over.gave_up", "The secret number was {secret}."),
+    ("round_over.lost", "You ran out of attempts! The secret number was {secret}."),
+    ("summary.played.one", "You played {played} round and won {won}."),
+    ("summary.played.other", "You played {played} rounds and won {won}."),
+    ("summary.average", "Average attempts per win: {average}"),
+    ("summary.score", "Total score: {total} (best round: {best})"),
+    ("summary.redundant.one", "{count} guess had already been ruled out by earlier answers."),
+    ("summary.redundant.other", "{count} guesses had already been ruled out by earlier answers."),
+    ("tui.title", "Guess the number! Round {round} ({difficulty}, {min} to {max})"),
+    ("tui.totals", "won {won} of {played}, score {score}"),
+    ("tui.secret", "The secret was {secret}"),
+    ("tui.possible", "Still possible: {low} to {high}"),
+    ("tui.attempts.limited", "Attempts: {used} used, {left} left"),
+    ("tui.attempts.unlimited", "Attempts: {used} used, no limit"),
+    ("tui.history", "History:"),
+    ("tui.correct", "Correct!"),
+    ("tui.no_guesses", "(no guesses yet)"),
+    ("tui.your_guess", "Your guess:"),
+    ("tui.keys", "Enter: guess   n: new round   g: give up   q: quit"),
+    ("tui.start", "Type a number and press Enter."),
+    ("tui.new_round", "Round {round}: type a number and press Enter."),
+    ("tui.won.one", "You win! It took you {attempts} attempt; score: {score}. Press n for a new round."),
+    ("tui.won.other", "You win! It took you {attempts} attempts; score: {score}. Press n for a new round."),
+    ("tui.lost", "You ran out of attempts! The secret number was {secret}. Press n for a new round."),
+    ("tui.gave_up", "The secret number was {secret}. Press n for a new round."),
+    ("tui.over", "This round is over. Press n for a new round or q to quit."),
+    ("step", "Guess {n}: {guess} -> {answer}"),
+    ("ask.limited.one", "Please input your guess ({left} attempt left)."),
+    ("ask.limited.other", "Please input your guess ({left} attempts left)."),
+    ("ask.unlimited", "Please input your guess."),
+    ("ran_out.one", "Ran out of attempts after {attempts} guess."),
+    ("ran_out.other", "Ran out of attempts after {attempts} guesses."),
+    ("autoplay.step", "Guess {n}: the secret is between {low} and {high}, so try {guess} -> {answer}"),
+    ("autoplay.win", "You win!"),
+    ("autoplay.found.one", "Found {secret} in {guesses} guess; binary search never needs more than {bound}."),
+    ("autoplay.found.other", "Found {secret} in {guesses} guesses; binary search never needs more than {bound}."),
+    ("autoplay.ran_out.one", "Ran out of attempts after {guesses} guess; this range can need up to {bound}."),
+    ("autoplay.ran_out.other", "Ran out of attempts after {guesses} guesses; this range can need up to {bound}."),
+    ("reverse.intro", "Think of a number between {min} and {max} and I'll guess it.\nAnswer each guess with higher (>), lower (<) or correct (=)."),
+    ("reverse.ask", "Is it {guess}?"),
+    ("reverse.input_ended", "No more input; I'll never know your number."),
+    ("reverse.bad_answer", "Please answer higher (>), lower (<) or correct (=)."),
+    ("reverse.higher", "your number is higher than {guess}"),
+    ("reverse.lower", "your number is lower than {guess}"),
+    ("reverse.equal", "your number is {guess}"),
+    ("reverse.contradicts_answer", "You can't mean that {answer}: in answer #{number} you said {earlier}."),
+    ("reverse.contradicts_range", "You can't mean that {answer}: you picked a number between {min} and {max}."),
+    ("reverse.found.one", "Your number is {number}! It took me {guesses} guess; binary search never needs more than {bound}."),
+    ("reverse.found.other", "Your number is {number}! It took me {guesses} guesses; binary search never needs more than {bound}."),
+    ("evil.intro", "Guess the number between {min} and {max}!\nI haven't picked it yet, and every answer I give will leave you as many numbers as I can."),
+    ("evil.autoplay.one", "Binary search needed {guesses} guess; no range this size can force more than {bound}."),
+    ("evil.autoplay.other", "Binary search needed {guesses} guesses; no range this size can force more than {bound}."),
+    ("evil.debug", "[debug] still possible: {low}..={high}, attempts: {attempts}"),
+    ("evil.check.one", "Check: {secret} agrees with the {answers} answer I gave."),
+    ("evil.check.other", "Check: {secret} agrees with all {answers} answers I gave."),
+    ("unavailable.evil", "Against the evil host you can guess, or type :range, :giveup or :quit."),
+    ("bulls.intro.repeats", "Crack the code! It has {length} symbols from {alphabet}, which may repeat.\nBulls are right symbols in the right place; cows are right symbols in the wrong place."),
+    ("bulls.intro.distinct", "Crack the code! It has {length} symbols from {alphabet}, all different.\nBulls are right symbols in the right place; cows are right symbols in the wrong place."),
+    ("bulls.cracked.one", "Cracked {code} in {attempts} guess."),
+    ("bulls.cracked.other", "Cracked {code} in {attempts} guesses."),
+    ("bulls.debug", "[debug] secret: {code}"),
+    ("bulls.ask.limited.one", "Please input your guess ({length} symbols, {left} attempt left)."),
+    ("bulls.ask.limited.other", "Please input your guess ({length} symbols, {left} attempts left)."),
+    ("bulls.ask.unlimited", "Please input your guess ({length} symbols)."),
+    ("bulls.input_ended", "No more input; the code was {code}."),
+    ("bulls.gave_up", "The code was {code}."),
+    ("bulls.lost", "You ran out of attempts! The code was {code}."),
+    ("bulls.no_range", "Codes have no range; bulls and cows are all there is to go on."),
+    ("bulls.won.one", "You cracked it! It took you {attempts} attempt."),
+    ("bulls.won.other", "You cracked it! It took you {attempts} attempts."),
+    ("bulls.score", "{bulls}, {cows}"),
+    ("bulls.bulls.one", "{count} bull"),
+    ("bulls.bulls.other", "{count} bulls"),
+    ("bulls.cows.one", "{count} cow"),
+    ("bulls.cows.other", "{count} cows"),
+    ("unavailable.bulls", "When cracking a code you can guess, or type :giveup or :quit."),
+    ("invalid_code.empty", "Please type a code."),
+    ("invalid_code.wrong_length", "The code has {expected} symbols, but you typed {got}."),
+    ("invalid_code.not_in_alphabet", "'{symbol}' isn't one of the code's symbols."),
+    ("invalid_code.repeated", "No symbol appears twice in this code, but you used '{symbol}' more than once."),
+    ("grid.intro", "Find the point! It's on a {width}x{height} grid: x from 1 to {width} (west to east), y from 1 to {height} (south to north).\nEach guess x,y is answered with the direction of the secret from it: N, NE, E, SE, S, SW, W, NW, or hit."),
+    ("direction.N", "N"),
+    ("direction.NE", "NE"),
+    ("direction.E", "E"),
+    ("direction.SE", "SE"),
+    ("direction.S", "S"),
+    ("direction.SW", "SW"),
+    ("direction.W", "W"),
+    ("direction.NW", "NW"),
+    ("direction.hit", "hit"),
+    ("grid.step", "Guess {n}: {point} is the middle of {region} -> {direction}"),
+    ("grid.region", "x {west} to {east}, y {south} to {north}"),
+    ("grid.found.one", "Found {point} in {attempts} guess; a {width}x{height} grid never takes more than {bound}."),
+    ("grid.found.other", "Found {point} in {attempts} guesses; a {width}x{height} grid never takes more than {bound}."),
+    ("grid.debug", "[debug] secret: {point}, remaining: {region}"),
+    ("grid.ask.limited.one", "Please input your guess x,y ({left} attempt left)."),
+    ("grid.ask.limited.other", "Please input your guess x,y ({left} attempts left)."),
+    ("grid.ask.unlimited", "Please input your guess x,y."),
+    ("grid.input_ended", "No more input; the secret point was {point}."),
+    ("grid.gave_up", "The secret point was {point}."),
+    ("grid.lost", "You ran out of attempts! The secret point was {point}."),
+    ("grid.range", "The secret is somewhere in {region}."),
+    ("grid.hit.one", "Hit! You found it in {attempts} attempt; binary search on both axes never needs more than {bound}."),
+    ("grid.hit.other", "Hit! You found it in {attempts} attempts; binary search on both axes never needs more than {bound}."),
+    ("grid.too_big", "(The grid is too big to draw; --map only draws sides up to {side}.)"),
+    ("unavailable.grid", "On the grid you can guess, or type :range, :giveup or :quit."),
+    ("invalid_point.empty", "Please type a point, like 3,7."),
+    ("invalid_point.malformed", "A point is two whole numbers, x and y, like 3,7."),
+    ("invalid_point.off_grid", "The point must be on the grid: x between 1 and {width}, y between 1 and {height}."),
+    ("words.intro", "Guess the word! It's one of the {count} words on the {list} list.\nEach guess is answered with whether the secret comes earlier or later in the alphabet."),
+    ("words.found.one", "Found '{word}' in {attempts} guess; a list of {count} words never takes more than {bound}."),
+    ("words.found.other", "Found '{word}' in {attempts} guesses; a list of {count} words never takes more than {bound}."),
+    ("words.ran_out.one", "Ran out of attempts after {attempts} guess; the word was '{word}'."),
+    ("words.ran_out.other", "Ran out of attempts after {attempts} guesses; the word was '{word}'."),
+    ("words.debug", "[debug] secret: {word}, remaining: {count} words from {first} to {last}"),
+    ("words.input_ended", "No more input; the word was '{word}'."),
+    ("words.gave_up", "The word was '{word}'."),
+    ("words.lost", "You ran out of attempts! The word was '{word}'."),
+    ("words.range", "The word is one of the {count} from '{first}' to '{last}'."),
+    ("words.won.one", "You win! It took you {attempts} attempt; binary search never needs more than {bound} for this list."),
+    ("words.won.other", "You win! It took you {attempts} attempts; binary search never needs more than {bound} for this list."),
+    ("words.later", "Later in the alphabet."),
+    ("words.earlier", "Earlier in the alphabet."),
+    ("words.equal", "That's the word!"),
+    ("unavailable.words", "With words you can guess, or type :range, :giveup or :quit."),
+    ("invalid_word.empty", "Please type a word."),
+    ("invalid_word.not_one_word", "The secret is a single word."),
+    ("invalid_word.unknown", "'{word}' isn't on the word list, so it can't be the secret."),
+    ("float.intro.absolute", "Guess the real number! It's between {min} and {max}, and any guess within {epsilon} of it wins.\nType decimals like 3.14 or scientific notation like 2.5e1."),
+    ("float.intro.relative", "Guess the real number! It's between {min} and {max}, and any guess within {percent}% of it wins.\nType decimals like 3.14 or scientific notation like 2.5e1."),
+    ("float.autoplay.one", "The secret was {secret}; bisection never needs more than {bound} guess for this range and tolerance."),
+    ("float.autoplay.other", "The secret was {secret}; bisection never needs more than {bound} guesses for this range and tolerance."),
+    ("float.debug", "[debug] secret: {secret}, remaining: {low} to {high}"),
+    ("float.won.one", "You win! The secret was {secret}. It took you {attempts} attempt; bisection never needs more than {bound}."),
+    ("float.won.other", "You win! The secret was {secret}. It took you {attempts} attempts; bisection never needs more than {bound}."),
+    ("float.close_enough", "Close enough!"),
+    ("unavailable.float", "With real numbers you can guess, or type :range, :giveup or :quit."),
+    ("invalid_float.decimal_comma", "Use a point for decimals: 3.5, not 3,5."),
+    ("invalid_float.not_finite", "NaN and infinity are never the secret; guess a real number."),
+    ("words.summary.one", "{list} ({words} words): {rounds} round, {wins} won ({rate}%)"),
+    ("words.summary.other", "{list} ({words} words): {rounds} rounds, {wins} won ({rate}%)"),
+    ("words.summary.average", ", {average} attempts per win on average, {fewest} at best"),
+];
+
+#[rustfmt::skip]
+const FRENCH: &[(&str, &str)] = &[
+    ("seed", "Graine : {seed} (rejouez cette partie avec --seed {seed})"),
+    ("title", "Devinez le nombre !"),
+    ("difficulty.easy", "facile"),
+    ("difficulty.normal", "normal"),
+    ("difficulty.hard", "difficile"),
+    ("difficulty.custom", "personnalisé"),
+    ("difficulty.limited.one", "Difficulté : {difficulty} ({max} essai par manche)"),
+    ("difficulty.limited.other", "Difficulté : {difficulty} ({max} essais par manche)"),
+    ("difficulty.unlimited", "Difficulté : {difficulty} (essais illimités)"),
+    ("debug", "[debug] secret : {secret}, essais : {attempts}, restant : {low}..={high}"),
+    ("ask_guess.limited.one", "Entrez votre proposition (entre {min} et {max}, {left} essai restant)."),
+    ("ask_guess.limited.other", "Entrez votre proposition (entre {min} et {max}, {left} essais restants)."),
+    ("ask_guess.unlimited", "Entrez votre proposition (entre {min} et {max})."),
+    ("play_again", "Rejouer ? (o/n)"),
+    ("yes", "o"),
+    ("too_small", "Trop petit !"),
+    ("too_big", "Trop grand !"),
+    ("win.one", "Gagné ! Il vous a fallu {attempts} essai ; score : {score}"),
+    ("win.other", "Gagné ! Il vous a fallu {attempts} essais ; score : {score}"),
+    ("invalid.empty", "Veuillez taper un nombre."),
+    ("invalid.not_a_number", "Ce n'est pas un nombre."),
+    ("invalid.negative", "Le secret n'est jamais négatif ; proposez un nombre entre {min} et {max}."),
+    ("invalid.out_of_range", "Votre proposition doit être entre {min} et {max}."),
+    ("invalid.overflow", "Ce nombre est bien trop grand ; proposez un nombre entre {min} et {max}."),
+    ("invalid.finished", "Cette manche est déjà terminée."),
+    ("invalid.repeated", "Vous avez déjà proposé {guess} ; le secret est entre {low} et {high}. Cette proposition ne compte pas."),
+    ("invalid.ruled_out", "Les réponses précédentes excluent déjà {guess} ; le secret est entre {low} et {high}. Cette proposition ne compte pas."),
+    ("warning.repeated", "Vous avez déjà proposé {guess} ; le secret est entre {low} et {high}."),
+    ("warning.ruled_out", "Les réponses précédentes excluent déjà {guess} ; le secret est entre {low} et {high}."),
+    ("interval", "Le secret est entre {low} et {high}."),
+    ("history.empty", "Vous n'avez encore rien proposé dans cette manche."),
+    ("history.too_small", "{n}. {guess} : trop petit"),
+    ("history.too_big", "{n}. {guess} : trop grand"),
+    ("history.win", "{n}. {guess} : trouvé"),
+    ("hinted", "Indice : le secret est entre {low} et {high}. Cela coûte {cost} points."),
+    ("undone.one", "{guess} est annulé ; il vous reste {left} annulation dans cette manche."),
+    ("undone.other", "{guess} est annulé ; il vous reste {left} annulations dans cette manche."),
+    ("help", "Commandes (le début d'un nom suffit, comme :his) :\n  :history [n]  vos propositions de la manche, ou les n dernières\n  :range        les nombres encore possibles\n  :hint         réduit de moitié les nombres possibles, pour {cost} points de votre score\n  :undo         annule votre dernière proposition (annulations restantes : {left})\n  :giveup       abandonne la manche et montre le secret\n  :new          commence une nouvelle manche\n  :help         cette liste\n  :quit         quitte le jeu"),
+    ("command.unknown", "La commande :{name} n'existe pas. Tapez :help pour la liste."),
+    ("command.ambiguous", ":{prefix} peut être {matches} ; tapez davantage du nom."),
+    ("command.unexpected_argument", ":{command} ne prend pas d'argument, mais a reçu « {argument} »."),
+    ("command.bad_count", ":{command} prend un nombre de propositions, comme :{command} 3, mais a reçu « {argument} »."),
+    ("unavailable.hot_cold", "Les indices chaud/froid ne délimitent pas d'intervalle, donc :range, :history, :hint et :undo ne fonctionnent pas avec eux."),
+    ("unavailable.nothing_to_hint", "Il ne reste qu'un nombre ; il n'y a plus rien à réduire."),
+    ("unavailable.nothing_to_undo", "Il n'y a encore aucune proposition à annuler."),
+    ("unavailable.no_undos_left", "Il ne vous reste plus d'annulation dans cette manche."),
+    ("unavailable.not_in_round", "Cette commande ne fonctionne que pendant une manche."),
+    ("hint.burning", "Brûlant !"),
+    ("hint.warm", "Tiède."),
+    ("hint.cold", "Froid."),
+    ("trend.warmer", "Plus chaud que votre dernière proposition."),
+    ("trend.colder", "Plus froid que votre dernière proposition."),
+    ("trend.same", "Aussi loin que votre dernière proposition."),
+    ("round_over.input_ended", "Plus d'entrée ; le nombre secret était {secret}."),
+    ("round_over.gave_up", "Le nombre secret était {secret}."),
+    ("round_over.lost", "Vous n'avez plus d'essais ! Le nombre secret était {secret}."),
+    ("summary.played.one", "Vous avez joué {played} manche et en avez gagné {won}."),
+    ("summary.played.other", "Vous avez joué {played} manches et en avez gagné {won}."),
+    ("summary.average", "Essais moyens par victoire : {average}"),
+    ("summary.score", "Score total : {total} (meilleure manche : {best})"),
+    ("summary.redundant.one", "{count} proposition était déjà exclue par les réponses précédentes."),
+    ("summary.redundant.other", "{count} propositions étaient déjà exclues par les réponses précédentes."),
+    ("tui.title", "Devinez le nombre ! Manche {round} ({difficulty}, de {min} à {max})"),
+    ("tui.totals", "{won} gagnées sur {played}, score {score}"),
+    ("tui.secret", "Le secret était {secret}"),
+    ("tui.possible", "Encore possible : de {low} à {high}"),
+    ("tui.attempts.limited", "Essais : {used} utilisés, {left} restants"),
+    ("tui.attempts.unlimited", "Essais : {used} utilisés, sans limite"),
+    ("tui.history", "Historique :"),
+    ("tui.correct", "Trouvé !"),
+    ("tui.no_guesses", "(aucune proposition)"),
+    ("tui.your_guess", "Votre proposition :"),
+    ("tui.keys", "Entrée : proposer   n : nouvelle manche   g : abandonner   q : quitter"),
+    ("tui.start", "Tapez un nombre et appuyez sur Entrée."),
+    ("tui.new_round", "Manche {round} : tapez un nombre et appuyez sur Entrée."),
+    ("tui.won.one", "Gagné ! Il vous a fallu {attempts} essai ; score : {score}. Appuyez sur n pour une nouvelle manche."),
+    ("tui.won.other", "Gagné ! Il vous a fallu {attempts} essais ; score : {score}. Appuyez sur n pour une nouvelle manche."),
+    ("tui.lost", "Vous n'avez plus d'essais ! Le nombre secret était {secret}. Appuyez sur n pour une nouvelle manche."),
+    ("tui.gave_up", "Le nombre secret était {secret}. Appuyez sur n pour une nouvelle manche."),
+    ("tui.over", "Cette manche est terminée. Appuyez sur n pour une nouvelle manche ou q pour quitter."),
+    ("step", "Proposition {n} : {guess} -> {answer}"),
+    ("ask.limited.one", "Entrez votre proposition ({left} essai restant)."),
+    ("ask.limited.other", "Entrez votre proposition ({left} essais restants)."),
+    ("ask.unlimited", "Entrez votre proposition."),
+    ("ran_out.one", "Plus d'essais après {attempts} proposition."),
+    ("ran_out.other", "Plus d'essais après {attempts} propositions."),
+    ("autoplay.step", "Proposition {n} : le secret est entre {low} et {high}, donc essayons {guess} -> {answer}"),
+    ("autoplay.win", "Gagné !"),
+    ("autoplay.found.one", "{secret} trouvé en {guesses} proposition ; la recherche dichotomique n'en demande jamais plus de {bound}."),
+    ("autoplay.found.other", "{secret} trouvé en {guesses} propositions ; la recherche dichotomique n'en demande jamais plus de {bound}."),
+    ("autoplay.ran_out.one", "Plus d'essais après {guesses} proposition ; cet intervalle peut en demander jusqu'à {bound}."),
+    ("autoplay.ran_out.other", "Plus d'essais après {guesses} propositions ; cet intervalle peut en demander jusqu'à {bound}."),
+    ("reverse.intro", "Pensez à un nombre entre {min} et {max} et je le devinerai.\nRépondez à chaque proposition par > si votre nombre est plus grand, < s'il est plus petit ou = si c'est lui."),
+    ("reverse.ask", "Est-ce {guess} ?"),
+    ("reverse.input_ended", "Plus d'entrée ; je ne connaîtrai jamais votre nombre."),
+    ("reverse.bad_answer", "Répondez par >, < ou =."),
+    ("reverse.higher", "votre nombre est plus grand que {guess}"),
+    ("reverse.lower", "votre nombre est plus petit que {guess}"),
+    ("reverse.equal", "votre nombre est {guess}"),
+    ("reverse.contradicts_answer", "Vous ne pouvez pas dire que {answer} : à la réponse n° {number}, vous avez dit que {earlier}."),
+    ("reverse.contradicts_range", "Vous ne pouvez pas dire que {answer} : vous avez choisi un nombre entre {min} et {max}."),
+    ("reverse.found.one", "Votre nombre est {number} ! Il m'a fallu {guesses} proposition ; la recherche dichotomique n'en demande jamais plus de {bound}."),
+    ("reverse.found.other", "Votre nombre est {number} ! Il m'a fallu {guesses} propositions ; la recherche dichotomique n'en demande jamais plus de {bound}."),
+    ("evil.intro", "Devinez le nombre entre {min} et {max} !\nJe ne l'ai pas encore choisi, et chacune de mes réponses vous laissera autant de nombres que possible."),
+    ("evil.autoplay.one", "La recherche dichotomique a eu besoin de {guesses} proposition ; aucun intervalle de cette taille ne peut en imposer plus de {bound}."),
+    ("evil.autoplay.other", "La recherche dichotomique a eu besoin de {guesses} propositions ; aucun intervalle de cette taille ne peut en imposer plus de {bound}."),
+    ("evil.debug", "[debug] encore possible : {low}..={high}, essais : {attempts}"),
+    ("evil.check.one", "Vérification : {secret} est cohérent avec {answers} réponse donnée."),
+    ("evil.check.other", "Vérification : {secret} est cohérent avec les {answers} réponses données."),
+    ("unavailable.evil", "Contre l'hôte malicieux, vous pouvez proposer un nombre, ou taper :range, :giveup ou :quit."),
+    ("bulls.intro.repeats", "Cassez le code ! Il a {length} symboles parmi {alphabet}, qui peuvent se répéter.\nLes taureaux sont les bons symboles à la bonne place ; les vaches, les bons symboles à la mauvaise place."),
+    ("bulls.intro.distinct", "Cassez le code ! Il a {length} symboles parmi {alphabet}, tous différents.\nLes taureaux sont les bons symboles à la bonne place ; les vaches, les bons symboles à la mauvaise place."),
+    ("bulls.cracked.one", "Code {code} cassé en {attempts} proposition."),
+    ("bulls.cracked.other", "Code {code} cassé en {attempts} propositions."),
+    ("bulls.debug", "[debug] secret : {code}"),
+    ("bulls.ask.limited.one", "Entrez votre proposition ({length} symboles, {left} essai restant)."),
+    ("bulls.ask.limited.other", "Entrez votre proposition ({length} symboles, {left} essais restants)."),
+    ("bulls.ask.unlimited", "Entrez votre proposition ({length} symboles)."),
+    ("bulls.input_ended", "Plus d'entrée ; le code était {code}."),
+    ("bulls.gave_up", "Le code était {code}."),
+    ("bulls.lost", "Vous n'avez plus d'essais ! Le code était {code}."),
+    ("bulls.no_range", "Un code n'a pas d'intervalle ; seuls les taureaux et les vaches vous guident."),
+    ("bulls.won.one", "Code cassé ! Il vous a fallu {attempts} essai."),
+    ("bulls.won.other", "Code cassé ! Il vous a fallu {attempts} essais."),
+    ("bulls.score", "{bulls}, {cows}"),
+    ("bulls.bulls.one", "{count} taureau"),
+    ("bulls.bulls.other", "{count} taureaux"),
+    ("bulls.cows.one", "{count} vache"),
+    ("bulls.cows.other", "{count} vaches"),
+    ("unavailable.bulls", "Pour casser un code, vous pouvez proposer, ou taper :giveup ou :quit."),
+    ("invalid_code.empty", "Veuillez taper un code."),
+    ("invalid_code.wrong_length", "Le code a {expected} symboles, mais vous en avez tapé {got}."),
+    ("invalid_code.not_in_alphabet", "« {symbol} » ne fait pas partie des symboles du code."),
+    ("invalid_code.repeated", "Aucun symbole n'apparaît deux fois dans ce code, mais vous avez utilisé « {symbol} » plusieurs fois."),
+    ("grid.intro", "Trouvez le point ! Il est sur une grille de {width}x{height} : x de 1 à {width} (d'ouest en est), y de 1 à {height} (du sud au nord).\nChaque proposition x,y reçoit la direction du secret vue depuis elle : N, NE, E, SE, S, SO, O, NO, ou touché."),
+    ("direction.N", "N"),
+    ("direction.NE", "NE"),
+    ("direction.E", "E"),
+    ("direction.SE", "SE"),
+    ("direction.S", "S"),
+    ("direction.SW", "SO"),
+    ("direction.W", "O"),
+    ("direction.NW", "NO"),
+    ("direction.hit", "touché"),
+    ("grid.step", "Proposition {n} : {point} est le milieu de {region} -> {direction}"),
+    ("grid.region", "x de {west} à {east}, y de {south} à {north}"),
+    ("grid.found.one", "{point} trouvé en {attempts} proposition ; une grille de {width}x{height} n'en demande jamais plus de {bound}."),
+    ("grid.found.other", "{point} trouvé en {attempts} propositions ; une grille de {width}x{height} n'en demande jamais plus de {bound}."),
+    ("grid.debug", "[debug] secret : {point}, restant : {region}"),
+    ("grid.ask.limited.one", "Entrez votre proposition x,y ({left} essai restant)."),
+    ("grid.ask.limited.other", "Entrez votre proposition x,y ({left} essais restants)."),
+    ("grid.ask.unlimited", "Entrez votre proposition x,y."),
+    ("grid.input_ended", "Plus d'entrée ; le point secret était {point}."),
+    ("grid.gave_up", "Le point secret était {point}."),
+    ("grid.lost", "Vous n'avez plus d'essais ! Le point secret était {point}."),
+    ("grid.range", "Le secret est quelque part dans {region}."),
+    ("grid.hit.one", "Touché ! Vous l'avez trouvé en {attempts} essai ; la recherche dichotomique sur les deux axes n'en demande jamais plus de {bound}."),
+    ("grid.hit.other", "Touché ! Vous l'avez trouvé en {attempts} essais ; la recherche dichotomique sur les deux axes n'en demande jamais plus de {bound}."),
+    ("grid.too_big", "(La grille est trop grande pour être dessinée ; --map ne dessine que des côtés jusqu'à {side}.)"),
+    ("unavailable.grid", "Sur la grille, vous pouvez proposer, ou taper :range, :giveup ou :quit."),
+    ("invalid_point.empty", "Veuillez taper un point, comme 3,7."),
+    ("invalid_point.malformed", "Un point, ce sont deux nombres entiers, x et y, comme 3,7."),
+    ("invalid_point.off_grid", "Le point doit être sur la grille : x entre 1 et {width}, y entre 1 et {height}."),
+    ("words.intro", "Devinez le mot ! C'est l'un des {count} mots de la liste {list}.\nChaque proposition reçoit pour réponse si le secret vient avant ou après dans l'alphabet."),
+    ("words.found.one", "« {word} » trouvé en {attempts} proposition ; une liste de {count} mots n'en demande jamais plus de {bound}."),
+    ("words.found.other", "« {word} » trouvé en {attempts} propositions ; une liste de {count} mots n'en demande jamais plus de {bound}."),
+    ("words.ran_out.one", "Plus d'essais après {attempts} proposition ; le mot était « {word} »."),
+    ("words.ran_out.other", "Plus d'essais après {attempts} propositions ; le mot était « {word} »."),
+    ("words.debug", "[debug] secret : {word}, restant : {count} mots de {first} à {last}"),
+    ("words.input_ended", "Plus d'entrée ; le mot était « {word} »."),
+    ("words.gave_up", "Le mot était « {word} »."),
+    ("words.lost", "Vous n'avez plus d'essais ! Le mot était « {word} »."),
+    ("words.range", "Le mot est l'un des {count} de « {first} » à « {last} »."),
+    ("words.won.one", "Gagné ! Il vous a fallu {attempts} essai ; la recherche dichotomique n'en demande jamais plus de {bound} pour cette liste."),
+    ("words.won.other", "Gagné ! Il vous a fallu {attempts} essais ; la recherche dichotomique n'en demande jamais plus de {bound} pour cette liste."),
+    ("words.later", "Plus loin dans l'alphabet."),
+    ("words.earlier", "Plus tôt dans l'alphabet."),
+    ("words.equal", "C'est le mot !"),
+    ("unavailable.words", "Avec les mots, vous pouvez proposer, ou taper :range, :giveup ou :quit."),
+    ("invalid_word.empty", "Veuillez taper un mot."),
+    ("invalid_word.not_one_word", "Le secret est un seul mot."),
+    ("invalid_word.unknown", "« {word} » n'est pas dans la liste, donc ce ne peut pas être le secret."),
+    ("float.intro.absolute", "Devinez le nombre réel ! Il est entre {min} et {max}, et toute proposition à moins de {epsilon} de lui gagne.\nTapez les décimales avec un point, comme 3.14, ou en notation scientifique, comme 2.5e1."),
+    ("float.intro.relative", "Devinez le nombre réel ! Il est entre {min} et {max}, et toute proposition à moins de {percent} % de lui gagne.\nTapez les décimales avec un point, comme 3.14, ou en notation scientifique, comme 2.5e1."),
+    ("float.autoplay.one", "Le secret était {secret} ; la bissection n'a jamais besoin de plus de {bound} proposition pour cet intervalle et cette tolérance."),
+    ("float.autoplay.other", "Le secret était {secret} ; la bissection n'a jamais besoin de plus de {bound} propositions pour cet intervalle et cette tolérance."),
+    ("float.debug", "[debug] secret : {secret}, restant : de {low} à {high}"),
+    ("float.won.one", "Gagné ! Le secret était {secret}. Il vous a fallu {attempts} essai ; la bissection n'en demande jamais plus de {bound}."),
+    ("float.won.other", "Gagné ! Le secret était {secret}. Il vous a fallu {attempts} essais ; la bissection n'en demande jamais plus de {bound}."),
+    ("float.close_enough", "Assez proche !"),
+    ("unavailable.float", "Avec les nombres réels, vous pouvez proposer, ou taper :range, :giveup ou :quit."),
+    ("invalid_float.decimal_comma", "Utilisez un point pour les décimales : 3.5, pas 3,5."),
+    ("invalid_float.not_finite", "NaN et l'infini ne sont jamais le secret ; proposez un nombre réel."),
+    ("words.summary.one", "{list} ({words} mots) : {rounds} manche, gagnées : {wins} ({rate} %)"),
+    ("words.summary.other", "{list} ({words} mots) : {rounds} manches, gagnées : {wins} ({rate} %)"),
+    ("words.summary.average", ", {average} essais par victoire en moyenne, {fewest} au mieux"),
+];
+
+#[rustfmt::skip]
+const GERMAN: &[(&str, &str)] = &[
+    ("seed", "Startwert: {seed} (diese Sitzung mit --seed {seed} wiederholen)"),
+    ("title", "Errate die Zahl!"),
+    ("difficulty.easy", "leicht"),
+    ("difficulty.normal", "normal"),
+    ("difficulty.hard", "schwer"),
+    ("difficulty.custom", "benutzerdefiniert"),
+    ("difficulty.limited.one", "Schwierigkeit: {difficulty} ({max} Versuch pro Runde)"),
+    ("difficulty.limited.other", "Schwierigkeit: {difficulty} ({max} Versuche pro Runde)"),
+    ("difficulty.unlimited", "Schwierigkeit: {difficulty} (unbegrenzte Versuche)"),
+    ("debug", "[debug] Geheimzahl: {secret}, Versuche: {attempts}, übrig: {low}..={high}"),
+    ("ask_guess.limited.one", "Bitte gib deinen Tipp ein (zwischen {min} und {max}, noch {left} Versuch)."),
+    ("ask_guess.limited.other", "Bitte gib deinen Tipp ein (zwischen {min} und {max}, noch {left} Versuche)."),
+    ("ask_guess.unlimited", "Bitte gib deinen Tipp ein (zwischen {min} und {max})."),
+    ("play_again", "Nochmal spielen? (j/n)"),
+    ("yes", "j"),
+    ("too_small", "Zu klein!"),
+    ("too_big", "Zu groß!"),
+    ("win.one", "Gewonnen! Du hast {attempts} Versuch gebraucht; Punkte: {score}"),
+    ("win.other", "Gewonnen! Du hast {attempts} Versuche gebraucht; Punkte: {score}"),
+    ("invalid.empty", "Bitte gib eine Zahl ein."),
+    ("invalid.not_a_number", "Das ist keine Zahl."),
+    ("invalid.negative", "Die Geheimzahl ist nie negativ; rate zwischen {min} und {max}."),
+    ("invalid.out_of_range", "Dein Tipp muss zwischen {min} und {max} liegen."),
+    ("invalid.overflow", "Diese Zahl ist viel zu groß; rate zwischen {min} und {max}."),
+    ("invalid.finished", "Diese Runde ist schon vorbei."),
+    ("invalid.repeated", "Du hast {guess} schon geraten; die Geheimzahl liegt zwischen {low} und {high}. Dieser Tipp zählt nicht."),
+    ("invalid.ruled_out", "Frühere Antworten schließen {guess} schon aus; die Geheimzahl liegt zwischen {low} und {high}. Dieser Tipp zählt nicht."),
+    ("warning.repeated", "Du hast {guess} schon geraten; die Geheimzahl liegt zwischen {low} und {high}."),
+    ("warning.ruled_out", "Frühere Antworten schließen {guess} schon aus; die Geheimzahl liegt zwischen {low} und {high}."),
+    ("interval", "Die Geheimzahl liegt zwischen {low} und {high}."),
+    ("history.empty", "Du hast in dieser Runde noch nicht getippt."),
+    ("history.too_small", "{n}. {guess}: zu klein"),
+    ("history.too_big", "{n}. {guess}: zu groß"),
+    ("history.win", "{n}. {guess}: richtig"),
+    ("hinted", "Hinweis: Die Geheimzahl liegt zwischen {low} und {high}. Das kostet {cost} Punkte."),
+    ("undone.one", "{guess} zurückgenommen; in dieser Runde bleibt dir {left} Rücknahme."),
+    ("undone.other", "{guess} zurückgenommen; in dieser Runde bleiben dir {left} Rücknahmen."),
+    ("help", "Befehle (der Anfang eines Namens genügt, etwa :his):\n  :history [n]  deine Tipps dieser Runde, oder die letzten n\n  :range        die noch möglichen Zahlen\n  :hint         halbiert die möglichen Zahlen, für {cost} Punkte deiner Wertung\n  :undo         nimmt deinen letzten Tipp zurück (übrige Rücknahmen: {left})\n  :giveup       beendet die Runde und zeigt die Geheimzahl\n  :new          startet eine neue Runde\n  :help         diese Liste\n  :quit         beendet das Spiel"),
+    ("command.unknown", "Den Befehl :{name} gibt es nicht. Tippe :help für die Liste."),
+    ("command.ambiguous", ":{prefix} könnte {matches} sein; tippe mehr vom Namen."),
+    ("command.unexpected_argument", ":{command} nimmt kein Argument, bekam aber „{argument}“."),
+    ("command.bad_count", ":{command} nimmt eine Anzahl Tipps, etwa :{command} 3, bekam aber „{argument}“."),
+    ("unavailable.hot_cold", "Heiß/kalt-Hinweise grenzen keinen Bereich ein, deshalb funktionieren :range, :history, :hint und :undo damit nicht."),
+    ("unavailable.nothing_to_hint", "Es ist nur noch eine Zahl übrig; da gibt es nichts einzugrenzen."),
+    ("unavailable.nothing_to_undo", "Es gibt noch keinen Tipp zum Zurücknehmen."),
+    ("unavailable.no_undos_left", "In dieser Runde hast du keine Rücknahmen mehr."),
+    ("unavailable.not_in_round", "Dieser Befehl funktioniert nur während einer Runde."),
+    ("hint.burning", "Glühend heiß!"),
+    ("hint.warm", "Warm."),
+    ("hint.cold", "Kalt."),
+    ("trend.warmer", "Wärmer als dein letzter Tipp."),
+    ("trend.colder", "Kälter als dein letzter Tipp."),
+    ("trend.same", "Genauso weit weg wie dein letzter Tipp."),
+    ("round_over.input_ended", "Keine Eingabe mehr; die Geheimzahl war {secret}."),
+    ("round_over.gave_up", "Die Geheimzahl war {secret}."),
+    ("round_over.lost", "Keine Versuche mehr! Die Geheimzahl war {secret}."),
+    ("summary.played.one", "Du hast {played} Runde gespielt und {won} gewonnen."),
+    ("summary.played.other", "Du hast {played} Runden gespielt und {won} gewonnen."),
+    ("summary.average", "Durchschnittliche Versuche pro Sieg: {average}"),
+    ("summary.score", "Gesamtpunktzahl: {total} (beste Runde: {best})"),
+    ("summary.redundant.one", "{count} Tipp war durch frühere Antworten schon ausgeschlossen."),
+    ("summary.redundant.other", "{count} Tipps waren durch frühere Antworten schon ausgeschlossen."),
+    ("tui.title", "Errate die Zahl! Runde {round} ({difficulty}, {min} bis {max})"),
+    ("tui.totals", "{won} von {played} gewonnen, Punkte {score}"),
+    ("tui.secret", "Die Geheimzahl war {secret}"),
+    ("tui.possible", "Noch möglich: {low} bis {high}"),
+    ("tui.attempts.limited", "Versuche: {used} verbraucht, {left} übrig"),
+    ("tui.attempts.unlimited", "Versuche: {used} verbraucht, unbegrenzt"),
+    ("tui.history", "Verlauf:"),
+    ("tui.correct", "Richtig!"),
+    ("tui.no_guesses", "(noch keine Tipps)"),
+    ("tui.your_guess", "Dein Tipp:"),
+    ("tui.keys", "Enter: tippen   n: neue Runde   g: aufgeben   q: beenden"),
+    ("tui.start", "Tippe eine Zahl und drücke Enter."),
+    ("tui.new_round", "Runde {round}: Tippe eine Zahl und drücke Enter."),
+    ("tui.won.one", "Gewonnen! Du hast {attempts} Versuch gebraucht; Punkte: {score}. Drücke n für eine neue Runde."),
+    ("tui.won.other", "Gewonnen! Du hast {attempts} Versuche gebraucht; Punkte: {score}. Drücke n für eine neue Runde."),
+    ("tui.lost", "Keine Versuche mehr! Die Geheimzahl war {secret}. Drücke n für eine neue Runde."),
+    ("tui.gave_up", "Die Geheimzahl war {secret}. Drücke n für eine neue Runde."),
+    ("tui.over", "Diese Runde ist vorbei. Drücke n für eine neue Runde oder q zum Beenden."),
+    ("step", "Tipp {n}: {guess} -> {answer}"),
+    ("ask.limited.one", "Bitte gib deinen Tipp ein (noch {left} Versuch)."),
+    ("ask.limited.other", "Bitte gib deinen Tipp ein (noch {left} Versuche)."),
+    ("ask.unlimited", "Bitte gib deinen Tipp ein."),
+    ("ran_out.one", "Keine Versuche mehr nach {attempts} Tipp."),
+    ("ran_out.other", "Keine Versuche mehr nach {attempts} Tipps."),
+    ("autoplay.step", "Tipp {n}: Die Geheimzahl liegt zwischen {low} und {high}, also {guess} -> {answer}"),
+    ("autoplay.win", "Gewonnen!"),
+    ("autoplay.found.one", "{secret} in {guesses} Tipp gefunden; binäre Suche braucht nie mehr als {bound}."),
+    ("autoplay.found.other", "{secret} in {guesses} Tipps gefunden; binäre Suche braucht nie mehr als {bound}."),
+    ("autoplay.ran_out.one", "Keine Versuche mehr nach {guesses} Tipp; dieser Bereich kann bis zu {bound} brauchen."),
+    ("autoplay.ran_out.other", "Keine Versuche mehr nach {guesses} Tipps; dieser Bereich kann bis zu {bound} brauchen."),
+    ("reverse.intro", "Denk dir eine Zahl zwischen {min} und {max} aus, und ich errate sie.\nBeantworte jeden Tipp mit > wenn deine Zahl größer ist, < wenn sie kleiner ist, oder = wenn er stimmt."),
+    ("reverse.ask", "Ist es {guess}?"),
+    ("reverse.input_ended", "Keine Eingabe mehr; ich werde deine Zahl nie erfahren."),
+    ("reverse.bad_answer", "Bitte antworte mit >, < oder =."),
+    ("reverse.higher", "deine Zahl größer als {guess} ist"),
+    ("reverse.lower", "deine Zahl kleiner als {guess} ist"),
+    ("reverse.equal", "deine Zahl {guess} ist"),
+    ("reverse.contradicts_answer", "Du kannst nicht meinen, dass {answer}: In Antwort Nr. {number} hast du gesagt, dass {earlier}."),
+    ("reverse.contradicts_range", "Du kannst nicht meinen, dass {answer}: Du hast eine Zahl zwischen {min} und {max} gewählt."),
+    ("reverse.found.one", "Deine Zahl ist {number}! Ich habe {guesses} Tipp gebraucht; binäre Suche braucht nie mehr als {bound}."),
+    ("reverse.found.other", "Deine Zahl ist {number}! Ich habe {guesses} Tipps gebraucht; binäre Suche braucht nie mehr als {bound}."),
+    ("evil.intro", "Errate die Zahl zwischen {min} und {max}!\nIch habe sie noch nicht gewählt, und jede meiner Antworten lässt dir so viele Zahlen wie möglich."),
+    ("evil.autoplay.one", "Binäre Suche hat {guesses} Tipp gebraucht; kein Bereich dieser Größe kann mehr als {bound} erzwingen."),
+    ("evil.autoplay.other", "Binäre Suche hat {guesses} Tipps gebraucht; kein Bereich dieser Größe kann mehr als {bound} erzwingen."),
+    ("evil.debug", "[debug] noch möglich: {low}..={high}, Versuche: {attempts}"),
+    ("evil.check.one", "Prüfung: {secret} passt zu {answers} Antwort, die ich gegeben habe."),
+    ("evil.check.other", "Prüfung: {secret} passt zu allen {answers} Antworten, die ich gegeben habe."),
+    ("unavailable.evil", "Gegen den fiesen Gastgeber kannst du tippen oder :range, :giveup oder :quit eingeben."),
+    ("bulls.intro.repeats", "Knack den Code! Er hat {length} Zeichen aus {alphabet}, die sich wiederholen können.\nBullen sind richtige Zeichen an der richtigen Stelle; Kühe sind richtige Zeichen an der falschen Stelle."),
+    ("bulls.intro.distinct", "Knack den Code! Er hat {length} Zeichen aus {alphabet}, alle verschieden.\nBullen sind richtige Zeichen an der richtigen Stelle; Kühe sind richtige Zeichen an der falschen Stelle."),
+    ("bulls.cracked.one", "{code} in {attempts} Tipp geknackt."),
+    ("bulls.cracked.other", "{code} in {attempts} Tipps geknackt."),
+    ("bulls.debug", "[debug] Geheimcode: {code}"),
+    ("bulls.ask.limited.one", "Bitte gib deinen Tipp ein ({length} Zeichen, noch {left} Versuch)."),
+    ("bulls.ask.limited.other", "Bitte gib deinen Tipp ein ({length} Zeichen, noch {left} Versuche)."),
+    ("bulls.ask.unlimited", "Bitte gib deinen Tipp ein ({length} Zeichen)."),
+    ("bulls.input_ended", "Keine Eingabe mehr; der Code war {code}."),
+    ("bulls.gave_up", "Der Code war {code}."),
+    ("bulls.lost", "Keine Versuche mehr! Der Code war {code}."),
+    ("bulls.no_range", "Codes haben keinen Bereich; nur Bullen und Kühe helfen weiter."),
+    ("bulls.won.one", "Geknackt! Du hast {attempts} Versuch gebraucht."),
+    ("bulls.won.other", "Geknackt! Du hast {attempts} Versuche gebraucht."),
+    ("bulls.score", "{bulls}, {cows}"),
+    ("bulls.bulls.one", "{count} Bulle"),
+    ("bulls.bulls.other", "{count} Bullen"),
+    ("bulls.cows.one", "{count} Kuh"),
+    ("bulls.cows.other", "{count} Kühe"),
+    ("unavailable.bulls", "Beim Codeknacken kannst du tippen oder :giveup oder :quit eingeben."),
+    ("invalid_code.empty", "Bitte gib einen Code ein."),
+    ("invalid_code.wrong_length", "Der Code hat {expected} Zeichen, du hast aber {got} eingegeben."),
+    ("invalid_code.not_in_alphabet", "„{symbol}“ gehört nicht zu den Zeichen des Codes."),
+    ("invalid_code.repeated", "Kein Zeichen kommt in diesem Code zweimal vor, du hast „{symbol}“ aber mehrmals benutzt."),
+    ("grid.intro", "Finde den Punkt! Er liegt auf einem {width}x{height}-Gitter: x von 1 bis {width} (West nach Ost), y von 1 bis {height} (Süd nach Nord).\nJeder Tipp x,y wird mit der Richtung beantwortet, in der der Punkt von dort aus liegt: N, NO, O, SO, S, SW, W, NW, oder Treffer."),
+    ("direction.N", "N"),
+    ("direction.NE", "NO"),
+    ("direction.E", "O"),
+    ("direction.SE", "SO"),
+    ("direction.S", "S"),
+    ("direction.SW", "SW"),
+    ("direction.W", "W"),
+    ("direction.NW", "NW"),
+    ("direction.hit", "Treffer"),
+    ("grid.step", "Tipp {n}: {point} ist die Mitte von {region} -> {direction}"),
+    ("grid.region", "x {west} bis {east}, y {south} bis {north}"),
+    ("grid.found.one", "{point} in {attempts} Tipp gefunden; ein {width}x{height}-Gitter braucht nie mehr als {bound}."),
+    ("grid.found.other", "{point} in {attempts} Tipps gefunden; ein {width}x{height}-Gitter braucht nie mehr als {bound}."),
+    ("grid.debug", "[debug] Geheimpunkt: {point}, übrig: {region}"),
+    ("grid.ask.limited.one", "Bitte gib deinen Tipp x,y ein (noch {left} Versuch)."),
+    ("grid.ask.limited.other", "Bitte gib deinen Tipp x,y ein (noch {left} Versuche)."),
+    ("grid.ask.unlimited", "Bitte gib deinen Tipp x,y ein."),
+    ("grid.input_ended", "Keine Eingabe mehr; der geheime Punkt war {point}."),
+    ("grid.gave_up", "Der geheime Punkt war {point}."),
+    ("grid.lost", "Keine Versuche mehr! Der geheime Punkt war {point}."),
+    ("grid.range", "Der Punkt liegt irgendwo in {region}."),
+    ("grid.hit.one", "Treffer! Du hast ihn in {attempts} Versuch gefunden; binäre Suche auf beiden Achsen braucht nie mehr als {bound}."),
+    ("grid.hit.other", "Treffer! Du hast ihn in {attempts} Versuchen gefunden; binäre Suche auf beiden Achsen braucht nie mehr als {bound}."),
+    ("grid.too_big", "(Das Gitter ist zu groß zum Zeichnen; --map zeichnet nur Seiten bis {side}.)"),
+    ("unavailable.grid", "Auf dem Gitter kannst du tippen oder :range, :giveup oder :quit eingeben."),
+    ("invalid_point.empty", "Bitte gib einen Punkt ein, etwa 3,7."),
+    ("invalid_point.malformed", "Ein Punkt besteht aus zwei ganzen Zahlen, x und y, etwa 3,7."),
+    ("invalid_point.off_grid", "Der Punkt muss auf dem Gitter liegen: x zwischen 1 und {width}, y zwischen 1 und {height}."),
+    ("words.intro", "Errate das Wort! Es ist eines der {count} Wörter der Liste {list}.\nJeder Tipp wird damit beantwortet, ob das Geheimwort im Alphabet früher oder später kommt."),
+    ("words.found.one", "„{word}“ in {attempts} Tipp gefunden; eine Liste mit {count} Wörtern braucht nie mehr als {bound}."),
+    ("words.found.other", "„{word}“ in {attempts} Tipps gefunden; eine Liste mit {count} Wörtern braucht nie mehr als {bound}."),
+    ("words.ran_out.one", "Keine Versuche mehr nach {attempts} Tipp; das Wort war „{word}“."),
+    ("words.ran_out.other", "Keine Versuche mehr nach {attempts} Tipps; das Wort war „{word}“."),
+    ("words.debug", "[debug] Geheimwort: {word}, übrig: {count} Wörter von {first} bis {last}"),
+    ("words.input_ended", "Keine Eingabe mehr; das Wort war „{word}“."),
+    ("words.gave_up", "Das Wort war „{word}“."),
+    ("words.lost", "Keine Versuche mehr! Das Wort war „{word}“."),
+    ("words.range", "Das Wort ist eines der {count} von „{first}“ bis „{last}“."),
+    ("words.won.one", "Gewonnen! Du hast {attempts} Versuch gebraucht; binäre Suche braucht für diese Liste nie mehr als {bound}."),
+    ("words.won.other", "Gewonnen! Du hast {attempts} Versuche gebraucht; binäre Suche braucht für diese Liste nie mehr als {bound}."),
+    ("words.later", "Später im Alphabet."),
+    ("words.earlier", "Früher im Alphabet."),
+    ("words.equal", "Das ist das Wort!"),
+    ("unavailable.words", "Bei Wörtern kannst du tippen oder :range, :giveup oder :quit eingeben."),
+    ("invalid_word.empty", "Bitte gib ein Wort ein."),
+    ("invalid_word.not_one_word", "Das Geheimwort ist ein einzelnes Wort."),
+    ("invalid_word.unknown", "„{word}“ steht nicht auf der Wortliste und kann daher nicht das Geheimwort sein."),
+    ("float.intro.absolute", "Errate die reelle Zahl! Sie liegt zwischen {min} und {max}, und jeder Tipp, der höchstens {epsilon} daneben liegt, gewinnt.\nGib Dezimalzahlen mit Punkt ein, etwa 3.14, oder in wissenschaftlicher Schreibweise, etwa 2.5e1."),
+    ("float.intro.relative", "Errate die reelle Zahl! Sie liegt zwischen {min} und {max}, und jeder Tipp, der höchstens {percent} % daneben liegt, gewinnt.\nGib Dezimalzahlen mit Punkt ein, etwa 3.14, oder in wissenschaftlicher Schreibweise, etwa 2.5e1."),
+    ("float.autoplay.one", "Die Geheimzahl war {secret}; Bisektion braucht für diesen Bereich und diese Toleranz nie mehr als {bound} Tipp."),
+    ("float.autoplay.other", "Die Geheimzahl war {secret}; Bisektion braucht für diesen Bereich und diese Toleranz nie mehr als {bound} Tipps."),
+    ("float.debug", "[debug] Geheimzahl: {secret}, übrig: {low} bis {high}"),
+    ("float.won.one", "Gewonnen! Die Geheimzahl war {secret}. Du hast {attempts} Versuch gebraucht; Bisektion braucht nie mehr als {bound}."),
+    ("float.won.other", "Gewonnen! Die Geheimzahl war {secret}. Du hast {attempts} Versuche gebraucht; Bisektion braucht nie mehr als {bound}."),
+    ("float.close_enough", "Nah genug!"),
+    ("unavailable.float", "Bei reellen Zahlen kannst du tippen oder :range, :giveup oder :quit eingeben."),
+    ("invalid_float.decimal_comma", "Verwende einen Punkt für Dezimalstellen: 3.5, nicht 3,5."),
+    ("invalid_float.not_finite", "NaN und Unendlich sind nie die Geheimzahl; rate eine reelle Zahl."),
+    ("words.summary.one", "{list} ({words} Wörter): {rounds} Runde, {wins} gewonnen ({rate} %)"),
+    ("words.summary.other", "{list} ({words} Wörter): {rounds} Runden, {wins} gewonnen ({rate} %)"),
+    ("words.summary.average", ", durchschnittlich {average} Versuche pro Sieg, bestenfalls {fewest}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn french_counts_zero_as_singular() {
+        assert_eq!(Language::English.plural(0), "other");
+        assert_eq!(Language::French.plural(0), "one");
+        assert_eq!(Language::German.plural(0), "other");
+        for language in Language::ALL {
+            assert_eq!(language.plural(1), "one");
+            assert_eq!(language.plural(2), "other");
+        }
+    }
+
+    #[test]
+    fn plural_picks_the_form_for_the_count() {
+        let english = Catalog::new(Language::English);
+        let french = Catalog::new(Language::French);
+        let args = |n: u64| [("count", n.to_string())];
+        assert_eq!(
+            english.plural("summary.redundant", 1, &args(1)),
+            "1 guess had already been ruled out by earlier answers."
+        );
+        assert_eq!(
+            english.plural("summary.redundant", 0, &args(0)),
+            "0 guesses had already been ruled out by earlier answers."
+        );
+        assert_eq!(
+            french.plural("summary.redundant", 0, &args(0)),
+            "0 proposition était déjà exclue par les réponses précédentes."
+        );
+    }
+
+    #[test]
+    fn a_missing_translation_falls_back_to_english() {
+        const PARTIAL: &[(&str, &str)] = &[("too_small", "Trop petit !")];
+        assert_eq!(translate(PARTIAL, "too_small"), "Trop petit !");
+        assert_eq!(translate(PARTIAL, "too_big"), "Too big!");
+        assert_eq!(translate(PARTIAL, "no.such.id"), "{missing message}");
+    }
+
+    #[test]
+    fn every_translation_is_complete() {
+        for language in Language::ALL {
+            assert_eq!(gaps(language), [], "{}", language.name());
+        }
+    }
+
+    #[test]
+    fn placeholders_are_filled_in_wherever_they_appear() {
+        let catalog = Catalog::new(Language::English);
+        assert_eq!(
+            catalog.text("seed", &[("seed", "7".to_string())]),
+            "Seed: 7 (replay this session with --seed 7)"
+        );
+        assert_eq!(placeholders("{b} {a} {b}"), ["a", "b"]);
+    }
+
+    #[test]
+    fn numbers_are_grouped_the_local_way() {
+        let number = |language, n: i128| Catalog::new(language).number(n);
+        assert_eq!(number(Language::English, -1_234_567), "-1,234,567");
+        assert_eq!(number(Language::French, 1000), "1\u{202f}000");
+        assert_eq!(number(Language::German, 999), "999");
+        assert_eq!(Catalog::new(Language::German).decimal(1234.5, 1), "1.234,5");
+    }
+
+    #[test]
+    fn the_environment_picks_the_language() {
+        let env = |lc_all: &str| {
+            let lc_all = lc_all.to_string();
+            move |name: &str| match name {
+                "LC_ALL" => Some(lc_all.clone()),
+                "LANG" => Some("de_DE.UTF-8".to_string()),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            Language::from_env(env("fr_FR.UTF-8@euro")),
+            Language::French
+        );
+        assert_eq!(Language::from_env(env("")), Language::German);
+        assert_eq!(Language::from_env(env("ja_JP")), Language::English);
+    }
+}
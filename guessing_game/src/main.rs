@@ -1,72 +1,2002 @@
 // https://doc.rust-lang.org/std/prelude/index.html
 
-// The Rng trait defines methods that random number generators implement, and this trait must be in scope for us to use those methods.
-use rand::Rng;
-// The Ordering type is another enum and has the variants Less, Greater, and Equal. These are the three outcomes that are possible when you compare two values.
+// The rules live in the guessing_game library (src/lib.rs); this binary only reads guesses from stdin and prints the answers.
+use guessing_game::cli::{self, Command, Config};
+use guessing_game::codes::{self, CodeGame, InvalidCode, Score};
+use guessing_game::difficulty;
+use guessing_game::evil::EvilHost;
+use guessing_game::floats::{self, FloatGame, InvalidFloat, Tolerance};
+use guessing_game::game::Redundancy;
+use guessing_game::grid::{self, Direction, GridGame, InvalidPoint, Region};
+use guessing_game::hints::{Feedback, HotCold};
+use guessing_game::http::{self, ApiOptions};
+use guessing_game::json::Value;
+use guessing_game::locale::{self, Catalog, Gap, Language};
+use guessing_game::protocol::{self, Input, Message, Protocol, SessionEnd};
+use guessing_game::records::{self, Loaded, RoundRecord, Store, WordRecord};
+use guessing_game::reverse::{self, Contradiction, Guesser};
+use guessing_game::server::{self, ServerOptions};
+use guessing_game::strategy::{self, Strategy};
+use guessing_game::tournament;
+use guessing_game::transcript::{self, Header, Recorder, Transcript};
+use guessing_game::tui::{Flow, Screen, Terminal, Tui};
+use guessing_game::words::{self, InvalidWord, WordGame, WordList};
+use guessing_game::{rng, solver, Difficulty, Game, GameRng, Outcome, Session, Status};
 use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::env;
 // or use std::io::stdin below
-use std::io;
-
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 fn main() {
+    // skip(1) drops the program name; env::var returns Err when the variable isn't set, and ok() turns that into None.
+    let config = match Config::parse(env::args().skip(1), |name| env::var(name).ok()) {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if config.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    match config.command {
+        Command::Play => play(&config, &store(&config)),
+        Command::Reverse => reverse(&config),
+        Command::Tui => tui(&config, &store(&config)),
+        Command::Evil => evil(&config),
+        Command::Replay => replay(&config),
+        Command::Bulls => bulls(&config),
+        Command::Grid => grid(&config),
+        Command::Words => words(&config, &store(&config)),
+        Command::Float => float(&config),
+        Command::Stats => show_stats(&store(&config), config.player.as_deref()),
+        Command::Leaderboard => show_leaderboard(&store(&config)),
+        Command::CheckTranslations => check_translations(),
+        Command::Serve => serve(&config),
+        Command::Join => join(&config),
+        Command::Tournament => tournament(&config),
+        Command::Http => http(&config),
+    }
+}
+
+// The data file, for the commands that keep records; the rest run fine without HOME or XDG_DATA_HOME.
+fn store(config: &Config) -> Store {
+    match config.data_file.clone().or_else(records::default_path) {
+        Some(path) => Store::new(path),
+        None => {
+            eprintln!("error: can't find a place for the data file; set HOME, XDG_DATA_HOME or pass --data-file");
+            process::exit(2);
+        }
+    }
+}
+
+// Without an explicit seed we still pick one up front, and say whether we did so it can be shown; then any session can be reproduced with --seed.
+fn pick_seed(config: &Config) -> (u64, bool) {
+    match config.seed {
+        Some(seed) => (seed, false),
+        None => (rng::random_seed(), true),
+    }
+}
+
+fn session_seed(config: &Config) -> u64 {
+    let (seed, random) = pick_seed(config);
+    if random {
+        let catalog = Catalog::new(config.language);
+        println!("{}", catalog.text("seed", &[("seed", seed.to_string())]));
+    }
+    seed
+}
+
+// Where the play loop's input comes from and its messages go: the terminal, or a transcript being replayed. Either way, a recorder can copy everything into a new transcript.
+struct Console {
+    protocol: Protocol,
+    catalog: Catalog,
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
+}
+
+// While replaying, the transcript's input lines stand in for stdin, and messages are collected as JSON instead of printed.
+struct Replay {
+    inputs: VecDeque<String>,
+    outputs: Vec<Value>,
+}
+
+impl Console {
+    fn new(protocol: Protocol, catalog: Catalog) -> Console {
+        Console {
+            protocol,
+            catalog,
+            recorder: None,
+            replay: None,
+        }
+    }
+
+    fn replaying(protocol: Protocol, inputs: Vec<String>) -> Console {
+        Console {
+            protocol,
+            catalog: Catalog::default(),
+            recorder: None,
+            replay: Some(Replay {
+                inputs: inputs.into(),
+                outputs: Vec::new(),
+            }),
+        }
+    }
+
+    // Shows a message the way the chosen protocol does; some messages have no text form.
+    fn emit(&mut self, message: Message) {
+        let json = message.to_json();
+        self.record(|recorder| recorder.output(json.clone()));
+        match &mut self.replay {
+            Some(replay) => replay.outputs.push(json),
+            None => {
+                if let Some(line) = message.render(self.protocol, &self.catalog) {
+                    println!("{line}");
+                }
+            }
+        }
+    }
+
+    // Reads the player's next input. A JSON line that can't be understood is answered with an error and skipped, so one bad line doesn't end the session.
+    fn read_input(&mut self) -> Option<Input> {
+        loop {
+            let line = match &mut self.replay {
+                Some(replay) => replay.inputs.pop_front()?,
+                None => read_line()?,
+            };
+            self.record(|recorder| recorder.input(&line));
+            match self.protocol {
+                Protocol::Text => return Some(Input::from_text(&line)),
+                Protocol::Jsonl if line.trim().is_empty() => continue,
+                Protocol::Jsonl => match Input::from_json(&line) {
+                    Ok(input) => return Some(input),
+                    Err(message) => self.emit(message),
+                },
+            }
+        }
+    }
+
+    // A transcript that can't be written shouldn't end the game, so the first failure is reported and recording stops.
+    fn record<F: FnOnce(&mut Recorder) -> io::Result<()>>(&mut self, write: F) {
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(error) = write(recorder) {
+                eprintln!("warning: stopped recording the transcript: {error}");
+                self.recorder = None;
+            }
+        }
+    }
+}
+
+fn play(config: &Config, store: &Store) {
+    let player = player_name(config);
+
+    let (seed, seed_was_random) = pick_seed(config);
+    let mut console = Console::new(config.protocol, Catalog::new(config.language));
+
+    if config.autoplay {
+        console.emit(hello(config, seed, seed_was_random));
+        autoplay(config, seed, &mut console);
+        return;
+    }
+
+    if let Some(path) = &config.record {
+        let header = Header {
+            started_at: Recorder::now(),
+            seed,
+            difficulty: config.difficulty,
+            rules: config.rules,
+            feedback: config.feedback,
+            reveal: config.reveal,
+            protocol: config.protocol,
+            redundancy: config.redundancy,
+            undos: config.undos,
+        };
+        match Recorder::create(path, &header) {
+            Ok(recorder) => console.recorder = Some(recorder),
+            Err(error) => {
+                eprintln!("error: can't record to {}: {error}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    let end = run_session(
+        config,
+        seed,
+        seed_was_random,
+        &mut console,
+        Some((store, &player)),
+    );
+
+    // process::exit ends the program immediately with the given status code, which lets scripts tell how the session ended.
+    process::exit(end.exit_code());
+}
+
+// Lists what each language lacks compared with English, and fails if anything does, so it can run as a check before a release.
+fn check_translations() {
+    let mut complete = true;
+    for language in Language::ALL {
+        if language == Language::English {
+            continue;
+        }
+        let gaps = locale::gaps(language);
+        if gaps.is_empty() {
+            println!("{} ({}): complete", language.code(), language.name());
+            continue;
+        }
+        complete = false;
+        println!(
+            "{} ({}): {} message(s) need work",
+            language.code(),
+            language.name(),
+            gaps.len()
+        );
+        for gap in gaps {
+            match gap {
+                Gap::Missing(id) => println!("  missing, shown in English: {id}"),
+                Gap::Placeholders(id) => println!("  placeholders differ from English: {id}"),
+            }
+        }
+    }
+    if !complete {
+        process::exit(1);
+    }
+}
+
+// Appends a finished round to the data file that stats and leaderboard read.
+fn record_round(
+    store: &Store,
+    player: &str,
+    config: &Config,
+    seed: u64,
+    round: u32,
+    game: &Game,
+    duration: Duration,
+) {
+    let record = RoundRecord {
+        finished_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+        player: player.to_string(),
+        difficulty: config.difficulty,
+        min: config.rules.bounds.min(),
+        max: config.rules.bounds.max(),
+        max_attempts: config.rules.max_attempts,
+        attempts: game.attempts(),
+        duration,
+        seed,
+        round,
+        status: game.status(),
+        score: game.score(),
+    };
+    // Losing the record shouldn't cost the player their game, so a write failure is only a warning.
+    if let Err(error) = store.append(&record) {
+        eprintln!(
+            "warning: couldn't record the round in {}: {error}",
+            store.path().display()
+        );
+    }
+}
+
+fn player_name(config: &Config) -> String {
+    config
+        .player
+        .clone()
+        .or_else(|| env::var("USER").ok())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
+
+// The full-screen game. Everything drawn comes from Tui::render; this loop only moves keys in and frames out.
+fn tui(config: &Config, store: &Store) {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("error: tui needs a terminal; use play to read guesses from a pipe");
+        process::exit(1);
+    }
+    let player = player_name(config);
+    let (seed, _) = pick_seed(config);
+    let mut session = Session::new(rng::seeded(seed), config.rules);
+    session.set_redundancy(config.redundancy);
+    let catalog = Catalog::new(config.language);
+    let mut tui = Tui::new(session, config.difficulty, catalog);
+
+    let terminal = match Terminal::enter() {
+        Ok(terminal) => terminal,
+        Err(error) => {
+            eprintln!("error: can't take over the terminal: {error}");
+            process::exit(1);
+        }
+    };
+    let result = loop {
+        let (width, height) = terminal.size();
+        let mut screen = Screen::new(width, height);
+        tui.render(&mut screen);
+        if let Err(error) = terminal.draw(&screen) {
+            break Err(error);
+        }
+        let key = match terminal.read_key() {
+            Ok(key) => key,
+            Err(error) => break Err(error),
+        };
+        let flow = tui.press(key);
+        for round in tui.finished_rounds() {
+            record_round(
+                store,
+                &player,
+                config,
+                seed,
+                round.number,
+                &round.game,
+                round.duration,
+            );
+        }
+        if flow == Flow::Quit {
+            break Ok(());
+        }
+    };
+    // Dropping the terminal puts it back the way it was, so the summary lands in the normal scrollback.
+    drop(terminal);
+
+    if let Err(error) = result {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+    println!("{}", catalog.text("seed", &[("seed", seed.to_string())]));
+    let summary = Message::Summary {
+        end: SessionEnd::Done,
+        tally: tui.session().tally(),
+    };
+    if let Some(text) = summary.text(&catalog) {
+        println!("{text}");
+    }
+}
+
+fn hello(config: &Config, seed: u64, seed_was_random: bool) -> Message {
+    Message::Hello {
+        seed,
+        seed_was_random,
+        difficulty: config.difficulty,
+        rules: config.rules,
+    }
+}
+
+// Plays rounds until the player stops, recording each finished round in `records` (the store and player name) when given.
+// Only rounds that were won, lost or given up with :giveup are recorded; one cut short by quitting, the end of input or :new was never finished.
+fn run_session(
+    config: &Config,
+    seed: u64,
+    seed_was_random: bool,
+    console: &mut Console,
+    records: Option<(&Store, &str)>,
+) -> SessionEnd {
+    console.emit(hello(config, seed, seed_was_random));
+
+    let mut session = Session::new(rng::seeded(seed), config.rules);
+    session.set_redundancy(config.redundancy);
+    session.set_undos(config.undos);
+    let mut round = 1;
+
+    let end = 'rounds: loop {
+        console.emit(Message::RoundStarted {
+            round,
+            rules: config.rules,
+        });
+        let started = Instant::now();
+        let round_end = play_round(&mut session, config, console);
+
+        let game = session.game();
+        console.emit(Message::RoundOver {
+            round,
+            status: game.status(),
+            secret: game.secret(),
+            attempts: game.attempts(),
+            score: game.score(),
+            input_ended: round_end == RoundEnd::EndOfInput,
+        });
+
+        if let (Some((store, player)), RoundEnd::Finished) = (records, &round_end) {
+            record_round(store, player, config, seed, round, game, started.elapsed());
+        }
+
+        match round_end {
+            RoundEnd::Finished => {}
+            RoundEnd::New => {
+                session.new_round();
+                round += 1;
+                continue;
+            }
+            RoundEnd::Quit => break SessionEnd::Quit,
+            RoundEnd::EndOfInput => break SessionEnd::EndOfInput,
+        }
+
+        let again = loop {
+            console.emit(Message::AskPlayAgain);
+            break match console.read_input() {
+                Some(Input::PlayAgain(again)) => again,
+                // y always works, whatever the language's own word for yes.
+                Some(Input::Guess(answer)) => {
+                    let answer = answer.trim();
+                    answer.eq_ignore_ascii_case("y")
+                        || answer.eq_ignore_ascii_case(&console.catalog.text("yes", &[]))
+                }
+                Some(Input::NewRound) => true,
+                Some(Input::Quit) => break 'rounds SessionEnd::Quit,
+                Some(Input::BadCommand(error)) => {
+                    console.emit(Message::BadCommand(error));
+                    continue;
+                }
+                Some(Input::History(_) | Input::Hint | Input::Undo | Input::Help) => {
+                    console.emit(Message::Unavailable("not_in_round"));
+                    continue;
+                }
+                // Running out of input between rounds is the same as answering no.
+                Some(Input::GiveUp) | Some(Input::Range) | None => false,
+            };
+        };
+        if !again {
+            break SessionEnd::Done;
+        }
+        session.new_round();
+        round += 1;
+    };
+
+    console.emit(Message::Summary {
+        end,
+        tally: session.tally(),
+    });
+    end
+}
+
+// Plays a transcript's input again against this build's engine and checks that every message comes out the same.
+fn replay(config: &Config) {
+    let path = config.transcript.as_deref().unwrap_or(Path::new(""));
+    let transcript = match Transcript::load(path) {
+        Ok(transcript) => transcript,
+        Err(message) => {
+            eprintln!("error: {message}");
+            process::exit(1);
+        }
+    };
+    let header = transcript.header;
+    let recorded = Config {
+        difficulty: header.difficulty,
+        rules: header.rules,
+        seed: Some(header.seed),
+        feedback: header.feedback,
+        reveal: header.reveal,
+        protocol: header.protocol,
+        redundancy: header.redundancy,
+        undos: header.undos,
+        ..Config::default()
+    };
+
+    let inputs = transcript.inputs();
+    let mut console = Console::replaying(header.protocol, inputs.clone());
+    run_session(&recorded, header.seed, false, &mut console, None);
+    let now = console
+        .replay
+        .map(|replay| replay.outputs)
+        .unwrap_or_default();
+
+    println!(
+        "Replaying {} (seed {}, {} input line(s))",
+        path.display(),
+        header.seed,
+        inputs.len()
+    );
+    if transcript.version < transcript::SAME_SECRETS_SINCE {
+        println!(
+            "This transcript is version {}, recorded before seeds dealt the same secrets on every build, so its secrets differ now.",
+            transcript.version
+        );
+    }
+    if let Some(divergence) = transcript.divergence(&now) {
+        let after = match divergence.read {
+            0 => "before any input".to_string(),
+            read => format!("after input line {read} ({:?})", inputs[read - 1]),
+        };
+        let show = |message: Option<Value>| {
+            message.map_or("nothing".to_string(), |message| message.to_string())
+        };
+        println!("Divergence at message {}, {after}:", divergence.index + 1);
+        println!("  recorded: {}", show(divergence.recorded));
+        println!("  now:      {}", show(divergence.now));
+        process::exit(cli::EXIT_DIVERGED);
+    }
+    println!("All {} message(s) match.", now.len());
+}
+
+// Lets binary search play the first round the same seed would give a human, printing each step. Autoplayed rounds aren't recorded.
+fn autoplay(config: &Config, seed: u64, console: &mut Console) {
+    let mut game = Game::random(config.rules, &mut rng::seeded(seed));
+    let bound = config.rules.optimal_attempts();
+
+    match solver::autoplay(&mut game) {
+        // Programs get the same outcome and round_over messages a played round would produce.
+        Ok(steps) if config.protocol == Protocol::Jsonl => {
+            for (number, step) in steps.iter().enumerate() {
+                console.emit(Message::Answer {
+                    guess: step.guess,
+                    outcome: step.outcome,
+                    attempts: number as u32 + 1,
+                    score: game.score(),
+                    hint: None,
+                });
+            }
+            console.emit(Message::RoundOver {
+                round: 1,
+                status: game.status(),
+                secret: game.secret(),
+                attempts: game.attempts(),
+                score: game.score(),
+                input_ended: false,
+            });
+        }
+        Ok(steps) => {
+            let catalog = &console.catalog;
+            for (number, step) in steps.iter().enumerate() {
+                print_step(number, step, catalog);
+            }
+            let guesses = steps.len() as u64;
+            let args = [
+                ("secret", catalog.number(game.secret())),
+                ("guesses", catalog.number(guesses)),
+                ("bound", catalog.number(bound)),
+            ];
+            match game.status() {
+                Status::Won => println!("{}", catalog.plural("autoplay.found", guesses, &args)),
+                // Only possible when --attempts allows fewer guesses than the optimum.
+                _ => println!("{}", catalog.plural("autoplay.ran_out", guesses, &args)),
+            }
+        }
+        Err(message) => {
+            eprintln!("error: the engine's answers were inconsistent: {message}");
+            process::exit(1);
+        }
+    }
+}
+
+fn print_step(number: usize, step: &solver::Step, catalog: &Catalog) {
+    let answer = match step.outcome {
+        Outcome::TooSmall => catalog.text("too_small", &[]),
+        Outcome::TooBig => catalog.text("too_big", &[]),
+        Outcome::Win => catalog.text("autoplay.win", &[]),
+        Outcome::Invalid(reason) => say_text(Message::Rejected(reason), catalog),
+    };
+    let args = [
+        ("n", (number + 1).to_string()),
+        ("low", catalog.number(step.low)),
+        ("high", catalog.number(step.high)),
+        ("guess", catalog.number(step.guess)),
+        ("answer", answer),
+    ];
+    println!("{}", catalog.text("autoplay.step", &args));
+}
+
+// A message as the text protocol would show it, for the modes that print without a Console.
+fn say_text(message: Message, catalog: &Catalog) -> String {
+    message.text(catalog).unwrap_or_default()
+}
+
+fn say(message: Message, catalog: &Catalog) {
+    println!("{}", say_text(message, catalog));
+}
+
+// The computer guesses the player's number from their higher/lower/correct answers.
+fn reverse(config: &Config) {
+    let catalog = Catalog::new(config.language);
+    let bounds = config.rules.bounds;
+    let range = [
+        ("min", catalog.number(bounds.min())),
+        ("max", catalog.number(bounds.max())),
+    ];
+    println!("{}", catalog.text("reverse.intro", &range));
+
+    let mut guesser = Guesser::new(bounds);
+    while let Some(guess) = guesser.next_guess() {
+        println!(
+            "{}",
+            catalog.text("reverse.ask", &[("guess", catalog.number(guess))])
+        );
+
+        let line = match read_line() {
+            Some(line) => line,
+            None => {
+                println!();
+                println!("{}", catalog.text("reverse.input_ended", &[]));
+                process::exit(cli::EXIT_END_OF_INPUT);
+            }
+        };
+        if is_quit(&line) {
+            process::exit(cli::EXIT_QUIT);
+        }
+
+        let answer = match reverse::parse_answer(&line) {
+            Some(answer) => answer,
+            None => {
+                println!("{}", catalog.text("reverse.bad_answer", &[]));
+                continue;
+            }
+        };
+        if let Err(contradiction) = guesser.answer(answer) {
+            println!("{}", contradiction_text(&contradiction, &catalog));
+        }
+    }
+
+    let guesses = guesser.guesses();
+    let args = [
+        (
+            "number",
+            catalog.number(guesser.answers().last().map_or(0, |answer| answer.guess)),
+        ),
+        ("guesses", catalog.number(guesses)),
+        ("bound", catalog.number(config.rules.optimal_attempts())),
+    ];
+    println!("{}", catalog.plural("reverse.found", guesses.into(), &args));
+}
+
+// A contradiction in the catalog's language; its Display stays English, like InvalidGuess's.
+fn contradiction_text(contradiction: &Contradiction, catalog: &Catalog) -> String {
+    let said = |answer: &reverse::Answer| {
+        let id = match answer.ordering {
+            Ordering::Less => "reverse.higher",
+            Ordering::Greater => "reverse.lower",
+            Ordering::Equal => "reverse.equal",
+        };
+        catalog.text(id, &[("guess", catalog.number(answer.guess))])
+    };
+    let answer = ("answer", said(&contradiction.answer));
+    match contradiction.conflicts_with {
+        Some((number, earlier)) => catalog.text(
+            "reverse.contradicts_answer",
+            &[
+                answer,
+                ("number", number.to_string()),
+                ("earlier", said(&earlier)),
+            ],
+        ),
+        None => catalog.text(
+            "reverse.contradicts_range",
+            &[
+                answer,
+                ("min", catalog.number(contradiction.bounds.min())),
+                ("max", catalog.number(contradiction.bounds.max())),
+            ],
+        ),
+    }
+}
+
+// What the single-round modes (evil, bulls, grid, words and float) tell `play_typed`, which reads their guesses one line at a time.
+trait TypedRound {
+    // The mode's name in catalog ids, like unavailable.bulls.
+    const MODE: &'static str;
+
+    fn is_finished(&self) -> bool;
+    fn status(&self) -> Status;
+    fn give_up(&mut self);
+    // The secret and what's left of the search, shown before each prompt with --reveal.
+    fn reveal(&self, catalog: &Catalog);
+    fn ask(&self, catalog: &Catalog);
+    // Answers :range.
+    fn range(&self, config: &Config, catalog: &Catalog);
+    // Submits a typed guess and prints the answer.
+    fn guess(&mut self, guess: &str, config: &Config, catalog: &Catalog);
+    // Says that the round is over without a win, and what the secret was; `how` is gave_up, lost or input_ended.
+    fn round_over(&mut self, how: &str, catalog: &Catalog) -> String;
+}
+
+// Reads guesses and commands until the round is over or the player leaves, then says how it ended unless it was won.
+// Undo, hints, history and new rounds belong to the number game, so here each gets the mode's unavailable message.
+fn play_typed<R: TypedRound>(round: &mut R, config: &Config, catalog: &Catalog) -> RoundEnd {
+    let mut end = RoundEnd::Finished;
+    while !round.is_finished() {
+        if config.reveal {
+            round.reveal(catalog);
+        }
+        round.ask(catalog);
+
+        let Some(line) = read_line() else {
+            println!();
+            end = RoundEnd::EndOfInput;
+            break;
+        };
+        match Input::from_text(&line) {
+            Input::Quit => {
+                end = RoundEnd::Quit;
+                break;
+            }
+            Input::GiveUp => round.give_up(),
+            Input::Range => round.range(config, catalog),
+            Input::BadCommand(error) => say(Message::BadCommand(error), catalog),
+            Input::History(_) | Input::Hint | Input::Undo | Input::NewRound | Input::Help => {
+                println!("{}", catalog.text(&format!("unavailable.{}", R::MODE), &[]))
+            }
+            Input::Guess(guess) => round.guess(&guess, config, catalog),
+            Input::PlayAgain(_) => unreachable!("typed input is never a play-again answer"),
+        }
+    }
+
+    // Leaving in the middle gives the round up, which is what lets the secret be told.
+    round.give_up();
+    let how = match (&end, round.status()) {
+        (RoundEnd::EndOfInput, _) => "input_ended",
+        (_, Status::Won) => return end,
+        (_, Status::Lost) => "lost",
+        _ => "gave_up",
+    };
+    println!("{}", round.round_over(how, catalog));
+    end
+}
+
+// Leaves with the exit status that says why a round was cut short; after a round that finished it just returns.
+fn exit_if_cut_short(end: RoundEnd) {
+    match end {
+        RoundEnd::Quit => process::exit(cli::EXIT_QUIT),
+        RoundEnd::EndOfInput => process::exit(cli::EXIT_END_OF_INPUT),
+        RoundEnd::Finished | RoundEnd::New => {}
+    }
+}
+
+// Only an explicit --attempts limits a round of bulls, grid, words or float; the presets' budgets were chosen for numbers in a range.
+fn explicit_attempts(config: &Config) -> Option<u32> {
+    match config.difficulty {
+        Difficulty::Custom => config.rules.max_attempts,
+        _ => None,
+    }
+}
+
+// The evil host, with the generator it settles on a secret from once the round is over.
+struct EvilRound {
+    host: EvilHost,
+    rng: GameRng,
+}
+
+impl EvilRound {
+    fn secret(&mut self) -> i128 {
+        self.host.settle(&mut self.rng).expect("the round is over")
+    }
+}
+
+impl TypedRound for EvilRound {
+    const MODE: &'static str = "evil";
+
+    fn is_finished(&self) -> bool {
+        self.host.is_finished()
+    }
+
+    fn status(&self) -> Status {
+        self.host.status()
+    }
+
+    fn give_up(&mut self) {
+        self.host.give_up();
+    }
+
+    fn reveal(&self, catalog: &Catalog) {
+        let remaining = self.host.remaining();
+        let args = [
+            ("low", catalog.number(*remaining.start())),
+            ("high", catalog.number(*remaining.end())),
+            ("attempts", catalog.number(self.host.attempts())),
+        ];
+        println!("{}", catalog.text("evil.debug", &args));
+    }
+
+    fn ask(&self, catalog: &Catalog) {
+        let message = Message::AskGuess {
+            bounds: self.host.rules().bounds,
+            attempts_left: self.host.attempts_left(),
+        };
+        say(message, catalog);
+    }
+
+    fn range(&self, _: &Config, catalog: &Catalog) {
+        let remaining = self.host.remaining();
+        let message = Message::Interval {
+            low: *remaining.start(),
+            high: *remaining.end(),
+        };
+        say(message, catalog);
+    }
+
+    fn guess(&mut self, guess: &str, _: &Config, catalog: &Catalog) {
+        match self.host.submit(guess) {
+            Outcome::TooSmall => println!("{}", catalog.text("too_small", &[])),
+            Outcome::TooBig => println!("{}", catalog.text("too_big", &[])),
+            Outcome::Win => {
+                let args = [
+                    ("attempts", catalog.number(self.host.attempts())),
+                    ("score", catalog.number(self.host.score())),
+                ];
+                println!(
+                    "{}",
+                    catalog.plural("win", self.host.attempts().into(), &args)
+                );
+            }
+            Outcome::Invalid(reason) => say(Message::Rejected(reason), catalog),
+        }
+    }
+
+    fn round_over(&mut self, how: &str, catalog: &Catalog) -> String {
+        let secret = [("secret", catalog.number(self.secret()))];
+        catalog.text(&format!("round_over.{how}"), &secret)
+    }
+}
+
+// One round against the evil host. When it's over the host settles on a secret, and we check it against every answer given, so the player can see it never lied.
+fn evil(config: &Config) {
+    let catalog = Catalog::new(config.language);
+    let seed = session_seed(config);
+    let bounds = config.rules.bounds;
+    let optimal = config.rules.optimal_attempts();
+    let mut round = EvilRound {
+        host: EvilHost::new(config.rules),
+        rng: rng::seeded(seed),
+    };
+
+    let range = [
+        ("min", catalog.number(bounds.min())),
+        ("max", catalog.number(bounds.max())),
+    ];
+    println!("{}", catalog.text("evil.intro", &range));
+
+    let end = if config.autoplay {
+        let steps = match solver::autoplay(&mut round.host) {
+            Ok(steps) => steps,
+            Err(message) => {
+                eprintln!("error: the solver failed: {message}");
+                process::exit(1);
+            }
+        };
+        for (number, step) in steps.iter().enumerate() {
+            print_step(number, step, &catalog);
+        }
+        let guesses = steps.len() as u64;
+        let args = [
+            ("guesses", catalog.number(guesses)),
+            ("bound", catalog.number(optimal)),
+        ];
+        println!("{}", catalog.plural("evil.autoplay", guesses, &args));
+        RoundEnd::Finished
+    } else {
+        play_typed(&mut round, config, &catalog)
+    };
+
+    // settle only ever picks a number the answers allow, so this failing would be a bug in the host.
+    let secret = round.secret();
+    if !round.host.consistent_with(secret) {
+        eprintln!("error: {secret} contradicts an earlier answer");
+        process::exit(1);
+    }
+    let answers = round.host.answers().len() as u64;
+    let args = [
+        ("secret", catalog.number(secret)),
+        ("answers", catalog.number(answers)),
+    ];
+    println!("{}", catalog.plural("evil.check", answers, &args));
+    exit_if_cut_short(end);
+}
+
+// Bulls and cows: one round against a secret code, read from stdin the same way as the number game.
+fn bulls(config: &Config) {
+    let rules = config.code.clone();
+    let max_attempts = explicit_attempts(config);
+    let seed = session_seed(config);
+    let mut game = CodeGame::random(rules.clone(), max_attempts, &mut rng::seeded(seed));
+    let catalog = Catalog::new(config.language);
+    let secret = [("code", rules.show(game.secret()))];
+    let length = catalog.number(rules.length() as u64);
+
+    let alphabet: String = rules.alphabet().iter().collect();
+    let intro = if rules.repeats() {
+        "bulls.intro.repeats"
+    } else {
+        "bulls.intro.distinct"
+    };
+    println!(
+        "{}",
+        catalog.text(intro, &[("length", length.clone()), ("alphabet", alphabet)])
+    );
+
+    if config.autoplay {
+        if let Err(message) = codes::autoplay(&mut game) {
+            eprintln!("error: the solver failed: {message}");
+            process::exit(1);
+        }
+        for (number, (code, score)) in game.guesses().iter().enumerate() {
+            let args = [
+                ("n", (number + 1).to_string()),
+                ("guess", rules.show(code)),
+                ("answer", score_text(score, &catalog)),
+            ];
+            println!("{}", catalog.text("step", &args));
+        }
+        let count = game.attempts().into();
+        let attempts = ("attempts", catalog.number(game.attempts()));
+        match game.status() {
+            Status::Won => println!(
+                "{}",
+                catalog.plural("bulls.cracked", count, &[secret[0].clone(), attempts])
+            ),
+            _ => println!("{}", catalog.plural("ran_out", count, &[attempts])),
+        }
+        return;
+    }
+
+    exit_if_cut_short(play_typed(&mut game, config, &catalog));
+}
+
+impl TypedRound for CodeGame {
+    const MODE: &'static str = "bulls";
+
+    fn is_finished(&self) -> bool {
+        CodeGame::is_finished(self)
+    }
+
+    fn status(&self) -> Status {
+        CodeGame::status(self)
+    }
+
+    fn give_up(&mut self) {
+        CodeGame::give_up(self);
+    }
+
+    fn reveal(&self, catalog: &Catalog) {
+        let secret = [("code", self.rules().show(self.secret()))];
+        println!("{}", catalog.text("bulls.debug", &secret));
+    }
+
+    fn ask(&self, catalog: &Catalog) {
+        let length = ("length", catalog.number(self.rules().length() as u64));
+        match self.attempts_left() {
+            Some(left) => println!(
+                "{}",
+                catalog.plural(
+                    "bulls.ask.limited",
+                    left.into(),
+                    &[length, ("left", catalog.number(left))]
+                )
+            ),
+            None => println!("{}", catalog.text("bulls.ask.unlimited", &[length])),
+        }
+    }
+
+    fn range(&self, _: &Config, catalog: &Catalog) {
+        println!("{}", catalog.text("bulls.no_range", &[]));
+    }
+
+    fn guess(&mut self, guess: &str, _: &Config, catalog: &Catalog) {
+        match self.submit(guess) {
+            Ok(_) if self.status() == Status::Won => println!(
+                "{}",
+                catalog.plural(
+                    "bulls.won",
+                    self.attempts().into(),
+                    &[("attempts", catalog.number(self.attempts()))]
+                )
+            ),
+            Ok(score) => println!("{}", score_text(&score, catalog)),
+            Err(reason) => println!("{}", invalid_code_text(&reason, catalog)),
+        }
+    }
+
+    fn round_over(&mut self, how: &str, catalog: &Catalog) -> String {
+        let secret = [("code", self.rules().show(self.secret()))];
+        catalog.text(&format!("bulls.{how}"), &secret)
+    }
+}
+
+fn score_text(score: &Score, catalog: &Catalog) -> String {
+    let count =
+        |id: &str, n: usize| catalog.plural(id, n as u64, &[("count", catalog.number(n as u64))]);
+    catalog.text(
+        "bulls.score",
+        &[
+            ("bulls", count("bulls.bulls", score.bulls)),
+            ("cows", count("bulls.cows", score.cows)),
+        ],
+    )
+}
+
+// Why a code was rejected, in the catalog's language.
+fn invalid_code_text(reason: &InvalidCode, catalog: &Catalog) -> String {
+    match reason {
+        InvalidCode::Empty => catalog.text("invalid_code.empty", &[]),
+        InvalidCode::WrongLength { expected, got } => catalog.text(
+            "invalid_code.wrong_length",
+            &[
+                ("expected", catalog.number(*expected as u64)),
+                ("got", catalog.number(*got as u64)),
+            ],
+        ),
+        InvalidCode::NotInAlphabet(symbol) => catalog.text(
+            "invalid_code.not_in_alphabet",
+            &[("symbol", symbol.to_string())],
+        ),
+        InvalidCode::Repeated(symbol) => {
+            catalog.text("invalid_code.repeated", &[("symbol", symbol.to_string())])
+        }
+        InvalidCode::Finished => catalog.text("invalid.finished", &[]),
+    }
+}
+
+fn grid(config: &Config) {
+    let rules = config.grid;
+    let max_attempts = explicit_attempts(config);
+    let seed = session_seed(config);
+    let mut game = GridGame::random(rules, max_attempts, &mut rng::seeded(seed));
+    let catalog = Catalog::new(config.language);
+    // Coordinates are never grouped into thousands: a point is typed as x,y, and a grouping comma would read as the separator.
+    let size = [
+        ("width", rules.width().to_string()),
+        ("height", rules.height().to_string()),
+    ];
+    let secret = [("point", game.secret().to_string())];
+    println!("{}", catalog.text("grid.intro", &size));
+
+    if config.autoplay {
+        let steps = match grid::autoplay(&mut game) {
+            Ok(steps) => steps,
+            Err(message) => {
+                eprintln!("error: the solver failed: {message}");
+                process::exit(1);
+            }
+        };
+        for (number, step) in steps.iter().enumerate() {
+            let args = [
+                ("n", (number + 1).to_string()),
+                ("point", step.guess.to_string()),
+                ("region", region_text(&step.region, &catalog)),
+                ("direction", direction_text(step.direction, &catalog)),
+            ];
+            println!("{}", catalog.text("grid.step", &args));
+        }
+        let count = game.attempts().into();
+        let attempts = ("attempts", catalog.number(game.attempts()));
+        match game.status() {
+            Status::Won => {
+                let [width, height] = size.clone();
+                let args = [
+                    secret[0].clone(),
+                    attempts,
+                    width,
+                    height,
+                    ("bound", catalog.number(rules.optimal_attempts())),
+                ];
+                println!("{}", catalog.plural("grid.found", count, &args));
+            }
+            _ => println!("{}", catalog.plural("ran_out", count, &[attempts])),
+        }
+        if config.map {
+            print_map(&game, &catalog);
+        }
+        return;
+    }
+
+    exit_if_cut_short(play_typed(&mut game, config, &catalog));
+    if config.map {
+        print_map(&game, &catalog);
+    }
+}
+
+impl TypedRound for GridGame {
+    const MODE: &'static str = "grid";
+
+    fn is_finished(&self) -> bool {
+        GridGame::is_finished(self)
+    }
+
+    fn status(&self) -> Status {
+        GridGame::status(self)
+    }
+
+    fn give_up(&mut self) {
+        GridGame::give_up(self);
+    }
+
+    fn reveal(&self, catalog: &Catalog) {
+        let args = [
+            ("point", self.secret().to_string()),
+            ("region", region_text(&self.region(), catalog)),
+        ];
+        println!("{}", catalog.text("grid.debug", &args));
+    }
+
+    fn ask(&self, catalog: &Catalog) {
+        match self.attempts_left() {
+            Some(left) => println!(
+                "{}",
+                catalog.plural(
+                    "grid.ask.limited",
+                    left.into(),
+                    &[("left", catalog.number(left))]
+                )
+            ),
+            None => println!("{}", catalog.text("grid.ask.unlimited", &[])),
+        }
+    }
+
+    fn range(&self, config: &Config, catalog: &Catalog) {
+        let region = ("region", region_text(&self.region(), catalog));
+        println!("{}", catalog.text("grid.range", &[region]));
+        if config.map {
+            print_map(self, catalog);
+        }
+    }
+
+    fn guess(&mut self, guess: &str, config: &Config, catalog: &Catalog) {
+        match self.submit(guess) {
+            Ok(Direction::Hit) => {
+                let args = [
+                    ("attempts", catalog.number(self.attempts())),
+                    ("bound", catalog.number(self.rules().optimal_attempts())),
+                ];
+                println!(
+                    "{}",
+                    catalog.plural("grid.hit", self.attempts().into(), &args)
+                );
+            }
+            Ok(direction) => {
+                println!("{}", direction_text(direction, catalog));
+                if config.map {
+                    print_map(self, catalog);
+                }
+            }
+            Err(reason) => println!("{}", invalid_point_text(&reason, catalog)),
+        }
+    }
+
+    fn round_over(&mut self, how: &str, catalog: &Catalog) -> String {
+        let secret = [("point", self.secret().to_string())];
+        catalog.text(&format!("grid.{how}"), &secret)
+    }
+}
+
+fn print_map(game: &GridGame, catalog: &Catalog) {
+    match game.map() {
+        Some(map) => println!("{map}"),
+        None => println!(
+            "{}",
+            catalog.text("grid.too_big", &[("side", grid::MAX_MAP_SIDE.to_string())])
+        ),
+    }
+}
+
+// The compass points are abbreviated the local way: west is O in French and east is O in German.
+fn direction_text(direction: Direction, catalog: &Catalog) -> String {
+    catalog.text(&format!("direction.{}", direction.name()), &[])
+}
+
+fn region_text(region: &Region, catalog: &Catalog) -> String {
+    catalog.text(
+        "grid.region",
+        &[
+            ("west", region.west.to_string()),
+            ("east", region.east.to_string()),
+            ("south", region.south.to_string()),
+            ("north", region.north.to_string()),
+        ],
+    )
+}
+
+fn invalid_point_text(reason: &InvalidPoint, catalog: &Catalog) -> String {
+    match reason {
+        InvalidPoint::Empty => catalog.text("invalid_point.empty", &[]),
+        InvalidPoint::Malformed => catalog.text("invalid_point.malformed", &[]),
+        InvalidPoint::OffGrid(rules) => catalog.text(
+            "invalid_point.off_grid",
+            &[
+                ("width", rules.width().to_string()),
+                ("height", rules.height().to_string()),
+            ],
+        ),
+        InvalidPoint::Finished => catalog.text("invalid.finished", &[]),
+    }
+}
+
+fn words(config: &Config, store: &Store) {
+    let list = match &config.word_list {
+        Some(path) => match WordList::load(path) {
+            Ok(list) => list,
+            Err(message) => {
+                eprintln!("error: {message}");
+                process::exit(1);
+            }
+        },
+        None => WordList::bundled(),
+    };
+    let max_attempts = explicit_attempts(config);
+    let seed = session_seed(config);
+    let mut game = WordGame::random(&list, max_attempts, &mut rng::seeded(seed));
+    let catalog = Catalog::new(config.language);
+    let secret = [("word", game.secret().to_string())];
+
+    let args = [
+        ("count", catalog.number(list.len() as u64)),
+        ("list", list.name().to_string()),
+    ];
+    println!("{}", catalog.text("words.intro", &args));
+
+    if config.autoplay {
+        if let Err(message) = words::autoplay(&mut game) {
+            eprintln!("error: the solver failed: {message}");
+            process::exit(1);
+        }
+        for (number, (word, ordering)) in game.guesses().iter().enumerate() {
+            let args = [
+                ("n", (number + 1).to_string()),
+                ("guess", word.clone()),
+                ("answer", word_answer(*ordering, &catalog)),
+            ];
+            println!("{}", catalog.text("step", &args));
+        }
+        let args = [
+            secret[0].clone(),
+            ("attempts", catalog.number(game.attempts())),
+            ("count", catalog.number(list.len() as u64)),
+            ("bound", catalog.number(list.optimal_attempts())),
+        ];
+        let id = match game.status() {
+            Status::Won => "words.found",
+            // Only possible when --attempts allows fewer guesses than the list can need.
+            _ => "words.ran_out",
+        };
+        println!("{}", catalog.plural(id, game.attempts().into(), &args));
+        return;
+    }
+
+    let started = Instant::now();
+    exit_if_cut_short(play_typed(&mut game, config, &catalog));
+
+    let record = WordRecord {
+        finished_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+        player: player_name(config),
+        list: list.name().to_string(),
+        words: list.len(),
+        attempts: game.attempts(),
+        duration: started.elapsed(),
+        seed,
+        status: game.status(),
+    };
+    if let Err(error) = store.append_word(&record) {
+        eprintln!(
+            "warning: couldn't record the round in {}: {error}",
+            store.path().display()
+        );
+        return;
+    }
+    let loaded = load_records(store);
+    if let Some(stats) = records::list_stats(&loaded.word_records).get(list.name()) {
+        println!("{}", list_summary(list.name(), stats, &catalog));
+    }
+}
+
+impl TypedRound for WordGame<'_> {
+    const MODE: &'static str = "words";
+
+    fn is_finished(&self) -> bool {
+        WordGame::is_finished(self)
+    }
+
+    fn status(&self) -> Status {
+        WordGame::status(self)
+    }
+
+    fn give_up(&mut self) {
+        WordGame::give_up(self);
+    }
+
+    fn reveal(&self, catalog: &Catalog) {
+        let [count, first, last] = remaining_words(self, catalog);
+        let args = [("word", self.secret().to_string()), count, first, last];
+        println!("{}", catalog.text("words.debug", &args));
+    }
+
+    fn ask(&self, catalog: &Catalog) {
+        ask(self.attempts_left(), catalog);
+    }
+
+    fn range(&self, _: &Config, catalog: &Catalog) {
+        println!(
+            "{}",
+            catalog.text("words.range", &remaining_words(self, catalog))
+        );
+    }
+
+    fn guess(&mut self, guess: &str, _: &Config, catalog: &Catalog) {
+        match self.submit(guess) {
+            Ok(Ordering::Equal) => {
+                let args = [
+                    ("attempts", catalog.number(self.attempts())),
+                    ("bound", catalog.number(self.list().optimal_attempts())),
+                ];
+                println!(
+                    "{}",
+                    catalog.plural("words.won", self.attempts().into(), &args)
+                );
+            }
+            Ok(ordering) => println!("{}", word_answer(ordering, catalog)),
+            Err(reason) => println!("{}", invalid_word_text(&reason, catalog)),
+        }
+    }
+
+    fn round_over(&mut self, how: &str, catalog: &Catalog) -> String {
+        let secret = [("word", self.secret().to_string())];
+        catalog.text(&format!("words.{how}"), &secret)
+    }
+}
+
+// The words still possible, as the count and the first and last of them.
+fn remaining_words(game: &WordGame, catalog: &Catalog) -> [(&'static str, String); 3] {
+    let remaining = game.remaining();
+    [
+        ("count", catalog.number(remaining.len() as u64)),
+        ("first", remaining[0].clone()),
+        ("last", remaining[remaining.len() - 1].clone()),
+    ]
+}
+
+// What a miss tells the player, from `guess.cmp(&secret)`.
+fn word_answer(ordering: Ordering, catalog: &Catalog) -> String {
+    let id = match ordering {
+        Ordering::Less => "words.later",
+        Ordering::Greater => "words.earlier",
+        Ordering::Equal => "words.equal",
+    };
+    catalog.text(id, &[])
+}
+
+fn invalid_word_text(reason: &InvalidWord, catalog: &Catalog) -> String {
+    match reason {
+        InvalidWord::Empty => catalog.text("invalid_word.empty", &[]),
+        InvalidWord::NotOneWord => catalog.text("invalid_word.not_one_word", &[]),
+        InvalidWord::Unknown(word) => {
+            catalog.text("invalid_word.unknown", &[("word", word.clone())])
+        }
+        InvalidWord::Finished => catalog.text("invalid.finished", &[]),
+    }
+}
+
+// The prompt of the modes whose guesses aren't numbers in a range: words and real numbers.
+fn ask(attempts_left: Option<u32>, catalog: &Catalog) {
+    match attempts_left {
+        Some(left) => println!(
+            "{}",
+            catalog.plural(
+                "ask.limited",
+                left.into(),
+                &[("left", catalog.number(left))]
+            )
+        ),
+        None => println!("{}", catalog.text("ask.unlimited", &[])),
+    }
+}
+
+fn float(config: &Config) {
+    let rules = config.floats;
+    let max_attempts = explicit_attempts(config);
+    let seed = session_seed(config);
+    let mut game = FloatGame::random(rules, max_attempts, &mut rng::seeded(seed));
+    let catalog = Catalog::new(config.language);
+    // Real numbers are shown the way they're typed, with a decimal point and no grouping, whatever the language: the parser takes nothing else.
+    let secret = [("secret", game.secret().to_string())];
+    let range = [
+        ("min", rules.min().to_string()),
+        ("max", rules.max().to_string()),
+    ];
+
+    let intro = match rules.tolerance() {
+        Tolerance::Absolute(epsilon) => catalog.text(
+            "float.intro.absolute",
+            &[
+                range[0].clone(),
+                range[1].clone(),
+                ("epsilon", epsilon.to_string()),
+            ],
+        ),
+        Tolerance::Relative(fraction) => catalog.text(
+            "float.intro.relative",
+            &[
+                range[0].clone(),
+                range[1].clone(),
+                ("percent", (fraction * 100.0).to_string()),
+            ],
+        ),
+    };
+    println!("{intro}");
+
+    if config.autoplay {
+        if let Err(message) = floats::autoplay(&mut game) {
+            eprintln!("error: the solver failed: {message}");
+            process::exit(1);
+        }
+        for (number, (guess, ordering)) in game.guesses().iter().enumerate() {
+            let args = [
+                ("n", (number + 1).to_string()),
+                ("guess", guess.to_string()),
+                ("answer", float_answer(*ordering, &catalog)),
+            ];
+            println!("{}", catalog.text("step", &args));
+        }
+        // With a low --attempts the round can end before bisection gets there, and then the bound would be beside the point.
+        match game.status() {
+            Status::Won => {
+                let bound = rules.optimal_attempts();
+                let args = [secret[0].clone(), ("bound", catalog.number(bound))];
+                println!("{}", catalog.plural("float.autoplay", bound.into(), &args));
+            }
+            _ => {
+                let attempts = ("attempts", catalog.number(game.attempts()));
+                println!(
+                    "{}",
+                    catalog.plural("ran_out", game.attempts().into(), &[attempts])
+                );
+            }
+        }
+        return;
+    }
+
+    exit_if_cut_short(play_typed(&mut game, config, &catalog));
+}
+
+impl TypedRound for FloatGame {
+    const MODE: &'static str = "float";
+
+    fn is_finished(&self) -> bool {
+        FloatGame::is_finished(self)
+    }
+
+    fn status(&self) -> Status {
+        FloatGame::status(self)
+    }
+
+    fn give_up(&mut self) {
+        FloatGame::give_up(self);
+    }
+
+    fn reveal(&self, catalog: &Catalog) {
+        let (low, high) = self.remaining();
+        let args = [
+            ("secret", self.secret().to_string()),
+            ("low", low.to_string()),
+            ("high", high.to_string()),
+        ];
+        println!("{}", catalog.text("float.debug", &args));
+    }
+
+    fn ask(&self, catalog: &Catalog) {
+        ask(self.attempts_left(), catalog);
+    }
+
+    fn range(&self, _: &Config, catalog: &Catalog) {
+        let (low, high) = self.remaining();
+        let args = [("low", low.to_string()), ("high", high.to_string())];
+        println!("{}", catalog.text("interval", &args));
+    }
+
+    fn guess(&mut self, guess: &str, _: &Config, catalog: &Catalog) {
+        match self.submit(guess) {
+            Ok(Ordering::Equal) => {
+                let args = [
+                    ("secret", self.secret().to_string()),
+                    ("attempts", catalog.number(self.attempts())),
+                    ("bound", catalog.number(self.rules().optimal_attempts())),
+                ];
+                println!(
+                    "{}",
+                    catalog.plural("float.won", self.attempts().into(), &args)
+                );
+            }
+            Ok(ordering) => println!("{}", float_answer(ordering, catalog)),
+            Err(reason) => println!("{}", invalid_float_text(&reason, catalog)),
+        }
+    }
+
+    fn round_over(&mut self, how: &str, catalog: &Catalog) -> String {
+        let secret = [("secret", self.secret().to_string())];
+        catalog.text(&format!("round_over.{how}"), &secret)
+    }
+}
+
+// What a guess tells the player, from how it compares with the secret.
+fn float_answer(ordering: Ordering, catalog: &Catalog) -> String {
+    let id = match ordering {
+        Ordering::Less => "too_small",
+        Ordering::Greater => "too_big",
+        Ordering::Equal => "float.close_enough",
+    };
+    catalog.text(id, &[])
+}
+
+fn invalid_float_text(reason: &InvalidFloat, catalog: &Catalog) -> String {
+    match reason {
+        InvalidFloat::Empty => catalog.text("invalid.empty", &[]),
+        InvalidFloat::NotANumber => catalog.text("invalid.not_a_number", &[]),
+        InvalidFloat::DecimalComma => catalog.text("invalid_float.decimal_comma", &[]),
+        InvalidFloat::NotFinite => catalog.text("invalid_float.not_finite", &[]),
+        InvalidFloat::OutOfRange(rules) => catalog.text(
+            "invalid.out_of_range",
+            &[
+                ("min", rules.min().to_string()),
+                ("max", rules.max().to_string()),
+            ],
+        ),
+        InvalidFloat::Finished => catalog.text("invalid.finished", &[]),
+    }
+}
+
+fn serve(config: &Config) {
+    let host = config.host.as_deref().unwrap_or("127.0.0.1");
+    let port = config.port.unwrap_or(server::DEFAULT_PORT);
+    let seed = session_seed(config);
+
+    let listener = match TcpListener::bind((host, port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("error: can't listen on {host}:{port}: {error}");
+            process::exit(1);
+        }
+    };
+    println!(
+        "Serving guessing_game on {host}:{port}; players can join with `guessing_game join {host}:{port}`."
+    );
+
+    let defaults = ServerOptions::default();
+    let options = ServerOptions {
+        idle_timeout: config.idle_timeout.unwrap_or(defaults.idle_timeout),
+        rounds: config.rounds,
+        ..defaults
+    };
+    if let Err(error) = server::serve(listener, config.rules, rng::seeded(seed), options) {
+        eprintln!("error: the server stopped: {error}");
+        process::exit(1);
+    }
+}
+
+fn http(config: &Config) {
+    let host = config.host.as_deref().unwrap_or("127.0.0.1");
+    let port = config.port.unwrap_or(http::DEFAULT_PORT);
+    let seed = session_seed(config);
+
+    let listener = match TcpListener::bind((host, port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("error: can't listen on {host}:{port}: {error}");
+            process::exit(1);
+        }
+    };
+    println!("Serving the guessing_game API on http://{host}:{port}/games; start a game with `curl -X POST http://{host}:{port}/games`.");
+
+    let defaults = ApiOptions::default();
+    let options = ApiOptions {
+        expiry: config.idle_timeout.unwrap_or(defaults.expiry),
+        ..defaults
+    };
+    if let Err(error) = http::serve(listener, config.rules, rng::seeded(seed), options) {
+        eprintln!("error: the server stopped: {error}");
+        process::exit(1);
+    }
+}
+
+// Sends each line typed on stdin to the server, while a second thread prints what the server says.
+fn join(config: &Config) {
+    let address = config.address.as_deref().unwrap_or_default();
+    let stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        Err(error) => {
+            eprintln!("error: can't connect to {address}: {error}");
+            process::exit(1);
+        }
+    };
+    let mut writer = stream.try_clone().expect("Failed to clone the connection");
+
+    if let Some(name) = &config.player {
+        let _ = writeln!(writer, "NAME {name}");
+    }
+
+    // The reader thread owns the other half of the connection; when the server hangs up there is nothing left to do, so it ends the program.
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines() {
+            match line {
+                Ok(line) => println!("{}", server::describe(&line)),
+                Err(_) => break,
+            }
+        }
+        println!("Disconnected from the server.");
+        process::exit(0);
+    });
+
+    while let Some(line) = read_line() {
+        // Friendlier spellings of the protocol's commands.
+        let message = match line.trim() {
+            input if is_quit(input) => "QUIT".to_string(),
+            "standings" => "STANDINGS".to_string(),
+            input => match input.strip_prefix("name ") {
+                Some(name) => format!("NAME {}", name.trim()),
+                None => input.to_string(),
+            },
+        };
+        if writeln!(writer, "{message}").is_err() {
+            break;
+        }
+    }
+
+    // Tell the server we're leaving, then wait for it to say goodbye and close the connection.
+    let _ = writeln!(writer, "QUIT");
     loop {
-        println!("Guess the number!");
-
-        // we call the rand::thread_rng function that gives us the particular random number generator that we’re going to use: one that is local to the current thread of execution and seeded by the operating system. Then we call the gen_range method on the random number generator. This method is defined by the Rng trait that we brought into scope with the use rand::Rng statement. The gen_range method takes a range expression as an argument and generates a random number in the range. The kind of range expression we’re using here takes the form start..=end and is inclusive on the lower and upper bounds, so we need to specify 1..=100 to request a number between 1 and 100.
-        // Unless otherwise specified, Rust defaults to an i32, which is the type of secret_number unless you add type information elsewhere that would cause Rust to infer a different numerical type.
-        let secret_number = rand::thread_rng().gen_range(1..=100);
-    
-        println!("The secret number is: {secret_number}");
-    
-        println!("Please input your guess.");
-    
-        // String is a string type provided by the standard library that is a growable, UTF-8 encoded bit of text.
-        // The :: syntax in the ::new line indicates that new is an associated function of the String type. An associated function is a function that’s implemented on a type, in this case String. This new function creates a new, empty string. You’ll find a new function on many types, because it’s a common name for a function that makes a new value of some kind.
-        let mut guess = String::new();
-    
-        // The full job of read_line is to take whatever the user types into standard input and append that into a string (without overwriting its contents), so we therefore pass that string as an argument. The string argument needs to be mutable so the method can change the string’s content.
-        // like variables, references are immutable by default. Hence, you need to write &mut guess rather than &guess to make it mutable
-        /*
-        As mentioned earlier, read_line puts whatever the user enters into the string we pass to it, but it also returns a Result value. Result is an enumeration, often called an enum, which is a type that can be in one of multiple possible states. We call each possible state a variant.
-    
-        Chapter 6 will cover enums in more detail. The purpose of these Result types is to encode error-handling information.
-    
-        Result's variants are Ok and Err. The Ok variant indicates the operation was successful, and inside Ok is the successfully generated value. The Err variant means the operation failed, and Err contains information about how or why the operation failed.
-    
-        Values of the Result type, like values of any type, have methods defined on them. An instance of Result has an expect method that you can call. If this instance of Result is an Err value, expect will cause the program to crash and display the message that you passed as an argument to expect. If the read_line method returns an Err, it would likely be the result of an error coming from the underlying operating system. If this instance of Result is an Ok value, expect will take the return value that Ok is holding and return just that value to you so you can use it. In this case, that value is the number of bytes in the user’s input.
-    
-        If you don’t call expect, the program will compile, but you’ll get a warning:
-        */
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-        
-        // We create a variable named guess. But wait, doesn’t the program already have a variable named guess? It does, but helpfully Rust allows us to shadow the previous value of guess with a new one. Shadowing lets us reuse the guess variable name rather than forcing us to create two unique variables, such as guess_str and guess for example. this feature is often used when you want to convert a value from one type to another type.
-        // We need to tell Rust the exact number type we want by using let guess: u32. The colon (:) after guess tells Rust we’ll annotate the variable’s type.
-        // the u32 annotation in this example program and the comparison with secret_number means that Rust will infer that secret_number should be a u32 as well. So now the comparison will be between two values of the same type!
-        // If parse returns an Err Result variant because it couldn’t create a number from the string, the expect call will crash the game and print the message we give it. If parse can successfully convert the string to a number, it will return the Ok variant of Result, and expect will return the number that we want from the Ok value.
-        //let guess: u32 = guess.trim().parse().expect("Please type a number!");
-        let guess: u32 = match guess.trim().parse() {
-            // parse produced the num value and placed it inside Ok
-            Ok(num) => num,
-            // The underscore, _, is a catchall value; in this example, we’re saying we want to match all Err values, no matter what information they have inside them.
-            Err(_) => continue,
-        };
-    
-        // placeholder
-        println!("You guessed: {guess}");
-        // println!("You guessed: {}", guess)
-    
-        //  The cmp method compares two values and can be called on anything that can be compared. It takes a reference to whatever you want to compare with
-        // Then it returns a variant of the Ordering enum we brought into scope with the use statement. We use a match expression to decide what to do next based on which variant of Ordering was returned from the call to cmp with the values in guess and secret_number.
-        // A match expression is made up of arms. An arm consists of a pattern to match against, and the code that should be run if the value given to match fits that arm’s pattern. Rust takes the value given to match and looks through each arm’s pattern in turn. Patterns and the match construct are powerful Rust features that let you express a variety of situations your code might encounter and make sure that you handle them all.
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
+        thread::park();
+    }
+}
+
+fn load_records(store: &Store) -> Loaded {
+    match store.load() {
+        Ok(loaded) => {
+            if loaded.skipped > 0 {
+                eprintln!(
+                    "warning: skipped {} unreadable line(s) in {}",
+                    loaded.skipped,
+                    store.path().display()
+                );
+            }
+            loaded
+        }
+        Err(error) => {
+            eprintln!("error: can't read {}: {error}", store.path().display());
+            process::exit(1);
+        }
+    }
+}
+
+fn tournament(config: &Config) {
+    let games = config.games.unwrap_or(1000);
+    let seed = session_seed(config);
+    let names: Vec<&str> = if config.strategies.is_empty() {
+        strategy::NAMES.to_vec()
+    } else {
+        config.strategies.iter().map(String::as_str).collect()
+    };
+    // The random strategy draws from its own generator, so its choices don't shift the secrets the others face.
+    let mut strategies: Vec<Box<dyn Strategy>> = names
+        .iter()
+        .map(|name| {
+            strategy::by_name(name, rng::seeded(seed.wrapping_add(1)))
+                .expect("names were checked when parsing")
+        })
+        .collect();
+
+    let rules = config.rules;
+    let attempts = match rules.max_attempts {
+        Some(max) => format!("{max} attempts"),
+        None => "unlimited attempts".to_string(),
+    };
+    println!(
+        "{games} game(s) per strategy: {} ({} to {}, {attempts}), seed {seed}",
+        config.difficulty,
+        rules.bounds.min(),
+        rules.bounds.max()
+    );
+    let standings = tournament::run(&mut strategies, rules, games, seed);
+
+    // Attempts are counted over won games only; a lost game has no attempt count that means anything.
+    println!();
+    println!(
+        "{:<10} {:>7} {:>8} {:>7} {:>6} {:>7} {:>6}",
+        "strategy", "won", "mean", "median", "worst", "wasted", "score"
+    );
+    let show = |value: Option<f64>| value.map_or("-".to_string(), |value| format!("{value:.2}"));
+    for standing in &standings {
+        println!(
+            "{:<10} {:>6.1}% {:>8} {:>7} {:>6} {:>7} {:>6}",
+            standing.name,
+            standing.wins() as f64 * 100.0 / standing.games() as f64,
+            show(standing.mean()),
+            show(standing.median()),
+            standing
+                .worst()
+                .map_or("-".to_string(), |worst| worst.to_string()),
+            standing.redundant,
+            standing.total_score / standing.games() as u64
+        );
+    }
+
+    // One column per strategy, one row per attempt count (or range of them, when there are too many to list).
+    println!();
+    print!("{:<9}", "attempts");
+    for standing in &standings {
+        print!(" {:>9}", standing.name);
+    }
+    println!();
+    for range in tournament::buckets(&standings, 20) {
+        let label = if range.start() == range.end() {
+            range.start().to_string()
+        } else {
+            format!("{}-{}", range.start(), range.end())
+        };
+        print!("{label:>9}");
+        for standing in &standings {
+            print!(" {:>9}", standing.wins_within(&range));
+        }
+        println!();
+    }
+    print!("{:>9}", "lost");
+    for standing in &standings {
+        print!(" {:>9}", standing.losses);
+    }
+    println!();
+}
+
+fn show_stats(store: &Store, only_player: Option<&str>) {
+    let loaded = load_records(store);
+    let stats = records::player_stats(&loaded.records);
+    if stats.is_empty() && loaded.word_records.is_empty() {
+        println!("No rounds recorded yet in {}.", store.path().display());
+        return;
+    }
+
+    for (player, stats) in &stats {
+        if only_player.is_some_and(|only| only != player) {
+            continue;
+        }
+        println!(
+            "{player}: {} round(s), {} won ({:.0}%), best score {}",
+            stats.rounds,
+            stats.wins,
+            stats.win_rate() * 100.0,
+            stats.best_score
+        );
+        // One row per attempt count, with a bar as long as the number of wins that took that many attempts.
+        for (attempts, wins) in &stats.histogram {
+            println!(
+                "  {attempts:>3} attempt(s) | {} {wins}",
+                "#".repeat(*wins as usize)
+            );
+        }
+    }
+
+    let word_records: Vec<WordRecord> = loaded
+        .word_records
+        .into_iter()
+        .filter(|record| only_player.is_none_or(|only| only == record.player))
+        .collect();
+    let lists = records::list_stats(&word_records);
+    if !lists.is_empty() {
+        println!("Word lists:");
+        for (list, stats) in &lists {
+            println!("  {}", list_summary(list, stats, &Catalog::default()));
+        }
+    }
+}
+
+fn list_summary(list: &str, stats: &records::ListStats, catalog: &Catalog) -> String {
+    let rounds = stats.rounds.into();
+    let mut summary = catalog.plural(
+        "words.summary",
+        rounds,
+        &[
+            ("list", list.to_string()),
+            ("words", catalog.number(stats.words as u64)),
+            ("rounds", catalog.number(stats.rounds)),
+            ("wins", catalog.number(stats.wins)),
+            ("rate", catalog.decimal(stats.win_rate() * 100.0, 0)),
+        ],
+    );
+    if let (Some(average), Some(fewest)) = (stats.average_attempts(), stats.fewest_attempts) {
+        summary.push_str(&catalog.text(
+            "words.summary.average",
+            &[
+                ("average", catalog.decimal(average, 1)),
+                ("fewest", catalog.number(fewest)),
+            ],
+        ));
+    }
+    summary
+}
+
+fn show_leaderboard(store: &Store) {
+    let records = load_records(store).records;
+    for difficulty in Difficulty::ALL {
+        let best = records::leaderboard(&records, difficulty, 10);
+        if best.is_empty() {
+            continue;
+        }
+        println!("{difficulty}:");
+        for (place, record) in best.iter().enumerate() {
+            println!(
+                "  {:>2}. {:<16} {:>4} points in {} attempt(s) ({} to {}, {:.1}s)",
+                place + 1,
+                record.player,
+                record.score,
+                record.attempts,
+                record.min,
+                record.max,
+                record.duration.as_secs_f64()
+            );
+        }
+    }
+}
+
+// How a single round stopped.
+#[derive(PartialEq, Eq)]
+enum RoundEnd {
+    Finished,
+    /// The player asked for a new round with :new, so there's no need to ask whether to play again.
+    New,
+    Quit,
+    EndOfInput,
+}
+
+// Keeps asking for guesses against the same secret until the round is over or the player leaves. The caller announces how the round ended.
+fn play_round(session: &mut Session, config: &Config, console: &mut Console) -> RoundEnd {
+    // Hot/cold hints compare each guess with the one before, so they start afresh every round.
+    let mut hot_cold = match config.feedback {
+        Feedback::Direction => None,
+        Feedback::HotCold(thresholds) => Some(HotCold::new(thresholds)),
+    };
+
+    while !session.game().is_finished() {
+        let game = session.game();
+        if config.reveal {
+            let remaining = game.remaining();
+            console.emit(Message::Debug {
+                secret: game.secret(),
+                attempts: game.attempts(),
+                low: *remaining.start(),
+                high: *remaining.end(),
+            });
+        }
+        console.emit(Message::AskGuess {
+            bounds: game.bounds(),
+            attempts_left: game.attempts_left(),
+        });
+
+        // read_input returns None once stdin is closed (end of a piped file, or Ctrl-D at a terminal).
+        let guess = match console.read_input() {
+            Some(Input::Guess(guess)) => guess,
+            Some(Input::GiveUp) => {
+                session.give_up();
                 break;
+            }
+            Some(Input::Quit) => {
+                session.give_up();
+                return RoundEnd::Quit;
+            }
+            Some(Input::NewRound) => {
+                session.give_up();
+                return RoundEnd::New;
+            }
+            // Hot/cold hints never say which way the secret lies, so there's no interval the player could know, or narrow down, or go back to;
+            // and the history's too small and too big would give away the very direction the hints keep back.
+            Some(Input::Range | Input::History(_) | Input::Hint | Input::Undo)
+                if hot_cold.is_some() =>
+            {
+                console.emit(Message::Unavailable("hot_cold"));
+                continue;
+            }
+            Some(Input::Range) => {
+                let remaining = game.remaining();
+                console.emit(Message::Interval {
+                    low: *remaining.start(),
+                    high: *remaining.end(),
+                });
+                continue;
+            }
+            Some(Input::History(count)) => {
+                let mut history = game.history();
+                if let Some(count) = count {
+                    history.drain(..history.len().saturating_sub(count as usize));
+                }
+                console.emit(Message::History(history));
+                continue;
+            }
+            Some(Input::Hint) => {
+                let message = match session.game_mut().hint() {
+                    Some(remaining) => Message::Hinted {
+                        low: *remaining.start(),
+                        high: *remaining.end(),
+                        cost: difficulty::HINT_COST,
+                    },
+                    None => Message::Unavailable("nothing_to_hint"),
+                };
+                console.emit(message);
+                continue;
+            }
+            Some(Input::Undo) => {
+                let game = session.game_mut();
+                let message = match game.undo() {
+                    Ok(guess) => Message::Undone {
+                        guess,
+                        attempts: game.attempts(),
+                        undos_left: game.undos_left(),
+                    },
+                    Err(error) => Message::Unavailable(protocol::undo_code(error)),
+                };
+                console.emit(message);
+                continue;
+            }
+            Some(Input::Help) => {
+                console.emit(Message::Help {
+                    hint_cost: difficulty::HINT_COST,
+                    undos_left: game.undos_left(),
+                });
+                continue;
+            }
+            Some(Input::BadCommand(error)) => {
+                console.emit(Message::BadCommand(error));
+                continue;
+            }
+            Some(Input::PlayAgain(_)) => {
+                console.emit(Message::BadInput {
+                    code: "unexpected",
+                    message: "This round isn't over yet; send a guess, give_up or quit."
+                        .to_string(),
+                });
+                continue;
+            }
+            None => {
+                session.give_up();
+                return RoundEnd::EndOfInput;
+            }
+        };
+
+        // A match expression is made up of arms. An arm consists of a pattern to match against, and the code that should be run if the value given to match fits that arm’s pattern.
+        let outcome = session.submit(&guess);
+        let game = session.game();
+        let message = match (outcome, game.parse(&guess)) {
+            (Outcome::Invalid(reason), _) | (_, Err(reason)) => Message::Rejected(reason),
+            (outcome, Ok(guess)) => Message::Answer {
+                guess,
+                outcome,
+                attempts: game.attempts(),
+                score: game.score(),
+                hint: match outcome {
+                    Outcome::TooSmall | Outcome::TooBig => {
+                        hot_cold.as_mut().map(|hot_cold| hot_cold.hint(game, guess))
+                    }
+                    _ => None,
+                },
             },
+        };
+        console.emit(message);
+        if let (Redundancy::Warn, Some(redundant)) = (config.redundancy, game.last_redundant()) {
+            console.emit(Message::Warning(redundant));
+        }
+    }
+    RoundEnd::Finished
+}
+
+fn is_quit(input: &str) -> bool {
+    matches!(input.trim().to_lowercase().as_str(), "quit" | "q")
+}
+
+// Returns None at the end of input. A read error (for example input that isn't valid UTF-8) is reported and treated the same way, since there is nothing sensible left to read.
+fn read_line() -> Option<String> {
+    // String is a string type provided by the standard library that is a growable, UTF-8 encoded bit of text.
+    // The :: syntax in the ::new line indicates that new is an associated function of the String type. An associated function is a function that’s implemented on a type, in this case String. This new function creates a new, empty string. You’ll find a new function on many types, because it’s a common name for a function that makes a new value of some kind.
+    let mut line = String::new();
+
+    // The full job of read_line is to take whatever the user types into standard input and append that into a string (without overwriting its contents), so we therefore pass that string as an argument. The string argument needs to be mutable so the method can change the string’s content.
+    // like variables, references are immutable by default. Hence, you need to write &mut line rather than &line to make it mutable
+    /*
+    As mentioned earlier, read_line puts whatever the user enters into the string we pass to it, but it also returns a Result value. Result is an enumeration, often called an enum, which is a type that can be in one of multiple possible states. We call each possible state a variant.
+
+    The Ok variant holds the number of bytes read, which is 0 only when the end of input has been reached; an empty line still contains its newline.
+    */
+    match io::stdin().read_line(&mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(line),
+        Err(error) => {
+            eprintln!("Failed to read line: {error}");
+            None
         }
     }
 }
@@ -0,0 +1,904 @@
+// Everything the play loop says and hears, in one place, so the same session can be shown as English text or as line-delimited JSON for other programs.
+//
+// The JSON lines protocol (`--protocol jsonl`), version 4
+//
+// Every line the game writes to stdout is one JSON object with a "type":
+//   {"type":"hello","protocol":"guessing_game","version":4,"seed":42,"difficulty":"normal","min":1,"max":100,"max_attempts":10}
+//   {"type":"round","round":1,"min":1,"max":100,"max_attempts":10}    max_attempts is null when unlimited
+//   {"type":"debug","secret":70,"attempts":0,"low":1,"high":100}      only with --reveal
+//   {"type":"prompt","expect":"guess","attempts_left":10}             attempts_left is null when unlimited
+//   {"type":"prompt","expect":"play_again"}
+//   {"type":"outcome","guess":50,"result":"too_small","attempts":1}   result is too_small, too_big or win
+//   {"type":"outcome","guess":50,"result":"miss","proximity":"warm","trend":"colder","attempts":2}
+//                                                                     with --hints hot-cold; proximity is burning, warm or cold,
+//                                                                     trend is warmer, colder, same, or null for the first guess
+//   {"type":"warning","code":"ruled_out","guess":80,"low":1,"high":69}   after an outcome, when earlier answers had already ruled the guess out;
+//                                                                     code is repeated when it had been guessed before
+//   {"type":"interval","low":1,"high":69}                             the numbers still possible, in answer to a range request
+//   {"type":"history","guesses":[{"guess":50,"result":"too_small"}]}  the round's guesses so far, oldest first
+//   {"type":"hint","low":51,"high":75,"cost":100}                     the numbers still possible after a hint, and what it took off the score
+//   {"type":"undone","guess":80,"attempts":2,"undos_left":0}          the guess taken back, and the attempts it leaves
+//   {"type":"help","commands":["history","range","hint","undo","giveup","new","help","quit"],"hint_cost":100,"undos_left":1}
+//   {"type":"error","code":"not_a_number","message":"That is not a number."}
+//   {"type":"round_over","round":1,"status":"won","secret":70,"attempts":7,"score":1000}   status is won, lost or gave_up
+//   {"type":"summary","end":"done","rounds_played":1,"rounds_won":1,"average_attempts":7,"total_score":1000,"best_score":1000,"redundant_guesses":0}
+//                                                                     end is done, quit or end_of_input
+//
+// Error codes: empty, not_a_number, negative, out_of_range, overflow, finished, and with --redundant free, repeated and ruled_out (the game rejected the guess),
+// bad_json, unknown_type, bad_value, unexpected (the line itself was wrong, or came at the wrong time), unknown_command, ambiguous_command, bad_argument (a typed
+// :command couldn't be read), and hot_cold, nothing_to_hint, nothing_to_undo, no_undos_left, not_in_round (a command that can't be carried out right now).
+//
+// Every line read from stdin is one JSON object with a "type":
+//   {"type":"guess","value":50}            value may also be a string, which is parsed like typed input
+//   {"type":"give_up"}
+//   {"type":"range"}                       asks for the numbers still possible
+//   {"type":"history","count":3}           count is optional; without it, every guess this round
+//   {"type":"hint"}                        costs HINT_COST points off the round's score
+//   {"type":"undo"}
+//   {"type":"new"}                         gives up the round and starts the next one without asking
+//   {"type":"help"}
+//   {"type":"quit"}
+//   {"type":"play_again","value":true}
+//
+// Numbers are written as plain JSON numbers even beyond 2^53; readers that need the full i128 range should not parse them as doubles.
+
+use std::str::FromStr;
+
+use crate::bounds::Bounds;
+use crate::cli;
+use crate::commands::{self, Command, CommandError};
+use crate::difficulty::{Difficulty, Rules};
+use crate::game::{InvalidGuess, Outcome, Redundant, Status, UndoError};
+use crate::hints::Hint;
+use crate::json::{self, Value};
+use crate::locale::{Catalog, Language};
+use crate::session::Tally;
+
+/// The version reported in the hello message. Bumped whenever a message changes shape.
+///
+/// 2 added the "miss" result of hot/cold outcomes, with its proximity and trend;
+/// 3 added the warning and interval messages, and the summary's redundant_guesses;
+/// 4 added the history, hint, undone and help messages, and the inputs that ask for them.
+pub const PROTOCOL_VERSION: u32 = 4;
+
+/// How the play loop talks to the outside world.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Protocol {
+    /// Sentences for a person at a terminal, in the language chosen with --lang or the locale.
+    #[default]
+    Text,
+    /// One JSON object per line, for programs.
+    Jsonl,
+}
+
+impl Protocol {
+    pub fn name(self) -> &'static str {
+        match self {
+            Protocol::Text => "text",
+            Protocol::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Protocol, String> {
+        match name.trim() {
+            "text" => Ok(Protocol::Text),
+            "jsonl" => Ok(Protocol::Jsonl),
+            _ => Err(format!(
+                "unknown protocol '{name}' (expected text or jsonl)"
+            )),
+        }
+    }
+}
+
+/// How the whole session stopped; each way has its own exit status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEnd {
+    Done,
+    Quit,
+    EndOfInput,
+}
+
+impl SessionEnd {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            SessionEnd::Done => 0,
+            SessionEnd::Quit => cli::EXIT_QUIT,
+            SessionEnd::EndOfInput => cli::EXIT_END_OF_INPUT,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            SessionEnd::Done => "done",
+            SessionEnd::Quit => "quit",
+            SessionEnd::EndOfInput => "end_of_input",
+        }
+    }
+}
+
+/// Something the play loop tells the player.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// The start of a session. `seed_was_random` says whether the player needs to be told the seed to replay it.
+    Hello {
+        seed: u64,
+        seed_was_random: bool,
+        difficulty: Difficulty,
+        rules: Rules,
+    },
+    RoundStarted {
+        round: u32,
+        rules: Rules,
+    },
+    /// The game's internal state, shown with --reveal.
+    Debug {
+        secret: i128,
+        attempts: u32,
+        low: i128,
+        high: i128,
+    },
+    AskGuess {
+        bounds: Bounds,
+        attempts_left: Option<u32>,
+    },
+    AskPlayAgain,
+    /// The answer to a valid guess. In hot/cold mode a miss carries a hint, which is shown instead of its direction.
+    Answer {
+        guess: i128,
+        outcome: Outcome,
+        attempts: u32,
+        score: u32,
+        hint: Option<Hint>,
+    },
+    Rejected(InvalidGuess),
+    /// A guess that was answered and counted even though earlier answers had already ruled it out.
+    Warning(Redundant),
+    /// The numbers still consistent with every answer so far.
+    Interval {
+        low: i128,
+        high: i128,
+    },
+    /// The guesses so far this round, oldest first, with their answers.
+    History(Vec<(i128, Outcome)>),
+    /// The numbers still possible after a hint, and the points it cost.
+    Hinted {
+        low: i128,
+        high: i128,
+        cost: u32,
+    },
+    Undone {
+        guess: i128,
+        attempts: u32,
+        undos_left: u32,
+    },
+    Help {
+        hint_cost: u32,
+        undos_left: u32,
+    },
+    BadCommand(CommandError),
+    /// A command that makes sense, but can't be carried out right now; the code says why.
+    Unavailable(&'static str),
+    /// A line that couldn't be understood at all, such as malformed JSON.
+    BadInput {
+        code: &'static str,
+        message: String,
+    },
+    RoundOver {
+        round: u32,
+        status: Status,
+        secret: i128,
+        attempts: u32,
+        score: u32,
+        /// The round was given up because stdin closed.
+        input_ended: bool,
+    },
+    Summary {
+        end: SessionEnd,
+        tally: Tally,
+    },
+}
+
+impl Message {
+    /// The message as text in the catalog's language, or `None` for messages a person doesn't need to see.
+    pub fn text(&self, catalog: &Catalog) -> Option<String> {
+        let number = |n: i128| catalog.number(n);
+        let text = match self {
+            Message::Hello {
+                seed,
+                seed_was_random,
+                difficulty,
+                rules,
+            } => {
+                let mut text = String::new();
+                if *seed_was_random {
+                    // The seed isn't grouped into thousands, so it can be copied straight into --seed.
+                    text.push_str(&catalog.text("seed", &[("seed", seed.to_string())]));
+                    text.push('\n');
+                }
+                text.push_str(&catalog.text("title", &[]));
+                text.push('\n');
+                let difficulty = catalog.text(&format!("difficulty.{}", difficulty.name()), &[]);
+                match rules.max_attempts {
+                    Some(max) => text.push_str(&catalog.plural(
+                        "difficulty.limited",
+                        max.into(),
+                        &[("difficulty", difficulty), ("max", number(max.into()))],
+                    )),
+                    None => text.push_str(
+                        &catalog.text("difficulty.unlimited", &[("difficulty", difficulty)]),
+                    ),
+                }
+                text
+            }
+            Message::RoundStarted { .. } => return None,
+            Message::Debug {
+                secret,
+                attempts,
+                low,
+                high,
+            } => catalog.text(
+                "debug",
+                &[
+                    ("secret", number(*secret)),
+                    ("attempts", number((*attempts).into())),
+                    ("low", number(*low)),
+                    ("high", number(*high)),
+                ],
+            ),
+            Message::AskGuess {
+                bounds,
+                attempts_left,
+            } => {
+                let mut args = vec![("min", number(bounds.min())), ("max", number(bounds.max()))];
+                match attempts_left {
+                    Some(left) => {
+                        args.push(("left", number((*left).into())));
+                        catalog.plural("ask_guess.limited", (*left).into(), &args)
+                    }
+                    None => catalog.text("ask_guess.unlimited", &args),
+                }
+            }
+            Message::AskPlayAgain => catalog.text("play_again", &[]),
+            Message::Answer {
+                outcome: Outcome::TooSmall | Outcome::TooBig,
+                hint: Some(hint),
+                ..
+            } => hint_text(hint, catalog),
+            Message::Answer {
+                outcome,
+                attempts,
+                score,
+                ..
+            } => match outcome {
+                Outcome::TooSmall => catalog.text("too_small", &[]),
+                Outcome::TooBig => catalog.text("too_big", &[]),
+                Outcome::Win => catalog.plural(
+                    "win",
+                    (*attempts).into(),
+                    &[
+                        ("attempts", number((*attempts).into())),
+                        ("score", number((*score).into())),
+                    ],
+                ),
+                Outcome::Invalid(reason) => invalid_text(reason, catalog),
+            },
+            Message::Rejected(reason) => invalid_text(reason, catalog),
+            Message::Warning(redundant) => {
+                let id = if redundant.repeated {
+                    "warning.repeated"
+                } else {
+                    "warning.ruled_out"
+                };
+                catalog.text(id, &redundant_args(redundant, catalog))
+            }
+            Message::Interval { low, high } => catalog.text(
+                "interval",
+                &[("low", number(*low)), ("high", number(*high))],
+            ),
+            Message::History(entries) if entries.is_empty() => catalog.text("history.empty", &[]),
+            Message::History(entries) => {
+                let lines: Vec<String> = entries
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (guess, outcome))| {
+                        catalog.text(
+                            &format!("history.{}", outcome_name(*outcome)),
+                            &[("n", number(index as i128 + 1)), ("guess", number(*guess))],
+                        )
+                    })
+                    .collect();
+                lines.join("\n")
+            }
+            Message::Hinted { low, high, cost } => catalog.text(
+                "hinted",
+                &[
+                    ("low", number(*low)),
+                    ("high", number(*high)),
+                    ("cost", number((*cost).into())),
+                ],
+            ),
+            Message::Undone {
+                guess, undos_left, ..
+            } => catalog.plural(
+                "undone",
+                (*undos_left).into(),
+                &[
+                    ("guess", number(*guess)),
+                    ("left", number((*undos_left).into())),
+                ],
+            ),
+            Message::Help {
+                hint_cost,
+                undos_left,
+            } => catalog.text(
+                "help",
+                &[
+                    ("cost", number((*hint_cost).into())),
+                    ("left", number((*undos_left).into())),
+                ],
+            ),
+            Message::BadCommand(error) => command_error_text(error, catalog),
+            Message::Unavailable(code) => catalog.text(&format!("unavailable.{code}"), &[]),
+            Message::BadInput { message, .. } => message.clone(),
+            Message::RoundOver {
+                status,
+                secret,
+                input_ended,
+                ..
+            } => {
+                let secret = [("secret", number(*secret))];
+                match status {
+                    // The blank line finishes off the prompt the player never answered.
+                    Status::GaveUp if *input_ended => {
+                        format!("\n{}", catalog.text("round_over.input_ended", &secret))
+                    }
+                    Status::GaveUp => catalog.text("round_over.gave_up", &secret),
+                    Status::Lost => catalog.text("round_over.lost", &secret),
+                    Status::Playing | Status::Won => return None,
+                }
+            }
+            Message::Summary { tally, .. } => {
+                let mut text = catalog.plural(
+                    "summary.played",
+                    tally.rounds_played.into(),
+                    &[
+                        ("played", number(tally.rounds_played.into())),
+                        ("won", number(tally.rounds_won.into())),
+                    ],
+                );
+                if let Some(average) = tally.average_attempts() {
+                    text.push('\n');
+                    text.push_str(&catalog.text(
+                        "summary.average",
+                        &[("average", catalog.decimal(average, 1))],
+                    ));
+                }
+                text.push('\n');
+                text.push_str(&catalog.text(
+                    "summary.score",
+                    &[
+                        ("total", number(tally.total_score.into())),
+                        ("best", number(tally.best_score.into())),
+                    ],
+                ));
+                if tally.redundant_guesses > 0 {
+                    text.push('\n');
+                    text.push_str(&catalog.plural(
+                        "summary.redundant",
+                        tally.redundant_guesses.into(),
+                        &[("count", number(tally.redundant_guesses.into()))],
+                    ));
+                }
+                text
+            }
+        };
+        Some(text)
+    }
+
+    /// The message as one object of the JSON lines protocol.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Message::Hello {
+                seed,
+                difficulty,
+                rules,
+                ..
+            } => Value::object()
+                .with("type", "hello")
+                .with("protocol", "guessing_game")
+                .with("version", PROTOCOL_VERSION)
+                .with("seed", *seed)
+                .with("difficulty", difficulty.name())
+                .with("min", rules.bounds.min())
+                .with("max", rules.bounds.max())
+                .with("max_attempts", rules.max_attempts),
+            Message::RoundStarted { round, rules } => Value::object()
+                .with("type", "round")
+                .with("round", *round)
+                .with("min", rules.bounds.min())
+                .with("max", rules.bounds.max())
+                .with("max_attempts", rules.max_attempts),
+            Message::Debug {
+                secret,
+                attempts,
+                low,
+                high,
+            } => Value::object()
+                .with("type", "debug")
+                .with("secret", *secret)
+                .with("attempts", *attempts)
+                .with("low", *low)
+                .with("high", *high),
+            Message::AskGuess { attempts_left, .. } => Value::object()
+                .with("type", "prompt")
+                .with("expect", "guess")
+                .with("attempts_left", *attempts_left),
+            Message::AskPlayAgain => Value::object()
+                .with("type", "prompt")
+                .with("expect", "play_again"),
+            Message::Answer {
+                outcome: Outcome::Invalid(reason),
+                ..
+            }
+            | Message::Rejected(reason) => error_json(invalid_code(reason), &reason.to_string()),
+            Message::Answer {
+                guess,
+                outcome: Outcome::TooSmall | Outcome::TooBig,
+                attempts,
+                hint: Some(hint),
+                ..
+            } => Value::object()
+                .with("type", "outcome")
+                .with("guess", *guess)
+                .with("result", "miss")
+                .with("proximity", hint.proximity.name())
+                .with("trend", hint.trend.map(|trend| trend.name()))
+                .with("attempts", *attempts),
+            Message::Answer {
+                guess,
+                outcome,
+                attempts,
+                ..
+            } => Value::object()
+                .with("type", "outcome")
+                .with("guess", *guess)
+                .with("result", outcome_name(*outcome))
+                .with("attempts", *attempts),
+            Message::Warning(redundant) => Value::object()
+                .with("type", "warning")
+                .with("code", redundant_code(redundant))
+                .with("guess", redundant.guess)
+                .with("low", redundant.low)
+                .with("high", redundant.high),
+            Message::Interval { low, high } => Value::object()
+                .with("type", "interval")
+                .with("low", *low)
+                .with("high", *high),
+            Message::History(entries) => Value::object().with("type", "history").with(
+                "guesses",
+                entries
+                    .iter()
+                    .map(|(guess, outcome)| {
+                        Value::object()
+                            .with("guess", *guess)
+                            .with("result", outcome_name(*outcome))
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Message::Hinted { low, high, cost } => Value::object()
+                .with("type", "hint")
+                .with("low", *low)
+                .with("high", *high)
+                .with("cost", *cost),
+            Message::Undone {
+                guess,
+                attempts,
+                undos_left,
+            } => Value::object()
+                .with("type", "undone")
+                .with("guess", *guess)
+                .with("attempts", *attempts)
+                .with("undos_left", *undos_left),
+            Message::Help {
+                hint_cost,
+                undos_left,
+            } => Value::object()
+                .with("type", "help")
+                .with("commands", commands::NAMES.to_vec())
+                .with("hint_cost", *hint_cost)
+                .with("undos_left", *undos_left),
+            Message::BadCommand(error) => error_json(error.code(), &error.to_string()),
+            // Like invalid guesses, errors in JSON carry the English wording.
+            Message::Unavailable(code) => error_json(
+                code,
+                &Catalog::new(Language::English).text(&format!("unavailable.{code}"), &[]),
+            ),
+            Message::BadInput { code, message } => error_json(code, message),
+            Message::RoundOver {
+                round,
+                status,
+                secret,
+                attempts,
+                score,
+                ..
+            } => Value::object()
+                .with("type", "round_over")
+                .with("round", *round)
+                .with("status", status_name(*status))
+                .with("secret", *secret)
+                .with("attempts", *attempts)
+                .with("score", *score),
+            Message::Summary { end, tally } => Value::object()
+                .with("type", "summary")
+                .with("end", end.name())
+                .with("rounds_played", tally.rounds_played)
+                .with("rounds_won", tally.rounds_won)
+                .with("average_attempts", tally.average_attempts())
+                .with("total_score", tally.total_score)
+                .with("best_score", tally.best_score)
+                .with("redundant_guesses", tally.redundant_guesses),
+        }
+    }
+
+    /// The message the way `protocol` shows it, or `None` if it shows nothing.
+    pub fn render(&self, protocol: Protocol, catalog: &Catalog) -> Option<String> {
+        match protocol {
+            Protocol::Text => self.text(catalog),
+            Protocol::Jsonl => Some(self.to_json().to_string()),
+        }
+    }
+}
+
+// An invalid guess in the catalog's language; the JSON protocol keeps the English from InvalidGuess's Display.
+fn invalid_text(reason: &InvalidGuess, catalog: &Catalog) -> String {
+    let id = format!("invalid.{}", invalid_code(reason));
+    match reason {
+        InvalidGuess::Negative(bounds)
+        | InvalidGuess::OutOfRange(bounds)
+        | InvalidGuess::Overflow(bounds) => catalog.text(
+            &id,
+            &[
+                ("min", catalog.number(bounds.min())),
+                ("max", catalog.number(bounds.max())),
+            ],
+        ),
+        InvalidGuess::Empty | InvalidGuess::NotANumber | InvalidGuess::Finished => {
+            catalog.text(&id, &[])
+        }
+        InvalidGuess::Redundant(redundant) => {
+            catalog.text(&id, &redundant_args(redundant, catalog))
+        }
+    }
+}
+
+fn redundant_args(redundant: &Redundant, catalog: &Catalog) -> [(&'static str, String); 3] {
+    [
+        ("guess", catalog.number(redundant.guess)),
+        ("low", catalog.number(redundant.low)),
+        ("high", catalog.number(redundant.high)),
+    ]
+}
+
+fn redundant_code(redundant: &Redundant) -> &'static str {
+    if redundant.repeated {
+        "repeated"
+    } else {
+        "ruled_out"
+    }
+}
+
+fn hint_text(hint: &Hint, catalog: &Catalog) -> String {
+    let mut text = catalog.text(&format!("hint.{}", hint.proximity.name()), &[]);
+    if let Some(trend) = hint.trend {
+        text.push(' ');
+        text.push_str(&catalog.text(&format!("trend.{}", trend.name()), &[]));
+    }
+    text
+}
+
+fn command_error_text(error: &CommandError, catalog: &Catalog) -> String {
+    match error {
+        CommandError::Unknown(name) => catalog.text("command.unknown", &[("name", name.clone())]),
+        CommandError::Ambiguous { prefix, matches } => {
+            let matches: Vec<String> = matches.iter().map(|name| format!(":{name}")).collect();
+            catalog.text(
+                "command.ambiguous",
+                &[("prefix", prefix.clone()), ("matches", matches.join(", "))],
+            )
+        }
+        CommandError::UnexpectedArgument { command, argument } => catalog.text(
+            "command.unexpected_argument",
+            &[
+                ("command", command.to_string()),
+                ("argument", argument.clone()),
+            ],
+        ),
+        CommandError::BadCount { command, argument } => catalog.text(
+            "command.bad_count",
+            &[
+                ("command", command.to_string()),
+                ("argument", argument.clone()),
+            ],
+        ),
+    }
+}
+
+/// The `Message::Unavailable` code for an undo the game refused.
+pub fn undo_code(error: UndoError) -> &'static str {
+    match error {
+        UndoError::Finished => "not_in_round",
+        UndoError::NothingToUndo => "nothing_to_undo",
+        UndoError::NoneLeft => "no_undos_left",
+    }
+}
+
+fn error_json(code: &str, message: &str) -> Value {
+    Value::object()
+        .with("type", "error")
+        .with("code", code)
+        .with("message", message)
+}
+
+/// The error code the JSON protocols use for a rejected guess.
+pub fn invalid_code(reason: &InvalidGuess) -> &'static str {
+    match reason {
+        InvalidGuess::Empty => "empty",
+        InvalidGuess::NotANumber => "not_a_number",
+        InvalidGuess::Negative(_) => "negative",
+        InvalidGuess::OutOfRange(_) => "out_of_range",
+        InvalidGuess::Overflow(_) => "overflow",
+        InvalidGuess::Finished => "finished",
+        InvalidGuess::Redundant(redundant) => redundant_code(redundant),
+    }
+}
+
+/// How the JSON protocols name an outcome.
+pub fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooSmall => "too_small",
+        Outcome::TooBig => "too_big",
+        Outcome::Win => "win",
+        Outcome::Invalid(_) => "invalid",
+    }
+}
+
+/// How the JSON protocols name a round's status.
+pub fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Playing => "playing",
+        Status::Won => "won",
+        Status::Lost => "lost",
+        Status::GaveUp => "gave_up",
+    }
+}
+
+/// Something the player told the play loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// A guess, still as text; the game parses it so it can explain what's wrong with it.
+    Guess(String),
+    GiveUp,
+    /// Show the numbers still possible.
+    Range,
+    /// Show the guesses so far this round, or only the last this many.
+    History(Option<u32>),
+    Hint,
+    Undo,
+    /// Give up the round and start the next one.
+    NewRound,
+    Help,
+    /// A line starting with a colon that isn't a command.
+    BadCommand(CommandError),
+    Quit,
+    PlayAgain(bool),
+}
+
+impl Input {
+    /// Reads one line typed by a person. Anything that isn't a command is a guess; the play-again prompt decides for itself what counts as yes.
+    /// Commands start with a colon (see `commands`), though quit, give up and range also work without one.
+    pub fn from_text(line: &str) -> Input {
+        if let Some(command) = commands::parse(line) {
+            return match command {
+                Ok(Command::History(count)) => Input::History(count),
+                Ok(Command::Range) => Input::Range,
+                Ok(Command::Hint) => Input::Hint,
+                Ok(Command::Undo) => Input::Undo,
+                Ok(Command::GiveUp) => Input::GiveUp,
+                Ok(Command::New) => Input::NewRound,
+                Ok(Command::Help) => Input::Help,
+                Ok(Command::Quit) => Input::Quit,
+                Err(error) => Input::BadCommand(error),
+            };
+        }
+        match line.trim().to_lowercase().as_str() {
+            "quit" | "q" => Input::Quit,
+            "give up" | "giveup" => Input::GiveUp,
+            "range" => Input::Range,
+            _ => Input::Guess(line.to_string()),
+        }
+    }
+
+    /// Reads one line of the JSON lines protocol. The error is the message to send back.
+    pub fn from_json(line: &str) -> Result<Input, Message> {
+        let bad = |code, message: String| Message::BadInput { code, message };
+
+        let value = json::parse(line)
+            .map_err(|error| bad("bad_json", format!("not valid JSON: {error}")))?;
+        let kind = value.get("type").and_then(Value::as_str).ok_or_else(|| {
+            bad(
+                "bad_value",
+                "every message needs a string \"type\"".to_string(),
+            )
+        })?;
+
+        match kind {
+            "guess" => match value.get("value") {
+                Some(Value::Number(digits)) => Ok(Input::Guess(digits.clone())),
+                Some(Value::String(text)) => Ok(Input::Guess(text.clone())),
+                _ => Err(bad(
+                    "bad_value",
+                    "a guess needs a number \"value\"".to_string(),
+                )),
+            },
+            "give_up" => Ok(Input::GiveUp),
+            "range" => Ok(Input::Range),
+            "history" => match value.get("count") {
+                None | Some(Value::Null) => Ok(Input::History(None)),
+                Some(count) => match count
+                    .as_number()
+                    .and_then(|digits| digits.parse::<u32>().ok())
+                {
+                    Some(count) if count > 0 => Ok(Input::History(Some(count))),
+                    _ => Err(bad(
+                        "bad_value",
+                        "a history \"count\" must be a whole number of at least 1".to_string(),
+                    )),
+                },
+            },
+            "hint" => Ok(Input::Hint),
+            "undo" => Ok(Input::Undo),
+            "new" => Ok(Input::NewRound),
+            "help" => Ok(Input::Help),
+            "quit" => Ok(Input::Quit),
+            "play_again" => value
+                .get("value")
+                .and_then(Value::as_bool)
+                .map(Input::PlayAgain)
+                .ok_or_else(|| {
+                    bad(
+                        "bad_value",
+                        "play_again needs a boolean \"value\"".to_string(),
+                    )
+                }),
+            other => Err(bad(
+                "unknown_type",
+                format!("unknown message type '{other}'"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json(message: Message) -> String {
+        message.to_json().to_string()
+    }
+
+    #[test]
+    fn messages_have_the_documented_shape() {
+        let rules = Rules {
+            bounds: Bounds::DEFAULT,
+            max_attempts: Some(10),
+        };
+        assert_eq!(
+            json(Message::Hello {
+                seed: 42,
+                seed_was_random: true,
+                difficulty: Difficulty::Normal,
+                rules,
+            }),
+            format!(
+                r#"{{"type":"hello","protocol":"guessing_game","version":{PROTOCOL_VERSION},"seed":42,"difficulty":"normal","min":1,"max":100,"max_attempts":10}}"#
+            )
+        );
+        assert_eq!(
+            json(Message::AskGuess {
+                bounds: Bounds::DEFAULT,
+                attempts_left: None,
+            }),
+            r#"{"type":"prompt","expect":"guess","attempts_left":null}"#
+        );
+        assert_eq!(
+            json(Message::Answer {
+                guess: 50,
+                outcome: Outcome::TooSmall,
+                attempts: 1,
+                score: 0,
+                hint: None,
+            }),
+            r#"{"type":"outcome","guess":50,"result":"too_small","attempts":1}"#
+        );
+        assert_eq!(
+            json(Message::Rejected(InvalidGuess::NotANumber)),
+            r#"{"type":"error","code":"not_a_number","message":"That is not a number."}"#
+        );
+        assert_eq!(
+            json(Message::RoundOver {
+                round: 1,
+                status: Status::GaveUp,
+                secret: 70,
+                attempts: 3,
+                score: 0,
+                input_ended: false,
+            }),
+            r#"{"type":"round_over","round":1,"status":"gave_up","secret":70,"attempts":3,"score":0}"#
+        );
+    }
+
+    #[test]
+    fn a_summary_without_wins_has_no_average() {
+        let summary = Message::Summary {
+            end: SessionEnd::Quit,
+            tally: Tally {
+                rounds_played: 1,
+                ..Tally::default()
+            },
+        }
+        .to_json();
+        assert_eq!(summary.get("end").and_then(Value::as_str), Some("quit"));
+        assert_eq!(summary.get("average_attempts"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn reads_every_input_type() {
+        let cases = [
+            (
+                r#"{"type":"guess","value":50}"#,
+                Input::Guess("50".to_string()),
+            ),
+            (
+                r#"{"type":"guess","value":" 7 "}"#,
+                Input::Guess(" 7 ".to_string()),
+            ),
+            (r#"{"type":"give_up"}"#, Input::GiveUp),
+            (r#"{"type":"range"}"#, Input::Range),
+            (r#"{"type":"history"}"#, Input::History(None)),
+            (r#"{"type":"history","count":3}"#, Input::History(Some(3))),
+            (r#"{"type":"quit"}"#, Input::Quit),
+            (
+                r#"{"value":false,"type":"play_again"}"#,
+                Input::PlayAgain(false),
+            ),
+        ];
+        for (line, input) in cases {
+            assert_eq!(Input::from_json(line), Ok(input), "{line}");
+        }
+    }
+
+    #[test]
+    fn bad_input_lines_get_an_error_code() {
+        let code = |line: &str| match Input::from_json(line) {
+            Err(Message::BadInput { code, .. }) => code,
+            other => panic!("{line} was read as {other:?}"),
+        };
+        assert_eq!(code("{"), "bad_json");
+        assert_eq!(code(r#"{"value":1}"#), "bad_value");
+        assert_eq!(code(r#"{"type":"guess","value":true}"#), "bad_value");
+        assert_eq!(code(r#"{"type":"history","count":0}"#), "bad_value");
+        assert_eq!(code(r#"{"type":"play_again"}"#), "bad_value");
+        assert_eq!(code(r#"{"type":"dance"}"#), "unknown_type");
+    }
+
+    #[test]
+    fn typed_commands_and_guesses_are_told_apart() {
+        assert_eq!(Input::from_text("Q"), Input::Quit);
+        assert_eq!(Input::from_text(":giveup"), Input::GiveUp);
+        assert_eq!(Input::from_text(" 42 "), Input::Guess(" 42 ".to_string()));
+    }
+}
@@ -0,0 +1,569 @@
+// Finished rounds are kept in a plain text file, one round per line, so they survive the process and can be summed up later.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::difficulty::Difficulty;
+use crate::game::Status;
+
+/// The tag every line written by this version starts with. A line with any other tag is skipped, so a newer format can share the file with older readers.
+pub const FORMAT_VERSION: &str = "v1";
+
+/// The number of tab-separated fields on a `v1` line, including the version tag.
+const FIELDS: usize = 13;
+
+/// The tag of a word game's line, which has fields of its own; see `WordRecord`.
+pub const WORD_FORMAT_VERSION: &str = "w1";
+
+/// The number of tab-separated fields on a `w1` line, including the tag.
+const WORD_FIELDS: usize = 9;
+
+/// One finished round as it is stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundRecord {
+    /// Seconds since the Unix epoch when the round ended.
+    pub finished_at: u64,
+    pub player: String,
+    pub difficulty: Difficulty,
+    pub min: i128,
+    pub max: i128,
+    pub max_attempts: Option<u32>,
+    pub attempts: u32,
+    pub duration: Duration,
+    /// The session's seed; together with `round` it identifies the secret.
+    pub seed: u64,
+    /// Which round of the session this was, starting at 1.
+    pub round: u32,
+    pub status: Status,
+    pub score: u32,
+}
+
+impl RoundRecord {
+    /// The line stored for this round, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        let max_attempts = match self.max_attempts {
+            Some(max) => max.to_string(),
+            None => "-".to_string(),
+        };
+        [
+            FORMAT_VERSION.to_string(),
+            self.finished_at.to_string(),
+            clean_name(&self.player),
+            self.difficulty.to_string(),
+            self.min.to_string(),
+            self.max.to_string(),
+            max_attempts,
+            self.attempts.to_string(),
+            self.duration.as_millis().to_string(),
+            self.seed.to_string(),
+            self.round.to_string(),
+            status_name(self.status).to_string(),
+            self.score.to_string(),
+        ]
+        .join("\t")
+    }
+
+    /// Parses one stored line; `None` for lines from another format version or that are damaged.
+    pub fn from_line(line: &str) -> Option<RoundRecord> {
+        let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+        if fields.len() != FIELDS || fields[0] != FORMAT_VERSION {
+            return None;
+        }
+
+        let max_attempts = match fields[6] {
+            "-" => None,
+            max => Some(max.parse().ok()?),
+        };
+        let duration_ms: u64 = fields[8].parse().ok()?;
+
+        // The ? operator returns None from the whole function as soon as any field fails to parse.
+        Some(RoundRecord {
+            finished_at: fields[1].parse().ok()?,
+            player: fields[2].to_string(),
+            difficulty: fields[3].parse().ok()?,
+            min: fields[4].parse().ok()?,
+            max: fields[5].parse().ok()?,
+            max_attempts,
+            attempts: fields[7].parse().ok()?,
+            duration: Duration::from_millis(duration_ms),
+            seed: fields[9].parse().ok()?,
+            round: fields[10].parse().ok()?,
+            status: parse_status(fields[11])?,
+            score: fields[12].parse().ok()?,
+        })
+    }
+
+    pub fn won(&self) -> bool {
+        self.status == Status::Won
+    }
+}
+
+/// One finished round of the word game as it is stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordRecord {
+    /// Seconds since the Unix epoch when the round ended.
+    pub finished_at: u64,
+    pub player: String,
+    /// The name of the word list the secret came from.
+    pub list: String,
+    /// How many words the list had.
+    pub words: usize,
+    pub attempts: u32,
+    pub duration: Duration,
+    pub seed: u64,
+    pub status: Status,
+}
+
+impl WordRecord {
+    /// The line stored for this round, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        [
+            WORD_FORMAT_VERSION.to_string(),
+            self.finished_at.to_string(),
+            clean_name(&self.player),
+            clean_name(&self.list),
+            self.words.to_string(),
+            self.attempts.to_string(),
+            self.duration.as_millis().to_string(),
+            self.seed.to_string(),
+            status_name(self.status).to_string(),
+        ]
+        .join("\t")
+    }
+
+    /// Parses one stored line; `None` for lines from another format or that are damaged.
+    pub fn from_line(line: &str) -> Option<WordRecord> {
+        let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+        if fields.len() != WORD_FIELDS || fields[0] != WORD_FORMAT_VERSION {
+            return None;
+        }
+        let duration_ms: u64 = fields[6].parse().ok()?;
+        Some(WordRecord {
+            finished_at: fields[1].parse().ok()?,
+            player: fields[2].to_string(),
+            list: fields[3].to_string(),
+            words: fields[4].parse().ok()?,
+            attempts: fields[5].parse().ok()?,
+            duration: Duration::from_millis(duration_ms),
+            seed: fields[7].parse().ok()?,
+            status: parse_status(fields[8])?,
+        })
+    }
+
+    pub fn won(&self) -> bool {
+        self.status == Status::Won
+    }
+}
+
+// Tabs and line breaks would split the record, so they become spaces; an empty name becomes "anonymous".
+fn clean_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if cleaned.is_empty() {
+        "anonymous".to_string()
+    } else {
+        cleaned
+    }
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Playing => "playing",
+        Status::Won => "won",
+        Status::Lost => "lost",
+        Status::GaveUp => "gave-up",
+    }
+}
+
+fn parse_status(name: &str) -> Option<Status> {
+    match name {
+        "playing" => Some(Status::Playing),
+        "won" => Some(Status::Won),
+        "lost" => Some(Status::Lost),
+        "gave-up" => Some(Status::GaveUp),
+        _ => None,
+    }
+}
+
+/// Where rounds are stored when no `--data-file` is given: `$XDG_DATA_HOME/guessing_game/rounds.tsv`, falling back to `~/.local/share` as the XDG spec says.
+pub fn default_path() -> Option<PathBuf> {
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        // The spec says a relative XDG_DATA_HOME is invalid and should be ignored.
+        Some(dir) if Path::new(&dir).is_absolute() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("share"),
+    };
+    Some(data_home.join("guessing_game").join("rounds.tsv"))
+}
+
+/// Everything read back from a data file.
+#[derive(Debug, Clone, Default)]
+pub struct Loaded {
+    pub records: Vec<RoundRecord>,
+    /// Rounds of the word game, which are kept in the same file.
+    pub word_records: Vec<WordRecord>,
+    /// Lines that couldn't be read, either damaged or written by a different format version.
+    pub skipped: usize,
+}
+
+/// The data file holding every recorded round.
+#[derive(Debug, Clone)]
+pub struct Store {
+    path: PathBuf,
+}
+
+impl Store {
+    pub fn new(path: PathBuf) -> Store {
+        Store { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends one round to the file, creating it (and its directory) if needed.
+    ///
+    /// The file is opened in append mode and the whole line goes out in a single write, so several games finishing at once each add a complete line rather than interleaving.
+    /// If an earlier writer died halfway through a line, the new record starts on a fresh line so only the damaged one is lost.
+    pub fn append(&self, record: &RoundRecord) -> io::Result<()> {
+        self.append_line(&record.to_line())
+    }
+
+    /// Appends one round of the word game, the same way as `append`.
+    pub fn append_word(&self, record: &WordRecord) -> io::Result<()> {
+        self.append_line(&record.to_line())
+    }
+
+    fn append_line(&self, record: &str) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let mut line = String::new();
+        if file.seek(SeekFrom::End(0))? > 0 {
+            let mut last = [0u8];
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                line.push('\n');
+            }
+        }
+        line.push_str(record);
+        line.push('\n');
+
+        // In append mode every write goes to the current end of the file, wherever the seeks above left us.
+        file.write_all(line.as_bytes())
+    }
+
+    /// Reads every round back. A missing file is simply empty; lines that can't be parsed are counted and skipped, so one bad line never hides the rest.
+    pub fn load(&self) -> io::Result<Loaded> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Loaded::default()),
+            Err(error) => return Err(error),
+        };
+        // from_utf8_lossy keeps going past invalid bytes; the damaged line then fails to parse like any other.
+        let text = String::from_utf8_lossy(&bytes);
+
+        let mut loaded = Loaded::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            if let Some(record) = RoundRecord::from_line(line) {
+                loaded.records.push(record);
+            } else if let Some(record) = WordRecord::from_line(line) {
+                loaded.word_records.push(record);
+            } else {
+                loaded.skipped += 1;
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+/// The best rounds of one difficulty, highest score first.
+pub fn leaderboard(
+    records: &[RoundRecord],
+    difficulty: Difficulty,
+    limit: usize,
+) -> Vec<&RoundRecord> {
+    let mut wins: Vec<&RoundRecord> = records
+        .iter()
+        .filter(|record| record.difficulty == difficulty && record.won())
+        .collect();
+    // Ties go to fewer attempts, then to whoever got there first.
+    wins.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.attempts.cmp(&b.attempts))
+            .then(a.finished_at.cmp(&b.finished_at))
+    });
+    wins.truncate(limit);
+    wins
+}
+
+/// Totals for one player.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub rounds: u32,
+    pub wins: u32,
+    pub best_score: u32,
+    /// How many wins took each number of attempts.
+    pub histogram: BTreeMap<u32, u32>,
+}
+
+impl PlayerStats {
+    pub fn win_rate(&self) -> f64 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.rounds as f64
+        }
+    }
+}
+
+/// Per-player totals, keyed (and so sorted) by player name.
+pub fn player_stats(records: &[RoundRecord]) -> BTreeMap<String, PlayerStats> {
+    let mut stats: BTreeMap<String, PlayerStats> = BTreeMap::new();
+    for record in records {
+        // The entry API inserts a default value the first time a player is seen.
+        let player = stats.entry(record.player.clone()).or_default();
+        player.rounds += 1;
+        if record.won() {
+            player.wins += 1;
+            player.best_score = player.best_score.max(record.score);
+            *player.histogram.entry(record.attempts).or_insert(0) += 1;
+        }
+    }
+    stats
+}
+
+/// Totals for one word list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListStats {
+    /// How many words the list had in its latest round; a file can change between sessions.
+    pub words: usize,
+    pub rounds: u32,
+    pub wins: u32,
+    /// The fewest attempts any win took.
+    pub fewest_attempts: Option<u32>,
+    total_attempts: u64,
+}
+
+impl ListStats {
+    pub fn win_rate(&self) -> f64 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.rounds as f64
+        }
+    }
+
+    /// The average attempts per win, or `None` before the first win.
+    pub fn average_attempts(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.total_attempts as f64 / self.wins as f64)
+    }
+}
+
+/// Per-list totals for the word game, keyed (and so sorted) by list name.
+pub fn list_stats(records: &[WordRecord]) -> BTreeMap<String, ListStats> {
+    let mut stats: BTreeMap<String, ListStats> = BTreeMap::new();
+    for record in records {
+        let list = stats.entry(record.list.clone()).or_default();
+        list.words = record.words;
+        list.rounds += 1;
+        if record.won() {
+            list.wins += 1;
+            list.total_attempts += u64::from(record.attempts);
+            list.fewest_attempts = Some(
+                list.fewest_attempts
+                    .map_or(record.attempts, |fewest| fewest.min(record.attempts)),
+            );
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(player: &str, status: Status, attempts: u32, score: u32) -> RoundRecord {
+        RoundRecord {
+            finished_at: 1_700_000_000,
+            player: player.to_string(),
+            difficulty: Difficulty::Normal,
+            min: 1,
+            max: 100,
+            max_attempts: Some(10),
+            attempts,
+            duration: Duration::from_millis(12_345),
+            seed: 42,
+            round: 1,
+            status,
+            score,
+        }
+    }
+
+    fn word(status: Status) -> WordRecord {
+        WordRecord {
+            finished_at: 1_700_000_000,
+            player: "ada".to_string(),
+            list: "fruit".to_string(),
+            words: 30,
+            attempts: 4,
+            duration: Duration::from_millis(900),
+            seed: 7,
+            status,
+        }
+    }
+
+    // A data file of its own under the system's temporary directory, so tests running at once don't share one.
+    fn store(name: &str) -> Store {
+        let dir = env::temp_dir().join(format!("guessing_game-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Store::new(dir.join("rounds.tsv"))
+    }
+
+    #[test]
+    fn records_read_back_what_they_wrote() {
+        let mut record = round("ada", Status::Lost, 10, 0);
+        record.max_attempts = None;
+        assert_eq!(RoundRecord::from_line(&record.to_line()), Some(record));
+        let record = round("ada", Status::Won, 3, 1000);
+        assert_eq!(RoundRecord::from_line(&record.to_line()), Some(record));
+
+        let record = word(Status::GaveUp);
+        assert_eq!(WordRecord::from_line(&record.to_line()), Some(record));
+        // Each kind of line is only read as its own kind.
+        assert_eq!(RoundRecord::from_line(&word(Status::Won).to_line()), None);
+    }
+
+    #[test]
+    fn tabs_and_line_breaks_in_names_are_cleaned() {
+        let record = round("a\tb\nc", Status::Won, 3, 1000);
+        let read = RoundRecord::from_line(&record.to_line()).unwrap();
+        assert_eq!(read.player, "a b c");
+        let read = RoundRecord::from_line(&round("  ", Status::Won, 3, 1000).to_line()).unwrap();
+        assert_eq!(read.player, "anonymous");
+    }
+
+    #[test]
+    fn damaged_and_unknown_lines_are_skipped_not_fatal() {
+        let store = store("skipped");
+        let good = round("ada", Status::Won, 3, 1000);
+        let newer = good.to_line().replacen(FORMAT_VERSION, "v2", 1);
+        let short = good.to_line().rsplit_once('\t').unwrap().0.to_string();
+        let bad_status = good.to_line().replace("\twon\t", "\tdrew\t");
+        let text = [
+            good.to_line(),
+            newer,
+            short,
+            bad_status,
+            "not a record".to_string(),
+            String::new(),
+            word(Status::Won).to_line(),
+        ]
+        .join("\n");
+        fs::create_dir_all(store.path().parent().unwrap()).unwrap();
+        fs::write(store.path(), text).unwrap();
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.records, vec![good]);
+        assert_eq!(loaded.word_records, vec![word(Status::Won)]);
+        assert_eq!(loaded.skipped, 4);
+        fs::remove_dir_all(store.path().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn a_missing_file_loads_as_empty() {
+        let loaded = store("missing").load().unwrap();
+        assert!(loaded.records.is_empty() && loaded.word_records.is_empty());
+        assert_eq!(loaded.skipped, 0);
+    }
+
+    #[test]
+    fn appending_after_a_torn_line_starts_a_fresh_one() {
+        let store = store("torn");
+        let first = round("ada", Status::Won, 3, 1000);
+        store.append(&first).unwrap();
+        // A writer that died halfway through its line left no newline behind.
+        let mut file = OpenOptions::new().append(true).open(store.path()).unwrap();
+        file.write_all(b"v1\t17000").unwrap();
+        drop(file);
+        let second = round("bob", Status::Lost, 10, 0);
+        store.append(&second).unwrap();
+
+        let text = fs::read_to_string(store.path()).unwrap();
+        assert!(text.ends_with('\n'));
+        assert_eq!(text.lines().count(), 3);
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.records, vec![first, second]);
+        assert_eq!(loaded.skipped, 1);
+        fs::remove_dir_all(store.path().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn the_leaderboard_ranks_wins_by_score_then_attempts_then_time() {
+        let best = round("ada", Status::Won, 5, 1000);
+        let mut fewer = round("bob", Status::Won, 4, 900);
+        fewer.finished_at += 10;
+        let mut earlier = round("cy", Status::Won, 6, 900);
+        earlier.finished_at -= 10;
+        let mut later = round("dee", Status::Won, 6, 900);
+        later.finished_at += 20;
+        let lost = round("eve", Status::Lost, 10, 2000);
+        let mut easy = round("fay", Status::Won, 1, 2000);
+        easy.difficulty = Difficulty::Easy;
+        let records = vec![
+            later.clone(),
+            lost,
+            earlier.clone(),
+            easy,
+            fewer.clone(),
+            best.clone(),
+        ];
+
+        let board = leaderboard(&records, Difficulty::Normal, 10);
+        assert_eq!(board, vec![&best, &fewer, &earlier, &later]);
+        assert_eq!(
+            leaderboard(&records, Difficulty::Normal, 2),
+            vec![&best, &fewer]
+        );
+        assert!(leaderboard(&records, Difficulty::Hard, 10).is_empty());
+    }
+
+    #[test]
+    fn player_stats_count_rounds_wins_and_attempts() {
+        let records = vec![
+            round("ada", Status::Won, 3, 800),
+            round("bob", Status::GaveUp, 2, 0),
+            round("ada", Status::Lost, 10, 0),
+            round("ada", Status::Won, 3, 950),
+            round("ada", Status::Won, 5, 700),
+        ];
+        let stats = player_stats(&records);
+        assert_eq!(stats.keys().collect::<Vec<_>>(), ["ada", "bob"]);
+
+        let ada = &stats["ada"];
+        assert_eq!((ada.rounds, ada.wins, ada.best_score), (4, 3, 950));
+        assert_eq!(ada.histogram, BTreeMap::from([(3, 2), (5, 1)]));
+        assert_eq!(ada.win_rate(), 0.75);
+
+        let bob = &stats["bob"];
+        assert_eq!((bob.rounds, bob.wins, bob.best_score), (1, 0, 0));
+        assert!(bob.histogram.is_empty());
+        assert_eq!(PlayerStats::default().win_rate(), 0.0);
+    }
+}
@@ -0,0 +1,217 @@
+// The game turned around: the player thinks of a number and the computer guesses it, using the same Ordering the engine compares with.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::bounds::Bounds;
+use crate::solver;
+
+/// Reads the player's answer to a guess as `guess.cmp(&their_number)`, exactly what the engine computes when it is the one holding the secret.
+///
+/// `higher`, `h` and `>` mean the player's number is higher than the guess (so the guess is `Less`, too small); `lower`, `l` and `<` the reverse; `correct`, `c`, `yes` and `=` mean `Equal`.
+pub fn parse_answer(input: &str) -> Option<Ordering> {
+    match input.trim().to_lowercase().as_str() {
+        "higher" | "h" | ">" | "too small" => Some(Ordering::Less),
+        "lower" | "l" | "<" | "too big" => Some(Ordering::Greater),
+        "correct" | "c" | "yes" | "=" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// One answer the player gave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answer {
+    pub guess: i128,
+    /// How the guess compares with the player's number.
+    pub ordering: Ordering,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ordering {
+            Ordering::Less => write!(f, "your number is higher than {}", self.guess),
+            Ordering::Greater => write!(f, "your number is lower than {}", self.guess),
+            Ordering::Equal => write!(f, "your number is {}", self.guess),
+        }
+    }
+}
+
+/// An answer that leaves no number possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    /// The answer just given, which has not been applied.
+    pub answer: Answer,
+    /// The earlier answer it conflicts with, with its position (starting at 1), or `None` when it conflicts with the range itself.
+    pub conflicts_with: Option<(usize, Answer)>,
+    pub bounds: Bounds,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "You can't mean that {}: ", self.answer)?;
+        match self.conflicts_with {
+            Some((number, earlier)) => write!(f, "in answer #{number} you said {earlier}."),
+            None => write!(f, "you picked a number between {}.", self.bounds),
+        }
+    }
+}
+
+/// The computer's side of the reversed game: it guesses with binary search and remembers which answers set the current limits, so it can point at the one a contradiction conflicts with.
+#[derive(Debug, Clone)]
+pub struct Guesser {
+    bounds: Bounds,
+    low: i128,
+    high: i128,
+    // Indexes into `answers` of the answers that last raised `low` and lowered `high`.
+    low_from: Option<usize>,
+    high_from: Option<usize>,
+    answers: Vec<Answer>,
+    found: bool,
+}
+
+impl Guesser {
+    pub fn new(bounds: Bounds) -> Guesser {
+        Guesser {
+            bounds,
+            low: bounds.min(),
+            high: bounds.max(),
+            low_from: None,
+            high_from: None,
+            answers: Vec::new(),
+            found: false,
+        }
+    }
+
+    /// The number to ask about next, or `None` once it has been found.
+    pub fn next_guess(&self) -> Option<i128> {
+        if self.found {
+            None
+        } else {
+            Some(solver::midpoint(self.low, self.high))
+        }
+    }
+
+    /// Applies the player's answer to the current guess. An answer that would leave no number possible is rejected, and the guess stays the same.
+    pub fn answer(&mut self, ordering: Ordering) -> Result<(), Contradiction> {
+        let guess = match self.next_guess() {
+            Some(guess) => guess,
+            None => return Ok(()),
+        };
+        let answer = Answer { guess, ordering };
+
+        // The guess is always inside low..=high, so it's only at either end that an answer can empty the interval.
+        let conflicts_with = match ordering {
+            Ordering::Less if guess == self.high => Some(self.high_from),
+            Ordering::Greater if guess == self.low => Some(self.low_from),
+            _ => None,
+        };
+        if let Some(index) = conflicts_with {
+            return Err(Contradiction {
+                answer,
+                conflicts_with: index.map(|index| (index + 1, self.answers[index])),
+                bounds: self.bounds,
+            });
+        }
+
+        match ordering {
+            Ordering::Less => {
+                self.low = guess + 1;
+                self.low_from = Some(self.answers.len());
+            }
+            Ordering::Greater => {
+                self.high = guess - 1;
+                self.high_from = Some(self.answers.len());
+            }
+            Ordering::Equal => self.found = true,
+        }
+        self.answers.push(answer);
+        Ok(())
+    }
+
+    /// Every answer accepted so far, in order.
+    pub fn answers(&self) -> &[Answer] {
+        &self.answers
+    }
+
+    /// How many guesses the player has answered.
+    pub fn guesses(&self) -> u32 {
+        self.answers.len() as u32
+    }
+
+    pub fn is_found(&self) -> bool {
+        self.found
+    }
+
+    /// The numbers the player's answers still allow.
+    pub fn remaining(&self) -> RangeInclusive<i128> {
+        self.low..=self.high
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_read_in_several_spellings() {
+        assert_eq!(parse_answer(" Higher "), Some(Ordering::Less));
+        assert_eq!(parse_answer("<"), Some(Ordering::Greater));
+        assert_eq!(parse_answer("yes"), Some(Ordering::Equal));
+        assert_eq!(parse_answer("maybe"), None);
+    }
+
+    #[test]
+    fn finds_the_players_number() {
+        let bounds = Bounds::DEFAULT;
+        for number in 1..=100 {
+            let mut guesser = Guesser::new(bounds);
+            while let Some(guess) = guesser.next_guess() {
+                guesser.answer(guess.cmp(&number)).unwrap();
+            }
+            assert_eq!(guesser.answers().last().unwrap().guess, number);
+            assert!(guesser.guesses() <= 7);
+        }
+    }
+
+    #[test]
+    fn a_contradiction_points_at_the_answer_it_conflicts_with() {
+        let mut guesser = Guesser::new(Bounds::new(1, 4).unwrap());
+        assert_eq!(guesser.next_guess(), Some(2));
+        guesser.answer(Ordering::Less).unwrap();
+        assert_eq!(guesser.next_guess(), Some(3));
+
+        let contradiction = guesser.answer(Ordering::Greater).unwrap_err();
+        let earlier = Answer {
+            guess: 2,
+            ordering: Ordering::Less,
+        };
+        assert_eq!(contradiction.conflicts_with, Some((1, earlier)));
+        assert_eq!(
+            contradiction.to_string(),
+            "You can't mean that your number is lower than 3: in answer #1 you said your number is higher than 2."
+        );
+        // The rejected answer changes nothing.
+        assert_eq!(guesser.next_guess(), Some(3));
+        assert_eq!(guesser.answers(), [earlier]);
+    }
+
+    #[test]
+    fn a_contradiction_can_be_with_the_range_itself() {
+        let mut guesser = Guesser::new(Bounds::new(1, 2).unwrap());
+        let contradiction = guesser.answer(Ordering::Greater).unwrap_err();
+        assert_eq!(contradiction.conflicts_with, None);
+        assert!(contradiction
+            .to_string()
+            .ends_with("you picked a number between 1 and 2."));
+    }
+
+    #[test]
+    fn answers_after_the_number_is_found_are_ignored() {
+        let mut guesser = Guesser::new(Bounds::DEFAULT);
+        guesser.answer(Ordering::Equal).unwrap();
+        assert!(guesser.is_found());
+        assert_eq!(guesser.answer(Ordering::Less), Ok(()));
+        assert_eq!(guesser.guesses(), 1);
+    }
+}
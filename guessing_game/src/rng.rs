@@ -0,0 +1,33 @@
+// The SeedableRng trait lets a generator be built from a fixed seed, so the same seed always yields the same sequence of numbers.
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The one generator type the game draws every random decision from.
+///
+/// rand's own StdRng may switch algorithms in any release, so a seed would stop replaying the same game after an update. ChaCha8Rng promises the same stream for a seed on every platform and in every version of rand_chacha, and rand only changes how it turns that stream into numbers between minor versions, which the `0.8` requirement in Cargo.toml rules out.
+pub type GameRng = ChaCha8Rng;
+
+/// Builds the game's generator from `seed`.
+pub fn seeded(seed: u64) -> GameRng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Picks a fresh seed from the operating system so a session that wasn't given one can still be replayed later.
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, Rules};
+
+    // If this fails, a seed shown to a player no longer replays their game; that is a breaking change, not a test to update lightly.
+    #[test]
+    fn a_seed_always_picks_the_same_secrets() {
+        let secrets: Vec<i128> = (0..5)
+            .map(|seed| Game::random(Rules::default(), &mut seeded(seed)).secret())
+            .collect();
+        assert_eq!(secrets, vec![47, 9, 86, 9, 77]);
+    }
+}
@@ -0,0 +1,570 @@
+// A small TCP server so several players can race for the same secret. Every message is one line of text, which keeps the protocol easy to speak with telnet or nc.
+//
+// Client to server:
+//   GUESS <n>    (a bare number works too)
+//   NAME <name>  letters, digits, `-` and `_`, at most 20 characters
+//   STANDINGS
+//   QUIT
+//
+// Server to client:
+//   HELLO guessing_game <protocol version> <your name>
+//   ROUND <round> <min> <max> <attempts per round, or ->
+//   TOO_SMALL <n> | TOO_BIG <n>     the answer to your guess
+//   OUT_OF_ATTEMPTS                 you have no guesses left this round
+//   WINNER <name> <secret> <attempts>
+//   NO_WINNER <secret>              every player ran out of attempts; a new round follows
+//   STANDINGS <name>=<wins> ...     most wins first
+//   JOINED <name> | LEFT <name> | RENAMED <old> <new>
+//   ERROR <message>
+//   BYE <reason>
+//
+// The state is shared behind one Mutex, and lines are written to clients while it is held, so a client that stops reading would hold up everyone else.
+// Every connection therefore gets a write timeout, and a client whose write fails or times out is disconnected.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::difficulty::Rules;
+use crate::game::{Game, Outcome, Status};
+use crate::rng::GameRng;
+
+/// Bumped whenever a message changes shape.
+/// 2 added NO_WINNER.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// The port the book's web server chapter uses, and ours by default.
+pub const DEFAULT_PORT: u16 = 7878;
+
+const MAX_NAME_LEN: usize = 20;
+
+/// How the server runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerOptions {
+    /// A client that sends nothing for this long is disconnected.
+    pub idle_timeout: Duration,
+    /// A client that takes longer than this to accept a line is disconnected, so it can't hold up the others.
+    pub write_timeout: Duration,
+    /// Stop after this many rounds have been played; `None` serves until the process is killed.
+    pub rounds: Option<u32>,
+}
+
+impl Default for ServerOptions {
+    fn default() -> ServerOptions {
+        ServerOptions {
+            idle_timeout: Duration::from_secs(300),
+            write_timeout: Duration::from_secs(5),
+            rounds: None,
+        }
+    }
+}
+
+struct Player {
+    name: String,
+    // A clone of the client's stream used only for writing, so any thread can send to it.
+    writer: TcpStream,
+    // Each player plays their own copy of the round, sharing the secret but not the attempt count.
+    game: Game,
+}
+
+struct State {
+    rules: Rules,
+    rng: GameRng,
+    options: ServerOptions,
+    round: u32,
+    game: Game,
+    players: BTreeMap<u64, Player>,
+    next_id: u64,
+    // Wins by name; kept after a player leaves so the standings don't forget them.
+    wins: BTreeMap<String, u32>,
+    finished: bool,
+}
+
+impl State {
+    fn round_line(&self) -> String {
+        let attempts = match self.rules.max_attempts {
+            Some(max) => max.to_string(),
+            None => "-".to_string(),
+        };
+        format!(
+            "ROUND {} {} {} {attempts}",
+            self.round,
+            self.rules.bounds.min(),
+            self.rules.bounds.max()
+        )
+    }
+
+    fn standings_line(&self) -> String {
+        let mut standings: Vec<(&String, &u32)> = self.wins.iter().collect();
+        // Most wins first; names break ties so the order is stable.
+        standings.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let mut line = String::from("STANDINGS");
+        for (name, wins) in standings {
+            line.push_str(&format!(" {name}={wins}"));
+        }
+        line
+    }
+
+    // Sends a line to everyone. A player whose connection fails is dropped, and their departure announced in turn.
+    fn broadcast(&mut self, line: &str) {
+        let mut gone = Vec::new();
+        for (id, player) in self.players.iter_mut() {
+            if send(&mut player.writer, line).is_err() {
+                gone.push(*id);
+            }
+        }
+        for id in gone {
+            self.remove(id);
+        }
+    }
+
+    fn remove(&mut self, id: u64) {
+        if let Some(player) = self.players.remove(&id) {
+            // Closing the socket wakes the player's own thread, which is waiting on a read, so it can finish.
+            let _ = player.writer.shutdown(Shutdown::Both);
+            self.broadcast(&format!("LEFT {}", player.name));
+        }
+    }
+
+    // True once nobody left in the round can still guess, so no one is going to win it.
+    fn round_lost(&self) -> bool {
+        !self.players.is_empty()
+            && self
+                .players
+                .values()
+                .all(|player| player.game.status() != Status::Playing)
+    }
+
+    // Announces the secret of a round nobody won and moves on, unless that was the last round.
+    fn end_lost_round(&mut self) {
+        let secret = self.game.secret();
+        self.broadcast(&format!("NO_WINNER {secret}"));
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        if self.options.rounds == Some(self.round) {
+            self.broadcast("BYE all rounds played");
+            self.finished = true;
+        } else {
+            self.start_round();
+        }
+    }
+
+    fn start_round(&mut self) {
+        self.round += 1;
+        self.game = Game::random(self.rules, &mut self.rng);
+        let secret = self.game.secret();
+        for player in self.players.values_mut() {
+            player.game = Game::new(self.rules, secret);
+        }
+        let line = self.round_line();
+        self.broadcast(&line);
+    }
+}
+
+fn send(stream: &mut TcpStream, line: &str) -> io::Result<()> {
+    stream.write_all(format!("{line}\n").as_bytes())
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Serves games on `listener` until the requested number of rounds has been won, or forever.
+///
+/// Every client gets its own thread; the shared state sits behind a Mutex, which each thread locks only while it handles one line.
+pub fn serve(
+    listener: TcpListener,
+    rules: Rules,
+    mut rng: GameRng,
+    options: ServerOptions,
+) -> io::Result<()> {
+    let game = Game::random(rules, &mut rng);
+    let state = Arc::new(Mutex::new(State {
+        rules,
+        rng,
+        options,
+        round: 1,
+        game,
+        players: BTreeMap::new(),
+        next_id: 1,
+        wins: BTreeMap::new(),
+        finished: false,
+    }));
+
+    // A non-blocking listener lets the loop notice when the last round is over instead of waiting forever in accept.
+    listener.set_nonblocking(true)?;
+    loop {
+        if state.lock().unwrap().finished {
+            return Ok(());
+        }
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let state = Arc::clone(&state);
+                thread::spawn(move || {
+                    // A failure on one connection only ends that client's thread.
+                    let _ = handle_client(state, stream);
+                });
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(20));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn handle_client(state: Arc<Mutex<State>>, stream: TcpStream) -> io::Result<()> {
+    // Accepted sockets may inherit non-blocking mode from the listener on some platforms, so set it back explicitly.
+    stream.set_nonblocking(false)?;
+    let options = state.lock().unwrap().options;
+    stream.set_read_timeout(Some(options.idle_timeout))?;
+    stream.set_write_timeout(Some(options.write_timeout))?;
+    let mut writer = stream.try_clone()?;
+
+    let id = {
+        let mut state = state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        let name = format!("player-{id}");
+        send(
+            &mut writer,
+            &format!("HELLO guessing_game {PROTOCOL_VERSION} {name}"),
+        )?;
+        send(&mut writer, &state.round_line())?;
+        state.broadcast(&format!("JOINED {name}"));
+        let game = Game::new(state.rules, state.game.secret());
+        state.players.insert(
+            id,
+            Player {
+                name,
+                writer: writer.try_clone()?,
+                game,
+            },
+        );
+        id
+    };
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    let reason = loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            // The client closed the connection.
+            Ok(0) => break None,
+            Ok(_) => {}
+            // A read timeout shows up as WouldBlock on Unix and TimedOut on Windows.
+            Err(error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                break Some("timeout");
+            }
+            Err(_) => break None,
+        }
+
+        let mut state = state.lock().unwrap();
+        if state.finished {
+            break Some("server closing");
+        }
+        if !state.players.contains_key(&id) {
+            // A failed broadcast already removed us.
+            return Ok(());
+        }
+        if handle_line(&mut state, id, line.trim()) {
+            break Some("quit");
+        }
+    };
+
+    let mut state = state.lock().unwrap();
+    if let Some(reason) = reason {
+        let _ = send(&mut writer, &format!("BYE {reason}"));
+    }
+    state.remove(id);
+    // The players still here may all be out of attempts, waiting on the one who just left.
+    if !state.finished && state.round_lost() {
+        state.end_lost_round();
+    }
+    Ok(())
+}
+
+// Handles one line from a client. Returns true when the client asked to leave.
+fn handle_line(state: &mut State, id: u64, line: &str) -> bool {
+    let (command, argument) = match line.split_once(' ') {
+        Some((command, argument)) => (command, argument.trim()),
+        None => (line, ""),
+    };
+
+    match command.to_ascii_uppercase().as_str() {
+        "" => {}
+        "QUIT" => return true,
+        "STANDINGS" => {
+            let standings = state.standings_line();
+            reply(state, id, &standings);
+        }
+        "NAME" if !valid_name(argument) => {
+            reply(state, id, "ERROR names are 1 to 20 letters, digits, - or _")
+        }
+        "NAME" if state.players.values().any(|player| player.name == argument) => {
+            reply(state, id, "ERROR that name is taken")
+        }
+        "NAME" => {
+            let old = std::mem::replace(
+                &mut state.players.get_mut(&id).unwrap().name,
+                argument.to_string(),
+            );
+            state.broadcast(&format!("RENAMED {old} {argument}"));
+        }
+        "GUESS" => guess(state, id, argument),
+        // A bare number is a guess too.
+        _ if command.parse::<i128>().is_ok() && argument.is_empty() => guess(state, id, command),
+        _ => reply(state, id, &format!("ERROR unknown command '{command}'")),
+    }
+    false
+}
+
+fn reply(state: &mut State, id: u64, line: &str) {
+    let failed = match state.players.get_mut(&id) {
+        Some(player) => send(&mut player.writer, line).is_err(),
+        None => false,
+    };
+    if failed {
+        state.remove(id);
+    }
+}
+
+fn guess(state: &mut State, id: u64, input: &str) {
+    // Work on the player's own game first, then let go of it so the replies below can borrow the whole state.
+    let (outcome, name, attempts, status) = match state.players.get_mut(&id) {
+        Some(player) if player.game.status() == Status::Lost => {
+            reply(state, id, "OUT_OF_ATTEMPTS");
+            return;
+        }
+        Some(player) => {
+            let outcome = player.game.submit(input);
+            (
+                outcome,
+                player.name.clone(),
+                player.game.attempts(),
+                player.game.status(),
+            )
+        }
+        None => return,
+    };
+
+    match outcome {
+        Outcome::TooSmall => reply(state, id, &format!("TOO_SMALL {}", input.trim())),
+        Outcome::TooBig => reply(state, id, &format!("TOO_BIG {}", input.trim())),
+        Outcome::Invalid(reason) => reply(state, id, &format!("ERROR {reason}")),
+        Outcome::Win => {
+            let secret = state.game.secret();
+            *state.wins.entry(name.clone()).or_insert(0) += 1;
+            state.broadcast(&format!("WINNER {name} {secret} {attempts}"));
+            let standings = state.standings_line();
+            state.broadcast(&standings);
+            state.next_round();
+            return;
+        }
+    }
+
+    if status == Status::Lost {
+        reply(state, id, "OUT_OF_ATTEMPTS");
+        if state.round_lost() {
+            state.end_lost_round();
+        }
+    }
+}
+
+/// Turns one line from the server into a sentence for a human.
+pub fn describe(line: &str) -> String {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        ["HELLO", _, _, name] => format!("Connected as {name}. Type a number to guess, `name <new name>` to rename, `standings` or `quit`."),
+        ["ROUND", round, min, max, "-"] => format!("Round {round}: guess the number between {min} and {max}!"),
+        ["ROUND", round, min, max, attempts] => {
+            format!("Round {round}: guess the number between {min} and {max} in {attempts} attempts!")
+        }
+        ["TOO_SMALL", guess] => format!("{guess}: Too small!"),
+        ["TOO_BIG", guess] => format!("{guess}: Too big!"),
+        ["OUT_OF_ATTEMPTS"] => "You're out of attempts; wait for the next round.".to_string(),
+        ["WINNER", name, secret, attempts] => {
+            format!("{name} wins! The secret number was {secret} ({attempts} attempts).")
+        }
+        ["NO_WINNER", secret] => format!("No one found it; the secret number was {secret}."),
+        ["STANDINGS"] => "No one has won yet.".to_string(),
+        ["STANDINGS", standings @ ..] => format!("Standings: {}", standings.join(", ").replace('=', ": ")),
+        ["JOINED", name] => format!("{name} joined."),
+        ["LEFT", name] => format!("{name} left."),
+        ["RENAMED", old, new] => format!("{old} is now {new}."),
+        ["ERROR", message @ ..] => message.join(" "),
+        ["BYE", reason @ ..] => format!("Goodbye ({}).", reason.join(" ")),
+        _ => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::Bounds;
+    use crate::rng;
+    use std::net::SocketAddr;
+    use std::thread::JoinHandle;
+
+    const SEED: u64 = 7;
+
+    fn rules() -> Rules {
+        Rules {
+            bounds: Bounds::new(1, 100).unwrap(),
+            max_attempts: Some(1),
+        }
+    }
+
+    // The first secret the server draws, since it draws from the same seed.
+    fn first_secret() -> i128 {
+        Game::random(rules(), &mut rng::seeded(SEED)).secret()
+    }
+
+    fn wrong_guess() -> i128 {
+        if first_secret() == 1 {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn start(rounds: Option<u32>) -> (SocketAddr, JoinHandle<io::Result<()>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let options = ServerOptions {
+            rounds,
+            ..ServerOptions::default()
+        };
+        let server = thread::spawn(move || serve(listener, rules(), rng::seeded(SEED), options));
+        (address, server)
+    }
+
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl Client {
+        // Connects and reads the greeting and the current round.
+        fn join(address: SocketAddr) -> Client {
+            let stream = TcpStream::connect(address).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let writer = stream.try_clone().unwrap();
+            let mut client = Client {
+                reader: BufReader::new(stream),
+                writer,
+            };
+            client.expect("HELLO");
+            client.expect("ROUND 1");
+            client
+        }
+
+        fn send(&mut self, line: &str) {
+            send(&mut self.writer, line).unwrap();
+        }
+
+        // Reads lines until one starts with `prefix`, skipping the others, and returns it.
+        fn expect(&mut self, prefix: &str) -> String {
+            loop {
+                let mut line = String::new();
+                let read = self.reader.read_line(&mut line).unwrap();
+                assert!(read > 0, "the server hung up waiting for '{prefix}'");
+                if line.starts_with(prefix) {
+                    return line.trim_end().to_string();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_round_everyone_loses_reveals_the_secret_and_starts_another() {
+        let (address, _server) = start(None);
+        let mut alice = Client::join(address);
+        let mut bob = Client::join(address);
+        alice.expect("JOINED");
+
+        alice.send(&wrong_guess().to_string());
+        alice.expect("OUT_OF_ATTEMPTS");
+        bob.send(&wrong_guess().to_string());
+        bob.expect("OUT_OF_ATTEMPTS");
+
+        let no_winner = format!("NO_WINNER {}", first_secret());
+        assert_eq!(alice.expect("NO_WINNER"), no_winner);
+        assert_eq!(bob.expect("NO_WINNER"), no_winner);
+        alice.expect("ROUND 2");
+        bob.expect("ROUND 2");
+    }
+
+    #[test]
+    fn the_last_player_still_guessing_leaving_ends_the_round() {
+        let (address, _server) = start(None);
+        let mut alice = Client::join(address);
+        let mut bob = Client::join(address);
+        alice.expect("JOINED");
+
+        alice.send(&wrong_guess().to_string());
+        alice.expect("OUT_OF_ATTEMPTS");
+        bob.send("QUIT");
+        bob.expect("BYE quit");
+
+        alice.expect("LEFT");
+        assert_eq!(
+            alice.expect("NO_WINNER"),
+            format!("NO_WINNER {}", first_secret())
+        );
+        alice.expect("ROUND 2");
+    }
+
+    #[test]
+    fn the_server_stops_after_its_last_round() {
+        let (address, server) = start(Some(1));
+        let mut alice = Client::join(address);
+
+        alice.send(&format!("GUESS {}", first_secret()));
+        assert_eq!(
+            alice.expect("WINNER"),
+            format!("WINNER player-1 {} 1", first_secret())
+        );
+        alice.expect("STANDINGS player-1=1");
+        alice.expect("BYE all rounds played");
+        server.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn names_are_checked_and_renames_announced() {
+        let (address, _server) = start(None);
+        let mut alice = Client::join(address);
+        let mut bob = Client::join(address);
+        alice.expect("JOINED");
+
+        alice.send("NAME alice");
+        assert_eq!(alice.expect("RENAMED"), "RENAMED player-1 alice");
+        assert_eq!(bob.expect("RENAMED"), "RENAMED player-1 alice");
+        bob.send("NAME alice");
+        assert_eq!(bob.expect("ERROR"), "ERROR that name is taken");
+        bob.send("NAME not a name");
+        bob.expect("ERROR names are");
+    }
+
+    #[test]
+    fn describe_turns_lines_into_sentences() {
+        assert_eq!(describe("TOO_BIG 50"), "50: Too big!");
+        assert_eq!(
+            describe("NO_WINNER 42"),
+            "No one found it; the secret number was 42."
+        );
+        assert_eq!(describe("STANDINGS a=2 b=1"), "Standings: a: 2, b: 1");
+        assert_eq!(describe("SOMETHING new"), "SOMETHING new");
+    }
+}
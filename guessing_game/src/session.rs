@@ -0,0 +1,191 @@
+use crate::difficulty::Rules;
+use crate::game::{Game, Outcome, Redundancy, Status};
+use crate::rng::GameRng;
+
+/// Running totals over every finished round of a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub rounds_played: u32,
+    pub rounds_won: u32,
+    /// Attempts summed over won rounds only, so a give-up doesn't skew the average.
+    pub winning_attempts: u32,
+    pub total_score: u32,
+    pub best_score: u32,
+    /// Guesses that earlier answers had already ruled out, over every round.
+    pub redundant_guesses: u32,
+}
+
+impl Tally {
+    /// The average number of attempts it took to win a round, or `None` before the first win.
+    pub fn average_attempts(&self) -> Option<f64> {
+        if self.rounds_won == 0 {
+            None
+        } else {
+            Some(self.winning_attempts as f64 / self.rounds_won as f64)
+        }
+    }
+
+    fn record(&mut self, game: &Game) {
+        self.rounds_played += 1;
+        if game.status() == Status::Won {
+            self.rounds_won += 1;
+            self.winning_attempts += game.attempts();
+        }
+        self.total_score += game.score();
+        self.best_score = self.best_score.max(game.score());
+        self.redundant_guesses += game.redundant_guesses();
+    }
+}
+
+/// A sequence of rounds played by one player.
+///
+/// Each round keeps its secret until it is won, lost or given up; only then can `new_round` start the next one.
+/// Every random decision is drawn from the session's one generator, so the same seed and the same input replay the same session.
+#[derive(Debug, Clone)]
+pub struct Session {
+    rng: GameRng,
+    rules: Rules,
+    redundancy: Redundancy,
+    undos: u32,
+    game: Game,
+    tally: Tally,
+}
+
+impl Session {
+    /// Starts a session whose rounds are all played under `rules`, with secrets drawn from `rng`.
+    pub fn new(mut rng: GameRng, rules: Rules) -> Session {
+        let game = Game::random(rules, &mut rng);
+        Session {
+            rng,
+            rules,
+            redundancy: Redundancy::default(),
+            undos: 0,
+            game,
+            tally: Tally::default(),
+        }
+    }
+
+    /// Sets what happens to redundant guesses, in this round and every one after it.
+    pub fn set_redundancy(&mut self, redundancy: Redundancy) {
+        self.redundancy = redundancy;
+        self.game.set_redundancy(redundancy);
+    }
+
+    /// Sets how many guesses each round, this one included, lets the player take back.
+    pub fn set_undos(&mut self, undos: u32) {
+        self.undos = undos;
+        self.game.set_undos(undos);
+    }
+
+    /// The current round, mutably, for commands like :undo and :hint that change it without a guess.
+    pub fn game_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    /// The current round.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn tally(&self) -> Tally {
+        self.tally
+    }
+
+    /// Submits a line of input to the current round, recording the round in the tally once it is over.
+    pub fn submit(&mut self, input: &str) -> Outcome {
+        let was_finished = self.game.is_finished();
+        let outcome = self.game.submit(input);
+        if !was_finished && self.game.is_finished() {
+            self.tally.record(&self.game);
+        }
+        outcome
+    }
+
+    /// Gives up the current round, which still counts as played.
+    pub fn give_up(&mut self) {
+        if !self.game.is_finished() {
+            self.game.give_up();
+            self.tally.record(&self.game);
+        }
+    }
+
+    /// Starts the next round with a fresh secret from the session's generator.
+    ///
+    /// A round that is still being played is given up first, so every round started is counted.
+    pub fn new_round(&mut self) {
+        self.give_up();
+        self.game = Game::random(self.rules, &mut self.rng);
+        self.game.set_redundancy(self.redundancy);
+        self.game.set_undos(self.undos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    fn play_to_the_end(session: &mut Session) {
+        let secret = session.game().secret();
+        assert_eq!(session.submit(&secret.to_string()), Outcome::Win);
+    }
+
+    #[test]
+    fn the_same_seed_draws_the_same_secrets() {
+        let mut a = Session::new(rng::seeded(3), Rules::default());
+        let mut b = Session::new(rng::seeded(3), Rules::default());
+        for _ in 0..5 {
+            assert_eq!(a.game().secret(), b.game().secret());
+            a.new_round();
+            b.new_round();
+        }
+    }
+
+    #[test]
+    fn finished_rounds_are_tallied_once() {
+        let mut session = Session::new(rng::seeded(3), Rules::default());
+        play_to_the_end(&mut session);
+        // Guesses after the win are rejected and don't count the round again.
+        session.submit("1");
+        session.give_up();
+
+        let tally = session.tally();
+        assert_eq!(tally.rounds_played, 1);
+        assert_eq!(tally.rounds_won, 1);
+        assert_eq!(tally.winning_attempts, 1);
+        assert_eq!(tally.best_score, 1000);
+        assert_eq!(tally.average_attempts(), Some(1.0));
+    }
+
+    #[test]
+    fn a_new_round_gives_up_the_one_in_play() {
+        let mut session = Session::new(rng::seeded(3), Rules::default());
+        session.new_round();
+        play_to_the_end(&mut session);
+        session.new_round();
+
+        let tally = session.tally();
+        assert_eq!(tally.rounds_played, 2);
+        assert_eq!(tally.rounds_won, 1);
+        assert_eq!(tally.total_score, 1000);
+        assert_eq!(session.game().status(), Status::Playing);
+    }
+
+    #[test]
+    fn settings_carry_over_to_later_rounds() {
+        let mut session = Session::new(rng::seeded(3), Rules::default());
+        session.set_redundancy(Redundancy::Free);
+        session.set_undos(2);
+        session.new_round();
+        assert_eq!(session.game().redundancy(), Redundancy::Free);
+        assert_eq!(session.game().undos_left(), 2);
+    }
+
+    #[test]
+    fn an_average_needs_a_win() {
+        let mut session = Session::new(rng::seeded(3), Rules::default());
+        session.give_up();
+        assert_eq!(session.tally().rounds_played, 1);
+        assert_eq!(session.tally().average_attempts(), None);
+    }
+}
@@ -0,0 +1,164 @@
+// A player that never wastes a guess: it keeps the interval the secret must be in and always guesses its middle, so every answer halves what's left.
+
+use crate::bounds::Bounds;
+use crate::difficulty::Rules;
+use crate::game::{Game, Outcome};
+
+/// Anything that answers guesses the way a `Game` does, so the solver can play against it.
+pub trait Host {
+    /// The range the secret lies in.
+    fn bounds(&self) -> Bounds;
+    fn guess(&mut self, guess: i128) -> Outcome;
+    fn is_finished(&self) -> bool;
+}
+
+impl Host for Game {
+    fn bounds(&self) -> Bounds {
+        Game::bounds(self)
+    }
+
+    fn guess(&mut self, guess: i128) -> Outcome {
+        Game::guess(self, guess)
+    }
+
+    fn is_finished(&self) -> bool {
+        Game::is_finished(self)
+    }
+}
+
+/// One guess made by the solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// The interval the solver knew the secret was in before guessing.
+    pub low: i128,
+    pub high: i128,
+    pub guess: i128,
+    pub outcome: Outcome,
+}
+
+/// The middle of `low..=high`, rounded down.
+///
+/// `(low + high) / 2` would overflow near the limits of i128; taking the bits both share plus half the bits that differ never does.
+pub fn midpoint(low: i128, high: i128) -> i128 {
+    (low & high) + ((low ^ high) >> 1)
+}
+
+/// Plays `game` (a `Game`, or any other `Host`) to the end with binary search and returns every step taken.
+///
+/// Along the way it checks the engine: an answer that leaves no number possible, or needing more guesses than `floor(log2(max - min + 1)) + 1`, means the engine's Too small/Too big answers were inconsistent, and is returned as an error.
+pub fn autoplay<H: Host>(game: &mut H) -> Result<Vec<Step>, String> {
+    let bound = Rules::unlimited(game.bounds()).optimal_attempts();
+    let mut low = game.bounds().min();
+    let mut high = game.bounds().max();
+    let mut steps = Vec::new();
+
+    while !game.is_finished() {
+        if low > high {
+            return Err(format!(
+                "the answers so far leave no possible secret (after {} guesses)",
+                steps.len()
+            ));
+        }
+
+        let guess = midpoint(low, high);
+        let outcome = game.guess(guess);
+        steps.push(Step {
+            low,
+            high,
+            guess,
+            outcome,
+        });
+
+        // checked_add returns None on overflow, which only happens if the engine says the largest possible number is too small (or the smallest too big).
+        let narrowed = match outcome {
+            Outcome::TooSmall => guess.checked_add(1).map(|next| low = next),
+            Outcome::TooBig => guess.checked_sub(1).map(|next| high = next),
+            Outcome::Win => Some(()),
+            Outcome::Invalid(reason) => {
+                return Err(format!("the engine rejected guess {guess}: {reason}"));
+            }
+        };
+        if narrowed.is_none() {
+            return Err(format!(
+                "the engine answered {outcome:?} to {guess}, which no secret allows"
+            ));
+        }
+
+        if steps.len() as u32 > bound {
+            return Err(format!(
+                "binary search needed more than {bound} guesses, the most a range from {} to {} should take",
+                game.bounds().min(),
+                game.bounds().max()
+            ));
+        }
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A host that claims every guess is too small, which no secret allows.
+    struct Liar(Bounds);
+
+    impl Host for Liar {
+        fn bounds(&self) -> Bounds {
+            self.0
+        }
+
+        fn guess(&mut self, _guess: i128) -> Outcome {
+            Outcome::TooSmall
+        }
+
+        fn is_finished(&self) -> bool {
+            false
+        }
+    }
+
+    fn solve(bounds: Bounds, secret: i128) -> Vec<Step> {
+        let mut game = Game::new(Rules::unlimited(bounds), secret);
+        let steps = autoplay(&mut game).unwrap();
+        assert_eq!(steps.last().unwrap().guess, secret);
+        steps
+    }
+
+    #[test]
+    fn every_secret_is_found_within_the_bound() {
+        let bounds = Bounds::DEFAULT;
+        let bound = Rules::unlimited(bounds).optimal_attempts();
+        assert_eq!(bound, 7);
+        let most = (1..=100)
+            .map(|secret| solve(bounds, secret).len() as u32)
+            .max();
+        assert_eq!(most, Some(bound));
+    }
+
+    #[test]
+    fn the_limits_of_i128_are_reachable() {
+        let bounds = Bounds::new(i128::MIN, i128::MAX).unwrap();
+        assert_eq!(Rules::unlimited(bounds).optimal_attempts(), 129);
+        for secret in [i128::MIN, -1, 0, i128::MAX] {
+            assert!(solve(bounds, secret).len() <= 129);
+        }
+    }
+
+    #[test]
+    fn midpoint_does_not_overflow() {
+        assert_eq!(midpoint(1, 100), 50);
+        assert_eq!(midpoint(-3, -2), -3);
+        assert_eq!(midpoint(i128::MAX - 1, i128::MAX), i128::MAX - 1);
+        assert_eq!(midpoint(i128::MIN, i128::MAX), -1);
+    }
+
+    #[test]
+    fn inconsistent_answers_are_an_error() {
+        let error = autoplay(&mut Liar(Bounds::DEFAULT)).unwrap_err();
+        assert!(error.contains("leave no possible secret"), "{error}");
+
+        let bounds = Bounds::new(i128::MAX - 1, i128::MAX).unwrap();
+        let error = autoplay(&mut Liar(bounds)).unwrap_err();
+        assert!(error.contains("which no secret allows"), "{error}");
+    }
+}
@@ -0,0 +1,255 @@
+// Ways to play the guessing game, each in a few lines, so they can be compared under the same rules.
+//
+// A strategy sees only what a player sees: the range, and each earlier guess with the `Ordering` it got (`guess.cmp(&secret)`, so Greater means Too big).
+// From that it picks the next guess. The numbers still possible are worked out from that history with `narrow`, one answer at a time; `play` carries them
+// forward so no strategy has to scan the whole history again every turn, which would make a long game quadratic.
+
+use std::cmp::Ordering;
+
+use rand::Rng;
+
+use crate::bounds::Bounds;
+use crate::game::Game;
+use crate::rng::GameRng;
+use crate::solver;
+
+/// Something that can play the guessing game.
+pub trait Strategy {
+    /// The short name the tournament reports it under.
+    fn name(&self) -> &'static str;
+
+    /// The next guess, given every guess so far this round with how it compared with the secret, and `remaining`: the `(low, high)` that `feasible` works out from them.
+    fn next_guess(&mut self, remaining: (i128, i128), history: &[(i128, Ordering)]) -> i128;
+}
+
+/// What's left of `remaining` once `guess` got `ordering`. If the answers contradict each other, low ends up above high.
+pub fn narrow(remaining: (i128, i128), guess: i128, ordering: Ordering) -> (i128, i128) {
+    let (low, high) = remaining;
+    // A guess that was too small rules out itself and everything below it; saturating keeps the limits of i128 from overflowing.
+    match ordering {
+        Ordering::Less => (low.max(guess.saturating_add(1)), high),
+        Ordering::Greater => (low, high.min(guess.saturating_sub(1))),
+        Ordering::Equal => (guess, guess),
+    }
+}
+
+/// The numbers still consistent with `history`, as `(low, high)`: `bounds` narrowed by every answer in turn.
+pub fn feasible(bounds: Bounds, history: &[(i128, Ordering)]) -> (i128, i128) {
+    history.iter().fold(
+        (bounds.min(), bounds.max()),
+        |remaining, &(guess, ordering)| narrow(remaining, guess, ordering),
+    )
+}
+
+/// Always guesses the middle of what's left, halving it with every answer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinarySearch;
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+
+    fn next_guess(&mut self, remaining: (i128, i128), _: &[(i128, Ordering)]) -> i128 {
+        let (low, high) = remaining;
+        solver::midpoint(low, high)
+    }
+}
+
+/// Guesses any number still possible, picked at random. It never wastes a guess, but it only halves what's left on average.
+#[derive(Debug, Clone)]
+pub struct RandomInInterval {
+    rng: GameRng,
+}
+
+impl RandomInInterval {
+    pub fn new(rng: GameRng) -> RandomInInterval {
+        RandomInInterval { rng }
+    }
+}
+
+impl Strategy for RandomInInterval {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn next_guess(&mut self, remaining: (i128, i128), _: &[(i128, Ordering)]) -> i128 {
+        let (low, high) = remaining;
+        if low > high {
+            return low;
+        }
+        self.rng.gen_range(low..=high)
+    }
+}
+
+/// Splits what's left unevenly, guessing `numerator / denominator` of the way up it. One third makes the ternary split.
+#[derive(Debug, Clone, Copy)]
+pub struct Skewed {
+    numerator: u32,
+    denominator: u32,
+}
+
+impl Skewed {
+    /// A split `numerator / denominator` of the way up, which must be a fraction between 0 and 1.
+    pub fn new(numerator: u32, denominator: u32) -> Skewed {
+        assert!(
+            numerator < denominator,
+            "the split must be a fraction below 1"
+        );
+        Skewed {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub fn ternary() -> Skewed {
+        Skewed::new(1, 3)
+    }
+}
+
+impl Strategy for Skewed {
+    fn name(&self) -> &'static str {
+        match (self.numerator, self.denominator) {
+            (1, 3) => "ternary",
+            _ => "skewed",
+        }
+    }
+
+    fn next_guess(&mut self, remaining: (i128, i128), _: &[(i128, Ordering)]) -> i128 {
+        let (low, high) = remaining;
+        if low >= high {
+            return low;
+        }
+        // width * numerator / denominator could overflow a u128, so the whole multiples of denominator are divided out first.
+        let width = high.abs_diff(low);
+        let (numerator, denominator) = (self.numerator as u128, self.denominator as u128);
+        let offset =
+            width / denominator * numerator + width % denominator * numerator / denominator;
+        // offset <= width, so low + offset lands inside low..=high; adding through u128 can't overflow the way i128 might.
+        (low as u128).wrapping_add(offset) as i128
+    }
+}
+
+/// Guesses the smallest number still possible, counting up one at a time: the worst strategy that never wastes a guess.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Linear;
+
+impl Strategy for Linear {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn next_guess(&mut self, remaining: (i128, i128), _: &[(i128, Ordering)]) -> i128 {
+        remaining.0
+    }
+}
+
+/// The names `by_name` knows, in the order the tournament plays them by default.
+pub const NAMES: [&str; 4] = ["binary", "ternary", "random", "linear"];
+
+/// Builds the strategy called `name`. `rng` is only used by strategies that make random choices.
+pub fn by_name(name: &str, rng: GameRng) -> Result<Box<dyn Strategy>, String> {
+    match name.trim() {
+        "binary" => Ok(Box::new(BinarySearch)),
+        "ternary" => Ok(Box::new(Skewed::ternary())),
+        "random" => Ok(Box::new(RandomInInterval::new(rng))),
+        "linear" => Ok(Box::new(Linear)),
+        other => Err(format!(
+            "unknown strategy '{other}' (expected {})",
+            NAMES.join(", ")
+        )),
+    }
+}
+
+/// Lets `strategy` play `game` until the round is over or it has made `max_guesses` guesses, in which case it gives up.
+///
+/// A guess the game rejects (one outside the range, say) ends the round as given up too, since the strategy would only make it again.
+pub fn play(strategy: &mut dyn Strategy, game: &mut Game, max_guesses: u32) {
+    let mut history = Vec::new();
+    let mut remaining = feasible(game.bounds(), &history);
+    while !game.is_finished() {
+        if history.len() as u32 >= max_guesses {
+            game.give_up();
+            break;
+        }
+        let guess = strategy.next_guess(remaining, &history);
+        match game.guess(guess).ordering() {
+            Some(ordering) => {
+                history.push((guess, ordering));
+                remaining = narrow(remaining, guess, ordering);
+            }
+            None => game.give_up(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Rules;
+    use crate::game::Status;
+
+    fn bounds(min: i128, max: i128) -> Bounds {
+        Bounds::new(min, max).unwrap()
+    }
+
+    #[test]
+    fn feasible_keeps_only_what_every_answer_allows() {
+        let range = bounds(1, 100);
+        assert_eq!(feasible(range, &[]), (1, 100));
+        let history = [(50, Ordering::Greater), (20, Ordering::Less)];
+        assert_eq!(feasible(range, &history), (21, 49));
+        assert_eq!(feasible(range, &[(37, Ordering::Equal)]), (37, 37));
+        // Answers that contradict each other leave low above high.
+        let history = [(50, Ordering::Less), (40, Ordering::Greater)];
+        let (low, high) = feasible(range, &history);
+        assert!(low > high);
+    }
+
+    #[test]
+    fn narrowing_saturates_at_the_limits_of_i128() {
+        let everything = (i128::MIN, i128::MAX);
+        assert_eq!(
+            narrow(everything, i128::MAX, Ordering::Less),
+            (i128::MAX, i128::MAX)
+        );
+        assert_eq!(
+            narrow(everything, i128::MIN, Ordering::Greater),
+            (i128::MIN, i128::MIN)
+        );
+    }
+
+    #[test]
+    fn skewed_guesses_its_fraction_of_the_way_up() {
+        let mut ternary = Skewed::ternary();
+        assert_eq!(ternary.next_guess((1, 100), &[]), 34);
+        assert_eq!(ternary.next_guess((10, 10), &[]), 10);
+        assert_eq!(ternary.next_guess((0, 2), &[]), 0);
+        assert_eq!(Skewed::new(2, 3).next_guess((0, 2), &[]), 1);
+        // The widest range there is: width * 2 would overflow a u128, but the offset is worked out without it.
+        let guess = Skewed::new(1, 2).next_guess((i128::MIN, i128::MAX), &[]);
+        assert_eq!(guess, -1);
+    }
+
+    #[test]
+    fn play_wins_with_every_strategy_and_gives_up_at_the_limit() {
+        let rules = Rules {
+            bounds: bounds(1, 100),
+            max_attempts: None,
+        };
+        for name in NAMES {
+            let mut strategy = by_name(name, crate::rng::seeded(1)).unwrap();
+            for secret in [1, 42, 100] {
+                let mut game = Game::new(rules, secret);
+                play(strategy.as_mut(), &mut game, 1000);
+                assert_eq!(game.status(), Status::Won, "{name} against {secret}");
+                assert_eq!(game.redundant_guesses(), 0, "{name} against {secret}");
+            }
+        }
+
+        let mut game = Game::new(rules, 100);
+        play(&mut Linear, &mut game, 5);
+        assert_eq!(game.attempts(), 5);
+        assert_eq!(game.status(), Status::GaveUp);
+    }
+}
@@ -0,0 +1,178 @@
+// Plays every strategy against the same long list of secrets, under the game's real rules, and sums up how each one did.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::difficulty::Rules;
+use crate::game::{Game, Status};
+use crate::rng;
+use crate::strategy::{self, Strategy};
+
+/// How many guesses a strategy gets in one game when the rules don't limit them, so a slow strategy on a wide range still finishes.
+pub const MAX_GUESSES: u32 = 10_000;
+
+/// How one strategy did over the whole tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: &'static str,
+    /// The attempts each won game took, smallest first.
+    pub attempts: Vec<u32>,
+    /// Games that ran out of attempts (or hit `MAX_GUESSES`).
+    pub losses: u32,
+    /// Guesses that earlier answers had already ruled out, over every game.
+    pub redundant: u32,
+    pub total_score: u64,
+}
+
+impl Standing {
+    pub fn games(&self) -> u32 {
+        self.attempts.len() as u32 + self.losses
+    }
+
+    pub fn wins(&self) -> u32 {
+        self.attempts.len() as u32
+    }
+
+    /// The mean attempts per win, or `None` without a win.
+    pub fn mean(&self) -> Option<f64> {
+        if self.attempts.is_empty() {
+            return None;
+        }
+        let total: u64 = self.attempts.iter().map(|&attempts| attempts as u64).sum();
+        Some(total as f64 / self.attempts.len() as f64)
+    }
+
+    /// The median attempts per win; with an even number of wins, the mean of the middle two.
+    pub fn median(&self) -> Option<f64> {
+        let n = self.attempts.len();
+        match n {
+            0 => None,
+            _ if n % 2 == 1 => Some(self.attempts[n / 2] as f64),
+            _ => Some((self.attempts[n / 2 - 1] + self.attempts[n / 2]) as f64 / 2.0),
+        }
+    }
+
+    /// The most attempts any win took.
+    pub fn worst(&self) -> Option<u32> {
+        self.attempts.last().copied()
+    }
+
+    /// How many wins took a number of attempts within `range`.
+    pub fn wins_within(&self, range: &RangeInclusive<u32>) -> usize {
+        self.attempts
+            .iter()
+            .filter(|attempts| range.contains(attempts))
+            .count()
+    }
+}
+
+/// Plays `games` games with each strategy. The secrets are drawn from `seed` once, so every strategy faces the same ones, in the same order.
+pub fn run(
+    strategies: &mut [Box<dyn Strategy>],
+    rules: Rules,
+    games: u32,
+    seed: u64,
+) -> Vec<Standing> {
+    let mut rng = rng::seeded(seed);
+    let secrets: Vec<i128> = (0..games)
+        .map(|_| rng.gen_range(rules.bounds.min()..=rules.bounds.max()))
+        .collect();
+
+    strategies
+        .iter_mut()
+        .map(|strategy| {
+            let mut standing = Standing {
+                name: strategy.name(),
+                attempts: Vec::new(),
+                losses: 0,
+                redundant: 0,
+                total_score: 0,
+            };
+            for &secret in &secrets {
+                let mut game = Game::new(rules, secret);
+                strategy::play(strategy.as_mut(), &mut game, MAX_GUESSES);
+                match game.status() {
+                    Status::Won => standing.attempts.push(game.attempts()),
+                    _ => standing.losses += 1,
+                }
+                standing.redundant += game.redundant_guesses();
+                standing.total_score += game.score() as u64;
+            }
+            standing.attempts.sort_unstable();
+            standing
+        })
+        .collect()
+}
+
+/// Groups attempt counts from 1 to the worst any strategy needed into at most `rows` ranges of equal width, for a table with a row per range.
+pub fn buckets(standings: &[Standing], rows: u32) -> Vec<RangeInclusive<u32>> {
+    let worst = standings
+        .iter()
+        .filter_map(Standing::worst)
+        .max()
+        .unwrap_or(0);
+    if worst == 0 {
+        return Vec::new();
+    }
+    let width = worst.div_ceil(rows.max(1));
+    (0..worst.div_ceil(width))
+        .map(|row| row * width + 1..=((row + 1) * width).min(worst))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standing(attempts: &[u32]) -> Standing {
+        Standing {
+            name: "test",
+            attempts: attempts.to_vec(),
+            losses: 0,
+            redundant: 0,
+            total_score: 0,
+        }
+    }
+
+    #[test]
+    fn the_median_is_the_middle_win_or_the_mean_of_the_middle_two() {
+        assert_eq!(standing(&[]).median(), None);
+        assert_eq!(standing(&[4]).median(), Some(4.0));
+        assert_eq!(standing(&[1, 2, 9]).median(), Some(2.0));
+        assert_eq!(standing(&[1, 2, 3, 9]).median(), Some(2.5));
+    }
+
+    #[test]
+    fn buckets_cover_every_attempt_count_up_to_the_worst() {
+        assert!(buckets(&[standing(&[])], 5).is_empty());
+        assert_eq!(
+            buckets(&[standing(&[1, 3]), standing(&[7])], 3),
+            vec![1..=3, 4..=6, 7..=7]
+        );
+        // Fewer attempt counts than rows get a row each.
+        assert_eq!(buckets(&[standing(&[2])], 10), vec![1..=1, 2..=2]);
+        // Zero rows is taken as one.
+        assert_eq!(buckets(&[standing(&[5])], 0), vec![1..=5]);
+    }
+
+    #[test]
+    fn every_strategy_faces_the_same_secrets() {
+        let rules = Rules {
+            bounds: crate::bounds::Bounds::new(1, 1000).unwrap(),
+            max_attempts: None,
+        };
+        let mut strategies = vec![
+            strategy::by_name("binary", rng::seeded(1)).unwrap(),
+            strategy::by_name("linear", rng::seeded(1)).unwrap(),
+        ];
+        let standings = run(&mut strategies, rules, 50, 7);
+        assert!(standings.iter().all(|standing| standing.wins() == 50));
+        assert!(standings[0].worst() <= Some(rules.optimal_attempts()));
+        // Linear needs exactly as many guesses as the secret, so its total is the sum of the secrets.
+        let secrets: u32 = standings[1].attempts.iter().sum();
+        let mut rng = rng::seeded(7);
+        let expected: i128 = (0..50).map(|_| rng.gen_range(1..=1000i128)).sum();
+        assert_eq!(secrets as i128, expected);
+    }
+}
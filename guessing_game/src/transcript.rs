@@ -0,0 +1,480 @@
+// A transcript is everything needed to play a session again: the seed and rules it started with, every line the player typed, and every message the game answered with.
+//
+// The file is JSON lines. The first line is the header:
+//   {"transcript":"guessing_game","version":4,"started_at":1700000000,"seed":42,"difficulty":"normal","min":1,"max":100,
+//    "max_attempts":10,"hints":"direction","thresholds":null,"reveal":false,"protocol":"text",
+//    "redundant":"warn","undos":1}
+// where hints is direction or hot-cold, thresholds is [burning, warm] for hot-cold, protocol says how to read the input lines,
+// redundant is allow, warn or free (transcripts from before it existed played as warn), and undos is how many guesses :undo could take back per round
+// (the difficulty's default when it's missing).
+// Every other line is one event, stamped with milliseconds since the session started:
+//   {"at":1530,"input":"50"}                                   a line the player typed, without its newline
+//   {"at":1531,"output":{"type":"outcome","guess":50,...}}     a message, in the shape of the JSON lines protocol (see protocol.rs)
+//
+// Outputs are compared as JSON, so a transcript recorded in text mode replays the same as one recorded with --protocol jsonl.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::bounds::Bounds;
+use crate::difficulty::{Difficulty, Rules};
+use crate::game::Redundancy;
+use crate::hints::{Feedback, Thresholds};
+use crate::json::{self, Value};
+use crate::protocol::Protocol;
+
+/// Bumped whenever the header or an event changes shape. Older versions are still read, with defaults for what they lack.
+///
+/// 2 added "redundant" to the header, and 3 added "undos". 4 changed no shape but the generator the seed feeds, so the same seed deals other secrets than it did before.
+pub const FORMAT_VERSION: u32 = 4;
+
+/// The first version whose seed still deals the same secrets in this build.
+pub const SAME_SECRETS_SINCE: u32 = 4;
+
+/// The settings a session was played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// Seconds since the Unix epoch when recording started.
+    pub started_at: u64,
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub rules: Rules,
+    pub feedback: Feedback,
+    pub reveal: bool,
+    /// How the player's input lines were written.
+    pub protocol: Protocol,
+    pub redundancy: Redundancy,
+    pub undos: u32,
+}
+
+impl Header {
+    pub fn to_json(&self) -> Value {
+        let (hints, thresholds) = match self.feedback {
+            Feedback::Direction => ("direction", None),
+            Feedback::HotCold(thresholds) => {
+                ("hot-cold", Some(vec![thresholds.burning, thresholds.warm]))
+            }
+        };
+        Value::object()
+            .with("transcript", "guessing_game")
+            .with("version", FORMAT_VERSION)
+            .with("started_at", self.started_at)
+            .with("seed", self.seed)
+            .with("difficulty", self.difficulty.name())
+            .with("min", self.rules.bounds.min())
+            .with("max", self.rules.bounds.max())
+            .with("max_attempts", self.rules.max_attempts)
+            .with("hints", hints)
+            .with("thresholds", thresholds)
+            .with("reveal", self.reveal)
+            .with("protocol", self.protocol.name())
+            .with("redundant", self.redundancy.name())
+            .with("undos", self.undos)
+    }
+
+    pub fn from_json(value: &Value) -> Result<Header, String> {
+        if value.get("transcript").and_then(Value::as_str) != Some("guessing_game") {
+            return Err("this isn't a guessing_game transcript".to_string());
+        }
+        let version: u32 = number(value, "version")?;
+        if !(1..=FORMAT_VERSION).contains(&version) {
+            return Err(format!(
+                "this transcript is version {version}, but this build reads versions 1 to {FORMAT_VERSION}"
+            ));
+        }
+
+        let feedback = match value.get("hints").and_then(Value::as_str) {
+            Some("direction") => Feedback::Direction,
+            Some("hot-cold") => {
+                let values = match value.get("thresholds") {
+                    Some(Value::Array(values)) if values.len() == 2 => values,
+                    _ => return Err("hot-cold needs two \"thresholds\"".to_string()),
+                };
+                let percent = |value: &Value| {
+                    value
+                        .as_number()
+                        .and_then(|digits| digits.parse().ok())
+                        .ok_or_else(|| "the thresholds must be whole numbers".to_string())
+                };
+                Feedback::HotCold(Thresholds::new(percent(&values[0])?, percent(&values[1])?)?)
+            }
+            _ => return Err("\"hints\" must be direction or hot-cold".to_string()),
+        };
+
+        let max_attempts = match value.get("max_attempts") {
+            Some(Value::Null) => None,
+            _ => Some(number(value, "max_attempts")?),
+        };
+
+        let difficulty: Difficulty = value
+            .get("difficulty")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "the header has no \"difficulty\"".to_string())?
+            .parse()?;
+        let undos = match value.get("undos") {
+            None => difficulty.undos(),
+            Some(_) => number(value, "undos")?,
+        };
+
+        Ok(Header {
+            started_at: number(value, "started_at")?,
+            seed: number(value, "seed")?,
+            difficulty,
+            rules: Rules {
+                bounds: Bounds::new(number(value, "min")?, number(value, "max")?)?,
+                max_attempts,
+            },
+            feedback,
+            reveal: value
+                .get("reveal")
+                .and_then(Value::as_bool)
+                .ok_or_else(|| "the header has no \"reveal\"".to_string())?,
+            protocol: value
+                .get("protocol")
+                .and_then(Value::as_str)
+                .ok_or_else(|| "the header has no \"protocol\"".to_string())?
+                .parse()?,
+            redundancy: match value.get("redundant") {
+                None => Redundancy::Warn,
+                Some(redundant) => redundant
+                    .as_str()
+                    .ok_or_else(|| "\"redundant\" must be a string".to_string())?
+                    .parse()?,
+            },
+            undos,
+        })
+    }
+}
+
+// Reads a member holding a whole number.
+fn number<T: std::str::FromStr>(value: &Value, key: &str) -> Result<T, String> {
+    value
+        .get(key)
+        .and_then(Value::as_number)
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| format!("\"{key}\" is missing or not a whole number in range"))
+}
+
+/// One thing that happened during a session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A line the player typed.
+    Input { at: u64, line: String },
+    /// A message the game sent, as JSON.
+    Output { at: u64, message: Value },
+}
+
+impl Event {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Event::Input { at, line } => {
+                Value::object().with("at", *at).with("input", line.as_str())
+            }
+            Event::Output { at, message } => Value::object()
+                .with("at", *at)
+                .with("output", message.clone()),
+        }
+    }
+
+    pub fn from_json(value: &Value) -> Result<Event, String> {
+        let at = number(value, "at")?;
+        match (value.get("input"), value.get("output")) {
+            (Some(Value::String(line)), None) => Ok(Event::Input {
+                at,
+                line: line.clone(),
+            }),
+            (None, Some(message @ Value::Object(_))) => Ok(Event::Output {
+                at,
+                message: message.clone(),
+            }),
+            _ => {
+                Err("an event needs either an \"input\" string or an \"output\" object".to_string())
+            }
+        }
+    }
+}
+
+/// Writes a transcript as the session goes, one line per event, so a crash loses nothing that came before it.
+#[derive(Debug)]
+pub struct Recorder {
+    file: File,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Creates (or overwrites) the transcript at `path` and writes its header.
+    pub fn create(path: &Path, header: &Header) -> io::Result<Recorder> {
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", header.to_json())?;
+        Ok(Recorder {
+            file,
+            started: Instant::now(),
+        })
+    }
+
+    /// Seconds since the Unix epoch, for the header's `started_at`.
+    pub fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs())
+    }
+
+    pub fn input(&mut self, line: &str) -> io::Result<()> {
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        self.write(Event::Input {
+            at: self.elapsed(),
+            line,
+        })
+    }
+
+    pub fn output(&mut self, message: Value) -> io::Result<()> {
+        self.write(Event::Output {
+            at: self.elapsed(),
+            message,
+        })
+    }
+
+    fn elapsed(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    // One write per line, like the round records, so lines are never interleaved half-written.
+    fn write(&mut self, event: Event) -> io::Result<()> {
+        self.file
+            .write_all(format!("{}\n", event.to_json()).as_bytes())
+    }
+}
+
+/// The first message where a replay and its transcript part ways.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// The message's position, starting at 0.
+    pub index: usize,
+    /// How many input lines had been read before it.
+    pub read: usize,
+    /// The message as recorded, or `None` if the recording had ended.
+    pub recorded: Option<Value>,
+    /// The message the replay sent instead, or `None` if it sent no more.
+    pub now: Option<Value>,
+}
+
+/// A transcript read back from disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    /// The format version the header was written in.
+    pub version: u32,
+    pub header: Header,
+    pub events: Vec<Event>,
+}
+
+impl Transcript {
+    /// Reads a transcript. Unlike the round records, a damaged line is an error: a replay with a line missing would diverge for no real reason.
+    pub fn load(path: &Path) -> Result<Transcript, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("can't read {}: {error}", path.display()))?;
+        Transcript::parse(&text).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    /// Reads a transcript from the text of its file.
+    pub fn parse(text: &str) -> Result<Transcript, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let parse = |number: usize, line: &str| {
+            json::parse(line).map_err(|error| format!("line {}: {error}", number + 1))
+        };
+        let (number, first) = lines
+            .next()
+            .ok_or_else(|| "the transcript is empty".to_string())?;
+        let first = parse(number, first)?;
+        let header =
+            Header::from_json(&first).map_err(|error| format!("line {}: {error}", number + 1))?;
+        // from_json has already checked it.
+        let version = first
+            .get("version")
+            .and_then(Value::as_number)
+            .and_then(|digits| digits.parse().ok())
+            .unwrap_or(FORMAT_VERSION);
+
+        let mut events = Vec::new();
+        for (number, line) in lines {
+            let event = Event::from_json(&parse(number, line)?)
+                .map_err(|error| format!("line {}: {error}", number + 1))?;
+            events.push(event);
+        }
+        Ok(Transcript {
+            version,
+            header,
+            events,
+        })
+    }
+
+    /// The lines the player typed, in order.
+    pub fn inputs(&self) -> Vec<String> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Input { line, .. } => Some(line.clone()),
+                Event::Output { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Compares the messages a replay produced with the recorded ones, and returns the first that differs, if any.
+    pub fn divergence(&self, now: &[Value]) -> Option<Divergence> {
+        let expected = self.outputs();
+        let inputs = self.inputs().len();
+        (0..expected.len().max(now.len())).find_map(|index| {
+            // A message the replay added past the end of the recording came after every input line.
+            let (read, recorded) = match expected.get(index) {
+                Some(&(read, message)) => (read, Some(message)),
+                None => (inputs, None),
+            };
+            let is = now.get(index);
+            (recorded != is).then(|| Divergence {
+                index,
+                read,
+                recorded: recorded.cloned(),
+                now: is.cloned(),
+            })
+        })
+    }
+
+    /// The messages the game sent, each with how many input lines had been read before it.
+    pub fn outputs(&self) -> Vec<(usize, &Value)> {
+        let mut inputs = 0;
+        let mut outputs = Vec::new();
+        for event in &self.events {
+            match event {
+                Event::Input { .. } => inputs += 1,
+                Event::Output { message, .. } => outputs.push((inputs, message)),
+            }
+        }
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> Header {
+        Header {
+            started_at: 1_700_000_000,
+            seed: 42,
+            difficulty: Difficulty::Normal,
+            rules: Difficulty::Normal.rules(),
+            feedback: Feedback::Direction,
+            reveal: false,
+            protocol: Protocol::Text,
+            redundancy: Redundancy::Free,
+            undos: 1,
+        }
+    }
+
+    fn outcome(guess: i128, result: &str) -> Value {
+        Value::object()
+            .with("type", "outcome")
+            .with("guess", guess)
+            .with("result", result)
+    }
+
+    // A transcript of two guesses, each answered.
+    fn transcript() -> Transcript {
+        let events = vec![
+            Event::Input {
+                at: 10,
+                line: "50".to_string(),
+            },
+            Event::Output {
+                at: 11,
+                message: outcome(50, "too_big"),
+            },
+            Event::Input {
+                at: 20,
+                line: "25".to_string(),
+            },
+            Event::Output {
+                at: 21,
+                message: outcome(25, "win"),
+            },
+        ];
+        let mut text = format!("{}\n", header().to_json());
+        for event in &events {
+            text.push_str(&format!("{}\n", event.to_json()));
+        }
+        Transcript::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn a_transcript_reads_back_what_was_written() {
+        let transcript = transcript();
+        assert_eq!(transcript.header, header());
+        assert_eq!(transcript.inputs(), ["50", "25"]);
+        assert_eq!(transcript.outputs()[1], (2, &outcome(25, "win")));
+    }
+
+    #[test]
+    fn a_replay_with_the_same_messages_does_not_diverge() {
+        let now = [outcome(50, "too_big"), outcome(25, "win")];
+        assert_eq!(transcript().divergence(&now), None);
+    }
+
+    #[test]
+    fn the_first_different_message_is_the_divergence() {
+        let now = [outcome(50, "too_big"), outcome(25, "too_small")];
+        assert_eq!(
+            transcript().divergence(&now),
+            Some(Divergence {
+                index: 1,
+                read: 2,
+                recorded: Some(outcome(25, "win")),
+                now: Some(outcome(25, "too_small")),
+            })
+        );
+    }
+
+    #[test]
+    fn missing_and_extra_messages_diverge_too() {
+        let fewer = transcript().divergence(&[outcome(50, "too_big")]).unwrap();
+        assert_eq!((fewer.index, fewer.read, fewer.now), (1, 2, None));
+
+        let extra = outcome(25, "win");
+        let more = [outcome(50, "too_big"), extra.clone(), extra.clone()];
+        let more = transcript().divergence(&more).unwrap();
+        assert_eq!((more.index, more.read), (2, 2));
+        assert_eq!((more.recorded, more.now), (None, Some(extra)));
+    }
+
+    #[test]
+    fn damaged_transcripts_are_refused() {
+        assert!(Transcript::parse("").is_err());
+        let error =
+            Transcript::parse(&format!("{}\n{{\"at\":1}}", header().to_json())).unwrap_err();
+        assert!(error.starts_with("line 2:"), "{error}");
+
+        let newer = header().to_json().to_string().replace(
+            &format!("\"version\":{FORMAT_VERSION}"),
+            &format!("\"version\":{}", FORMAT_VERSION + 1),
+        );
+        assert!(Transcript::parse(&newer).is_err());
+    }
+
+    #[test]
+    fn older_headers_get_defaults_for_what_they_lack() {
+        let mut old = header().to_json();
+        if let Value::Object(members) = &mut old {
+            members.retain(|(key, _)| key != "redundant" && key != "undos");
+            members[1].1 = Value::from(1u32);
+        }
+        let read = Header::from_json(&old).unwrap();
+        assert_eq!(read.redundancy, Redundancy::Warn);
+        assert_eq!(read.undos, Difficulty::Normal.undos());
+        assert_eq!(Transcript::parse(&old.to_string()).unwrap().version, 1);
+        assert_eq!(transcript().version, FORMAT_VERSION);
+    }
+}
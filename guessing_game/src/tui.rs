@@ -0,0 +1,635 @@
+// A full-screen way to play: the whole state of the round is redrawn after every key instead of scrolling past as lines of text.
+//
+//   Guess the number! Round 2 (normal, 1 to 100)        won 1 of 1, score 1000
+//
+//   Still possible: 26 to 50
+//   [.........................#########################.........................................]
+//   Attempts: 2 used, 8 left
+//
+//   History:
+//      50  v Too big!
+//      25  ^ Too small!
+//
+//   Your guess: 37_
+//   Too small!
+//   Enter: guess   n: new round   g: give up   q: quit
+//
+// Every word on screen comes from the Catalog, in the language the player chose; the keys stay n, g and q in all of them.
+//
+// Drawing goes into a `Screen`, a grid of characters in memory, and only `Terminal` ever touches the real terminal.
+// That keeps the layout easy to check without one: render into a Screen and compare its lines.
+//
+// The standard library has no way to switch a terminal out of line mode, so `Terminal` borrows `stty` for that and uses ANSI escape codes for the rest.
+// In raw mode Ctrl-C is just another key rather than a signal, which lets the game quit and restore the terminal the same way it does for `q`.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::panic;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, Outcome, Redundancy, Status};
+use crate::locale::Catalog;
+use crate::protocol::Message;
+use crate::session::Session;
+use crate::Difficulty;
+
+/// The size, in columns and rows, assumed when stty can't tell us the real one.
+pub const DEFAULT_SIZE: (usize, usize) = (80, 24);
+
+/// A grid of characters standing in for the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screen {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+}
+
+impl Screen {
+    /// A blank screen `width` columns wide and `height` rows tall.
+    pub fn new(width: usize, height: usize) -> Screen {
+        Screen {
+            width,
+            height,
+            cells: vec![' '; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Writes `text` starting at `row` and `column`, cutting off whatever doesn't fit.
+    pub fn put(&mut self, row: usize, column: usize, text: &str) {
+        if row >= self.height {
+            return;
+        }
+        for (offset, c) in text.chars().enumerate() {
+            let column = column + offset;
+            if column >= self.width {
+                break;
+            }
+            self.cells[row * self.width + column] = c;
+        }
+    }
+
+    /// The text of one row, without trailing spaces.
+    pub fn line(&self, row: usize) -> String {
+        let start = row * self.width;
+        self.cells[start..start + self.width]
+            .iter()
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.height).map(|row| self.line(row)).collect()
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.lines().join("\n"))
+    }
+}
+
+/// A key press, as far as the game cares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Backspace,
+    Enter,
+    /// Ctrl-C, which raw mode delivers as a key instead of a signal.
+    Interrupt,
+    /// Ctrl-D, or stdin closing.
+    EndOfInput,
+}
+
+impl Key {
+    /// Decodes one byte read from a terminal in raw mode. Bytes that aren't printable ASCII or one of the keys above are ignored.
+    pub fn from_byte(byte: u8) -> Option<Key> {
+        match byte {
+            3 => Some(Key::Interrupt),
+            4 => Some(Key::EndOfInput),
+            b'\r' | b'\n' => Some(Key::Enter),
+            // Terminals disagree on whether backspace sends DEL or BS.
+            8 | 127 => Some(Key::Backspace),
+            b' '..=b'~' => Some(Key::Char(byte as char)),
+            _ => None,
+        }
+    }
+}
+
+/// What the driver should do after a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Quit,
+}
+
+/// A round that has ended, kept until the driver collects it with `Tui::finished_rounds`.
+#[derive(Debug, Clone)]
+pub struct FinishedRound {
+    pub number: u32,
+    pub game: Game,
+    pub duration: Duration,
+}
+
+/// The state of the full-screen game: the session, plus what only the screen needs to remember.
+#[derive(Debug, Clone)]
+pub struct Tui {
+    session: Session,
+    difficulty: Difficulty,
+    catalog: Catalog,
+    round: u32,
+    started: Instant,
+    input: String,
+    status: String,
+    finished: Vec<FinishedRound>,
+}
+
+impl Tui {
+    pub fn new(session: Session, difficulty: Difficulty, catalog: Catalog) -> Tui {
+        Tui {
+            session,
+            difficulty,
+            catalog,
+            round: 1,
+            started: Instant::now(),
+            input: String::new(),
+            status: catalog.text("tui.start", &[]),
+            finished: Vec::new(),
+        }
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// The number of the round on screen, counting from 1.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Takes the rounds that have ended since the last call: won, lost or given up with g. A round left by starting a new one or quitting isn't among them.
+    pub fn finished_rounds(&mut self) -> Vec<FinishedRound> {
+        std::mem::take(&mut self.finished)
+    }
+
+    /// Updates the state for one key press.
+    pub fn press(&mut self, key: Key) -> Flow {
+        let playing = !self.session.game().is_finished();
+        match key {
+            Key::Interrupt | Key::EndOfInput | Key::Char('q') => {
+                // The session still counts the round as given up in its tally.
+                self.session.give_up();
+                return Flow::Quit;
+            }
+            Key::Char('n') => {
+                self.session.new_round();
+                self.round += 1;
+                self.started = Instant::now();
+                self.input.clear();
+                self.status = self
+                    .catalog
+                    .text("tui.new_round", &[("round", self.round.to_string())]);
+            }
+            Key::Char('g') if playing => {
+                self.give_up();
+                self.input.clear();
+                let secret = self.catalog.number(self.session.game().secret());
+                self.status = self.catalog.text("tui.gave_up", &[("secret", secret)]);
+            }
+            Key::Char(c) if playing && (c.is_ascii_digit() || c == '-') => {
+                // 40 characters is enough for any i128, sign included.
+                if self.input.len() < 40 {
+                    self.input.push(c);
+                }
+            }
+            Key::Backspace => {
+                self.input.pop();
+            }
+            Key::Enter if playing => return self.submit(),
+            Key::Char(_) | Key::Enter => {
+                if !playing {
+                    self.status = self.catalog.text("tui.over", &[]);
+                }
+            }
+        }
+        Flow::Continue
+    }
+
+    fn submit(&mut self) -> Flow {
+        let input = std::mem::take(&mut self.input);
+        let outcome = self.session.submit(&input);
+        let game = self.session.game();
+        let catalog = &self.catalog;
+        // Rejected guesses and redundancy warnings read the same as in the line-by-line game.
+        let say = |message: Message| message.text(catalog).unwrap_or_default();
+        if let Outcome::Invalid(reason) = outcome {
+            self.status = say(Message::Rejected(reason));
+            return Flow::Continue;
+        }
+
+        self.status = match (outcome, game.status()) {
+            (Outcome::Win, _) => catalog.plural(
+                "tui.won",
+                game.attempts().into(),
+                &[
+                    ("attempts", catalog.number(game.attempts())),
+                    ("score", catalog.number(game.score())),
+                ],
+            ),
+            (_, Status::Lost) => {
+                catalog.text("tui.lost", &[("secret", catalog.number(game.secret()))])
+            }
+            (Outcome::TooSmall, _) => catalog.text("too_small", &[]),
+            _ => catalog.text("too_big", &[]),
+        };
+        if let (Redundancy::Warn, Some(redundant)) = (game.redundancy(), game.last_redundant()) {
+            self.status = format!("{} {}", self.status, say(Message::Warning(redundant)));
+        }
+        if game.is_finished() {
+            self.finish();
+        }
+        Flow::Continue
+    }
+
+    // Gives up the round on screen, if it's still going.
+    fn give_up(&mut self) {
+        if !self.session.game().is_finished() {
+            self.session.give_up();
+            self.finish();
+        }
+    }
+
+    fn finish(&mut self) {
+        self.finished.push(FinishedRound {
+            number: self.round,
+            game: self.session.game().clone(),
+            duration: self.started.elapsed(),
+        });
+    }
+
+    /// Draws everything onto `screen`, which should start out blank.
+    pub fn render(&self, screen: &mut Screen) {
+        let game = self.session.game();
+        let bounds = game.bounds();
+        let tally = self.session.tally();
+        let height = screen.height();
+        let catalog = &self.catalog;
+        let number = |n: i128| catalog.number(n);
+
+        let title = catalog.text(
+            "tui.title",
+            &[
+                ("round", self.round.to_string()),
+                (
+                    "difficulty",
+                    catalog.text(&format!("difficulty.{}", self.difficulty.name()), &[]),
+                ),
+                ("min", number(bounds.min())),
+                ("max", number(bounds.max())),
+            ],
+        );
+        let totals = catalog.text(
+            "tui.totals",
+            &[
+                ("won", number(tally.rounds_won.into())),
+                ("played", number(tally.rounds_played.into())),
+                ("score", number(tally.total_score.into())),
+            ],
+        );
+        screen.put(0, 0, &title);
+        // The totals go on the right, if there's room for them beside the title. Screen cells hold chars, so that's what gets counted, not bytes.
+        let (title_width, totals_width) = (title.chars().count(), totals.chars().count());
+        if title_width + 2 + totals_width <= screen.width() {
+            screen.put(0, screen.width() - totals_width, &totals);
+        }
+
+        let remaining = game.remaining();
+        let possible = if game.status() == Status::Won {
+            catalog.text("tui.secret", &[("secret", number(game.secret()))])
+        } else {
+            catalog.text(
+                "tui.possible",
+                &[
+                    ("low", number(*remaining.start())),
+                    ("high", number(*remaining.end())),
+                ],
+            )
+        };
+        screen.put(2, 0, &possible);
+        screen.put(3, 0, &bar(game, screen.width()));
+        let used = ("used", number(game.attempts().into()));
+        let attempts = match game.attempts_left() {
+            Some(left) => catalog.text(
+                "tui.attempts.limited",
+                &[used, ("left", number(left.into()))],
+            ),
+            None => catalog.text("tui.attempts.unlimited", &[used]),
+        };
+        screen.put(4, 0, &attempts);
+
+        // The three rows at the bottom are the prompt, the status line and the keys; the history gets whatever is left, and the oldest guesses scroll off first.
+        screen.put(6, 0, &catalog.text("tui.history", &[]));
+        let history = game.history();
+        let rows = height.saturating_sub(6 + 1 + 4);
+        let shown = history.len().min(rows);
+        let skipped = history.len() - shown;
+        for (row, &(guess, outcome)) in history[skipped..].iter().enumerate() {
+            let marker = match outcome {
+                Outcome::TooSmall => format!("^ {}", catalog.text("too_small", &[])),
+                Outcome::TooBig => format!("v {}", catalog.text("too_big", &[])),
+                Outcome::Win => format!("* {}", catalog.text("tui.correct", &[])),
+                Outcome::Invalid(_) => String::new(),
+            };
+            screen.put(7 + row, 0, &format!("{:>6}  {marker}", number(guess)));
+        }
+        if history.is_empty() {
+            screen.put(7, 0, &format!("  {}", catalog.text("tui.no_guesses", &[])));
+        }
+
+        let your_guess = catalog.text("tui.your_guess", &[]);
+        let prompt = if game.is_finished() {
+            format!("{your_guess} -")
+        } else {
+            format!("{your_guess} {}_", self.input)
+        };
+        screen.put(height.saturating_sub(3), 0, &prompt);
+        screen.put(height.saturating_sub(2), 0, &self.status);
+        screen.put(height.saturating_sub(1), 0, &catalog.text("tui.keys", &[]));
+    }
+}
+
+// The whole range drawn `width` characters wide, with # over the numbers still possible and . over those ruled out.
+fn bar(game: &Game, width: usize) -> String {
+    let cells = width.saturating_sub(2);
+    let bounds = game.bounds();
+    let remaining = game.remaining();
+    // Positions as fractions of the range, in f64 so ranges as wide as i128 can't overflow; a cell is filled if any of it is still possible.
+    let span = bounds.width() as f64 + 1.0;
+    let from = remaining.start().abs_diff(bounds.min()) as f64 / span;
+    let to = (remaining.end().abs_diff(bounds.min()) as f64 + 1.0) / span;
+    let mut bar = String::from("[");
+    for cell in 0..cells {
+        let start = cell as f64 / cells as f64;
+        let end = (cell + 1) as f64 / cells as f64;
+        bar.push(if start < to && from < end { '#' } else { '.' });
+    }
+    bar.push(']');
+    bar
+}
+
+// The type of a panic hook, as `panic::take_hook` returns it.
+type Hook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// The real terminal, switched to raw mode and a screen of its own for as long as this value lives.
+pub struct Terminal {
+    // What `stty -g` printed beforehand, which `stty` takes back to restore every setting.
+    saved: String,
+    // The panic hook that was there before ours, shared with ours so that it can still call it; Drop puts it back.
+    previous: Option<Arc<Hook>>,
+}
+
+impl fmt::Debug for Terminal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Terminal")
+            .field("saved", &self.saved)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Terminal {
+    /// Switches the terminal over, and arranges for a panic to switch it back before its message is printed.
+    pub fn enter() -> io::Result<Terminal> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        stty(&["raw", "-echo"])?;
+        // The alternate screen keeps the game from scrolling away what was on the terminal before; ?25l hides the cursor.
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+
+        let previous = Arc::new(panic::take_hook());
+        let chained = Arc::clone(&previous);
+        let restore = saved.clone();
+        panic::set_hook(Box::new(move |info| {
+            leave(&restore);
+            chained(info);
+        }));
+        Ok(Terminal {
+            saved,
+            previous: Some(previous),
+        })
+    }
+
+    /// The terminal's size as (columns, rows).
+    pub fn size(&self) -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let (rows, columns) = size.trim().split_once(' ')?;
+                Some((columns.parse().ok()?, rows.parse().ok()?))
+            })
+            .filter(|&(columns, rows)| columns > 0 && rows > 0)
+            .unwrap_or(DEFAULT_SIZE)
+    }
+
+    /// Replaces what's on the terminal with `screen`.
+    pub fn draw(&self, screen: &Screen) -> io::Result<()> {
+        // Raw mode turns off the translation of \n into \r\n, so each line goes back to the first column itself.
+        let mut frame = String::from("\x1b[H");
+        for (row, line) in screen.lines().iter().enumerate() {
+            if row > 0 {
+                frame.push_str("\r\n");
+            }
+            frame.push_str(line);
+            frame.push_str("\x1b[K");
+        }
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(frame.as_bytes())?;
+        stdout.flush()
+    }
+
+    /// Waits for the next key the game understands.
+    pub fn read_key(&self) -> io::Result<Key> {
+        let mut byte = [0];
+        loop {
+            if io::stdin().read(&mut byte)? == 0 {
+                return Ok(Key::EndOfInput);
+            }
+            if let Some(key) = Key::from_byte(byte[0]) {
+                return Ok(key);
+            }
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        leave(&self.saved);
+        // Hooks can't be changed while the thread is panicking; ours then stays, and restoring twice does no harm.
+        if thread::panicking() {
+            return;
+        }
+        // Dropping the hook installed by enter leaves the previous one with a single owner, so it can be put back.
+        drop(panic::take_hook());
+        if let Some(previous) = self.previous.take().and_then(Arc::into_inner) {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+// Undoes everything enter did. Errors are ignored: there's nowhere left to report them, and a half-restored terminal is still better than none.
+fn leave(saved: &str) {
+    print!("\x1b[?25h\x1b[?1049l");
+    let _ = io::stdout().flush();
+    let _ = stty(&[saved]);
+}
+
+// Runs stty against our own terminal, which it finds through stdin, and returns what it printed.
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("stty {} failed", args.join(" "))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Rules;
+    use crate::locale::Language;
+    use crate::rng;
+
+    fn tui() -> Tui {
+        let session = Session::new(rng::seeded(1), Rules::default());
+        Tui::new(session, Difficulty::Normal, Catalog::default())
+    }
+
+    fn type_line(tui: &mut Tui, line: &str) {
+        for c in line.chars() {
+            tui.press(Key::Char(c));
+        }
+        tui.press(Key::Enter);
+    }
+
+    fn render(tui: &Tui, width: usize, height: usize) -> Vec<String> {
+        let mut screen = Screen::new(width, height);
+        tui.render(&mut screen);
+        screen.lines()
+    }
+
+    #[test]
+    fn text_that_does_not_fit_is_cut_off() {
+        let mut screen = Screen::new(5, 2);
+        screen.put(0, 3, "abc");
+        screen.put(1, 0, "hi");
+        screen.put(2, 0, "gone");
+        assert_eq!(screen.to_string(), "   ab\nhi");
+    }
+
+    #[test]
+    fn draws_the_round_so_far() {
+        let mut tui = tui();
+        let secret = tui.session().game().secret();
+        type_line(&mut tui, &(secret - 1).to_string());
+        // Only digits and - can be typed, so this submits an empty line.
+        type_line(&mut tui, "x");
+        tui.press(Key::Char('9'));
+
+        let lines = render(&tui, 80, 24);
+        // The totals are right-aligned.
+        assert_eq!(
+            lines[0],
+            format!(
+                "{:<61}won 0 of 0, score 0",
+                "Guess the number! Round 1 (normal, 1 to 100)"
+            )
+        );
+        assert_eq!(lines[2], format!("Still possible: {secret} to 100"));
+        assert_eq!(lines[4], "Attempts: 1 used, 9 left");
+        assert_eq!(lines[7], format!("{:>6}  ^ Too small!", secret - 1));
+        assert_eq!(lines[21], "Your guess: 9_");
+        assert_eq!(lines[22], "Please type a number.");
+    }
+
+    #[test]
+    fn the_oldest_guesses_scroll_off_a_short_screen() {
+        let mut tui = tui();
+        let secret = tui.session().game().secret();
+        for guess in [100, 99, 98] {
+            if guess != secret {
+                type_line(&mut tui, &guess.to_string());
+            }
+        }
+        let lines = render(&tui, 80, 13);
+        // Two rows are left for the history: the last two guesses.
+        assert!(lines[7].ends_with("v Too big!"), "{lines:?}");
+        assert_eq!(lines[9], "");
+        assert_eq!(
+            lines[12],
+            "Enter: guess   n: new round   g: give up   q: quit"
+        );
+    }
+
+    #[test]
+    fn a_new_round_clears_the_screen_and_counts_the_old_one() {
+        let mut tui = tui();
+        tui.press(Key::Char('n'));
+        let lines = render(&tui, 80, 24);
+        assert!(lines[0].starts_with("Guess the number! Round 2"));
+        assert!(lines[0].ends_with("won 0 of 1, score 0"));
+        assert_eq!(lines[7], "  (no guesses yet)");
+        // Only rounds given up with g are finished; one left for a new round isn't.
+        assert!(tui.finished_rounds().is_empty());
+        tui.press(Key::Char('g'));
+        assert_eq!(tui.finished_rounds().len(), 1);
+    }
+
+    #[test]
+    fn speaks_the_catalogs_language() {
+        let session = Session::new(rng::seeded(1), Rules::default());
+        let mut tui = Tui::new(session, Difficulty::Normal, Catalog::new(Language::German));
+        let secret = tui.session().game().secret();
+        type_line(&mut tui, &(secret + 1).to_string());
+
+        let lines = render(&tui, 80, 24);
+        assert!(lines[0].starts_with("Errate die Zahl! Runde 1 (normal, 1 bis 100)"));
+        assert!(lines[0].ends_with("0 von 0 gewonnen, Punkte 0"));
+        assert_eq!(lines[4], "Versuche: 1 verbraucht, 9 übrig");
+        assert_eq!(lines[7], format!("{:>6}  v Zu groß!", secret + 1));
+        assert_eq!(lines[22], "Zu groß!");
+        assert_eq!(
+            lines[23],
+            "Enter: tippen   n: neue Runde   g: aufgeben   q: beenden"
+        );
+    }
+
+    #[test]
+    fn the_bar_marks_what_is_still_possible() {
+        let rules = Rules::unlimited(crate::Bounds::new(1, 10).unwrap());
+        let mut game = Game::new(rules, 8);
+        game.guess(5);
+        assert_eq!(bar(&game, 12), "[.....#####]");
+    }
+
+    #[test]
+    fn keys_are_read_from_raw_bytes() {
+        assert_eq!(Key::from_byte(b'7'), Some(Key::Char('7')));
+        assert_eq!(Key::from_byte(127), Some(Key::Backspace));
+        assert_eq!(Key::from_byte(3), Some(Key::Interrupt));
+        assert_eq!(Key::from_byte(0x1b), None);
+    }
+}
@@ -0,0 +1,369 @@
+// The guessing game with words: the secret is a word from a list, and each guess is answered with whether the secret comes earlier or later in the alphabet.
+//
+// Strings are Ord just like numbers, so `guess.cmp(&secret)` works unchanged; what takes care is making sure two spellings of the same word compare equal.
+// Every word, from the list and from the player, goes through `normalize` first: it is lowercased, and an accent typed as a separate combining mark
+// (e followed by U+0301) is joined to its letter (é), the way most keyboards produce it. `compare` then sorts accented letters with their plain ones,
+// as a dictionary does (cafe, café, cafes), instead of after z, where their code points would put them.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use rand::Rng;
+
+use crate::game::Status;
+
+/// The list used when no file is given.
+const BUNDLED: &str = include_str!("words.txt");
+
+/// The name the bundled list is reported under.
+pub const BUNDLED_NAME: &str = "bundled";
+
+// The accented letters `normalize` knows how to put together: each with its plain letter and the combining mark that goes on it.
+#[rustfmt::skip]
+const ACCENTED: &[(char, char, char)] = &[
+    ('à', 'a', '\u{300}'), ('á', 'a', '\u{301}'), ('â', 'a', '\u{302}'), ('ã', 'a', '\u{303}'), ('ä', 'a', '\u{308}'), ('å', 'a', '\u{30a}'),
+    ('è', 'e', '\u{300}'), ('é', 'e', '\u{301}'), ('ê', 'e', '\u{302}'), ('ë', 'e', '\u{308}'),
+    ('ì', 'i', '\u{300}'), ('í', 'i', '\u{301}'), ('î', 'i', '\u{302}'), ('ï', 'i', '\u{308}'),
+    ('ò', 'o', '\u{300}'), ('ó', 'o', '\u{301}'), ('ô', 'o', '\u{302}'), ('õ', 'o', '\u{303}'), ('ö', 'o', '\u{308}'),
+    ('ù', 'u', '\u{300}'), ('ú', 'u', '\u{301}'), ('û', 'u', '\u{302}'), ('ü', 'u', '\u{308}'),
+    ('ý', 'y', '\u{301}'), ('ÿ', 'y', '\u{308}'),
+    ('ñ', 'n', '\u{303}'), ('ç', 'c', '\u{327}'),
+];
+
+/// The word as the game compares it: trimmed, lowercased, and with combining accents joined to the letters they go on.
+///
+/// This covers the accented letters of the Western European languages; other combining marks are kept as they are, so such words still match only when typed the same way.
+pub fn normalize(word: &str) -> String {
+    let mut normalized = String::new();
+    for c in word.trim().to_lowercase().chars() {
+        let composed = normalized.chars().last().and_then(|previous| {
+            ACCENTED
+                .iter()
+                .find(|&&(_, base, mark)| base == previous && mark == c)
+                .map(|&(accented, _, _)| accented)
+        });
+        match composed {
+            Some(accented) => {
+                normalized.pop();
+                normalized.push(accented);
+            }
+            None => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+// The plain letter under an accent, or the character itself.
+fn unaccented(c: char) -> char {
+    ACCENTED
+        .iter()
+        .find(|&&(accented, _, _)| accented == c)
+        .map_or(c, |&(_, base, _)| base)
+}
+
+/// Dictionary order for two normalized words: letters compare without their accents first, and only words that are otherwise the same are ordered by accent.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .map(unaccented)
+        .cmp(b.chars().map(unaccented))
+        .then_with(|| a.cmp(b))
+}
+
+/// A list of words to draw secrets from, normalized and sorted with `compare`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordList {
+    name: String,
+    words: Vec<String>,
+}
+
+impl WordList {
+    /// The list that ships with the game.
+    pub fn bundled() -> WordList {
+        WordList::parse(BUNDLED_NAME, BUNDLED).expect("the bundled list is valid")
+    }
+
+    /// Reads a list from a file, named after the file.
+    pub fn load(path: &Path) -> Result<WordList, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("can't read {}: {error}", path.display()))?;
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        WordList::parse(&name, &text).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    /// Reads a list with one word per line. Blank lines and lines starting with `#` are skipped, and a word listed twice is kept once.
+    pub fn parse(name: &str, text: &str) -> Result<WordList, String> {
+        let mut words = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let word = normalize(line);
+            if word.chars().any(char::is_whitespace) {
+                return Err(format!(
+                    "line {}: '{line}' is more than one word",
+                    number + 1
+                ));
+            }
+            words.push(word);
+        }
+        words.sort_by(|a, b| compare(a, b));
+        words.dedup();
+        if words.len() < 2 {
+            return Err("a word list needs at least two different words".to_string());
+        }
+        Ok(WordList {
+            name: name.to_string(),
+            words,
+        })
+    }
+
+    /// What statistics are kept under: the file's name, or `BUNDLED_NAME`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Always false, since a list has at least two words; here because clippy expects it next to `len`.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Where a normalized word is in the list, if it's there at all.
+    pub fn position(&self, word: &str) -> Option<usize> {
+        self.words
+            .binary_search_by(|listed| compare(listed, word))
+            .ok()
+    }
+
+    /// The fewest guesses that always suffice: binary search over the sorted list, floor(log2(len)) + 1.
+    pub fn optimal_attempts(&self) -> u32 {
+        usize::BITS - self.words.len().leading_zeros()
+    }
+}
+
+/// Why a line of input was not accepted as a guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidWord {
+    Empty,
+    /// More than one word.
+    NotOneWord,
+    /// Not on the list, so it can't be the secret; holds the word as normalized.
+    Unknown(String),
+    Finished,
+}
+
+impl fmt::Display for InvalidWord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidWord::Empty => write!(f, "Please type a word."),
+            InvalidWord::NotOneWord => write!(f, "The secret is a single word."),
+            InvalidWord::Unknown(word) => {
+                write!(
+                    f,
+                    "'{word}' isn't on the word list, so it can't be the secret."
+                )
+            }
+            InvalidWord::Finished => write!(f, "This round is already over."),
+        }
+    }
+}
+
+/// One round of the word game.
+#[derive(Debug, Clone)]
+pub struct WordGame<'a> {
+    list: &'a WordList,
+    secret: usize,
+    max_attempts: Option<u32>,
+    /// Each guess with `guess.cmp(&secret)`: Less means the secret comes later in the alphabet.
+    guesses: Vec<(String, Ordering)>,
+    // The positions in the list still consistent with every answer, like low and high in `Game`.
+    low: usize,
+    high: usize,
+    status: Status,
+}
+
+impl<'a> WordGame<'a> {
+    /// Starts a round whose secret is the word at `secret` in the list, for tests and solvers.
+    pub fn new(list: &'a WordList, secret: usize, max_attempts: Option<u32>) -> WordGame<'a> {
+        assert!(secret < list.len(), "the secret must be on the list");
+        WordGame {
+            list,
+            secret,
+            max_attempts,
+            guesses: Vec::new(),
+            low: 0,
+            high: list.len() - 1,
+            status: Status::Playing,
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(
+        list: &'a WordList,
+        max_attempts: Option<u32>,
+        rng: &mut R,
+    ) -> WordGame<'a> {
+        let secret = rng.gen_range(0..list.len());
+        WordGame::new(list, secret, max_attempts)
+    }
+
+    /// Normalizes a line of input and answers it as a guess. A rejected guess doesn't count as an attempt.
+    pub fn submit(&mut self, input: &str) -> Result<Ordering, InvalidWord> {
+        if self.is_finished() {
+            return Err(InvalidWord::Finished);
+        }
+        let word = normalize(input);
+        if word.is_empty() {
+            return Err(InvalidWord::Empty);
+        }
+        if word.chars().any(char::is_whitespace) {
+            return Err(InvalidWord::NotOneWord);
+        }
+        let position = self
+            .list
+            .position(&word)
+            .ok_or_else(|| InvalidWord::Unknown(word.clone()))?;
+        Ok(self.guess_at(position))
+    }
+
+    // Answers a guess of the word at `position` in the list. Callers check that the round is still going and that the position is on the list.
+    fn guess_at(&mut self, position: usize) -> Ordering {
+        // Positions follow `compare`, so comparing them is comparing the words.
+        let ordering = position.cmp(&self.secret);
+        match ordering {
+            Ordering::Less => self.low = self.low.max(position + 1),
+            // position > secret >= 0, so this can't go below zero.
+            Ordering::Greater => self.high = self.high.min(position - 1),
+            Ordering::Equal => {
+                self.low = position;
+                self.high = position;
+            }
+        }
+        self.guesses
+            .push((self.list.words[position].clone(), ordering));
+        if ordering == Ordering::Equal {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+        ordering
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    pub fn list(&self) -> &WordList {
+        self.list
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.list.words[self.secret]
+    }
+
+    /// Every guess so far with its answer, in order.
+    pub fn guesses(&self) -> &[(String, Ordering)] {
+        &self.guesses
+    }
+
+    /// The words still consistent with every answer, in order.
+    pub fn remaining(&self) -> &[String] {
+        &self.list.words[self.low..=self.high]
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+/// Plays `game` to the end with binary search over the list, always guessing the middle word of those still possible, and returns how many guesses it took.
+///
+/// Needing more than `optimal_attempts` guesses means the game's answers were inconsistent, and is returned as an error.
+pub fn autoplay(game: &mut WordGame) -> Result<u32, String> {
+    let bound = game.list().optimal_attempts();
+    while !game.is_finished() {
+        let middle = game.low + (game.high - game.low) / 2;
+        game.guess_at(middle);
+        if game.attempts() > bound {
+            return Err(format!(
+                "binary search needed more than {bound} guesses, the most a list of {} words should take",
+                game.list().len()
+            ));
+        }
+    }
+    Ok(game.attempts())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combining_accents_are_joined_and_sorted_with_their_letters() {
+        assert_eq!(normalize(" Cafe\u{301} "), "café");
+        let list = WordList::parse("test", "cafes\ncafé\ncafe\n# a comment\n\nCAFE").unwrap();
+        assert_eq!(list.words(), ["cafe", "café", "cafes"]);
+    }
+
+    #[test]
+    fn guesses_must_be_one_word_on_the_list() {
+        let list = WordList::parse("test", "apple\nbanana\ncherry").unwrap();
+        let mut game = WordGame::new(&list, 1, None);
+        assert_eq!(game.submit(" "), Err(InvalidWord::Empty));
+        assert_eq!(game.submit("big apple"), Err(InvalidWord::NotOneWord));
+        assert_eq!(
+            game.submit("durian"),
+            Err(InvalidWord::Unknown("durian".to_string()))
+        );
+        assert_eq!(game.submit("Apple"), Ok(Ordering::Less));
+        assert_eq!(game.remaining(), ["banana", "cherry"]);
+        assert_eq!(game.submit("banana"), Ok(Ordering::Equal));
+        assert_eq!(game.submit("banana"), Err(InvalidWord::Finished));
+        assert_eq!(game.attempts(), 2);
+    }
+
+    #[test]
+    fn autoplay_finds_every_word_within_the_bound() {
+        let list = WordList::bundled();
+        for secret in 0..list.len() {
+            let mut game = WordGame::new(&list, secret, None);
+            assert!(autoplay(&mut game).unwrap() <= list.optimal_attempts());
+            assert_eq!(game.status(), Status::Won);
+        }
+    }
+
+    #[test]
+    fn autoplay_stops_when_the_attempts_run_out() {
+        let list = WordList::bundled();
+        let mut game = WordGame::new(&list, 0, Some(1));
+        assert_eq!(autoplay(&mut game), Ok(1));
+        assert_eq!(game.status(), Status::Lost);
+    }
+}